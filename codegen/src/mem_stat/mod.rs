@@ -114,14 +114,19 @@ fn gen_mem_fn_body(
 }
 
 pub fn expand(input: proc_macro2::TokenStream) -> Result<TokenStream> {
-    let input: DeriveInput = syn::parse2(input)?;
+    let mut input: DeriveInput = syn::parse2(input)?;
     let name = &input.ident;
 
     let heap = gen_heap_size_body(&input.data)?;
     let used = gen_used_size_body(&input.data)?;
 
+    for param in input.generics.type_params_mut() {
+        param.bounds.push(syn::parse_quote!(MemStat));
+    }
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
     Ok(quote! {
-        impl MemStat for #name {
+        impl #impl_generics MemStat for #name #ty_generics #where_clause {
             fn heap_size(&self) -> usize {
                 #heap
             }
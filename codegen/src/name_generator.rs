@@ -2,12 +2,17 @@ use convert_case::{Case, Casing};
 use proc_macro2::{Ident, Literal};
 use quote::__private::Span;
 
+/// Types which archived size depends on value.
+const VARIABLE_SIZE_TYPES: [&str; 8] = [
+    "String", "Vec", "VecDeque", "Box", "HashMap", "HashSet", "BTreeMap", "BTreeSet",
+];
+
+/// Checks whether type has variable size type anywhere inside (`String`,
+/// `Vec<u8>`, `Option<String>`, `[String; 2]` etc.), so its archived size
+/// depends on value.
 pub fn is_unsized(ty_: &str) -> bool {
-    matches!(ty_, "String")
-}
-
-pub fn is_unsized_vec(ty_: &[String]) -> bool {
-    ty_.iter().any(|v| matches!(v.as_str(), "String"))
+    ty_.split(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .any(|part| VARIABLE_SIZE_TYPES.contains(&part))
 }
 
 pub fn is_float(ty_: &str) -> bool {
@@ -48,6 +53,24 @@ impl WorktableNameGenerator {
         )
     }
 
+    pub fn get_column_value_type_ident(&self) -> Ident {
+        Ident::new(
+            format!("{}ColumnValue", self.name).as_str(),
+            Span::mixed_site(),
+        )
+    }
+
+    pub fn get_bitmap_filter_ident(&self) -> Ident {
+        Ident::new(
+            format!("{}BitmapFilter", self.name).as_str(),
+            Span::mixed_site(),
+        )
+    }
+
+    pub fn get_cursor_type_ident(&self) -> Ident {
+        Ident::new(format!("{}Cursor", self.name).as_str(), Span::mixed_site())
+    }
+
     pub fn get_available_type_ident(&self) -> Ident {
         Ident::new(
             format!("{}AvaiableTypes", self.name).as_str(),
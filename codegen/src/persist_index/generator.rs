@@ -1,16 +1,22 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use proc_macro2::{Ident, Literal, TokenStream};
 use quote::__private::Span;
 use quote::{ToTokens, quote};
-use syn::ItemStruct;
+use syn::{GenericArgument, ItemStruct, PathArguments, Type};
 
-use crate::name_generator::{WorktableNameGenerator, is_unsized};
+use crate::name_generator::WorktableNameGenerator;
 use crate::persist_table::WT_INDEX_EXTENSION;
 
 pub struct Generator {
     pub struct_def: ItemStruct,
     pub field_types: HashMap<Ident, TokenStream>,
+    pub unsized_fields: HashSet<Ident>,
+    /// Hash, bitmap and spatial indexes are not persisted, they are rebuilt
+    /// from rows on load.
+    pub rebuilt_fields: HashSet<Ident>,
+    /// Bitmap indexes share row ids, so they are created together.
+    pub bitmap_fields: HashSet<Ident>,
 }
 
 impl WorktableNameGenerator {
@@ -48,42 +54,81 @@ impl WorktableNameGenerator {
 
 impl Generator {
     pub fn new(struct_def: ItemStruct) -> Self {
-        let mut fields = vec![];
-        let mut types = vec![];
+        let mut field_types = HashMap::new();
+        let mut unsized_fields = HashSet::new();
+        let mut rebuilt_fields = HashSet::new();
+        let mut bitmap_fields = HashSet::new();
 
         for field in &struct_def.fields {
-            fields.push(
-                field
-                    .ident
-                    .clone()
-                    .expect("index fields should always be named fields"),
-            );
-            let index_type = field.ty.to_token_stream().to_string();
-            let mut split = index_type.split("<");
-            // skip `IndexMap` ident.
-            split.next();
-            let substr = split
+            let ident = field
+                .ident
+                .clone()
+                .expect("index fields should always be named fields");
+            let Type::Path(index_type) = &field.ty else {
+                panic!("index type should be a path")
+            };
+            let segment = index_type
+                .path
+                .segments
+                .last()
+                .expect("index type should not be empty");
+            if segment.ident == "IndexHashMap"
+                || segment.ident == "IndexHashMultiMap"
+                || segment.ident == "IndexBitmap"
+                || segment.ident == "IndexSpatial"
+            {
+                if segment.ident == "IndexBitmap" {
+                    bitmap_fields.insert(ident.clone());
+                }
+                rebuilt_fields.insert(ident);
+                continue;
+            }
+            let PathArguments::AngleBracketed(args) = &segment.arguments else {
+                panic!("index type should always have generics")
+            };
+            let mut types = args.args.iter().filter_map(|arg| match arg {
+                GenericArgument::Type(ty) => Some(ty.to_token_stream()),
+                _ => None,
+            });
+            let key_type = types
                 .next()
-                .expect("index type should always contain key generic")
-                .to_string();
-            types.push(
-                substr
-                    .split(",")
-                    .next()
-                    .expect("index type should always contain key and value generics")
-                    .to_string()
-                    .parse()
-                    .expect("should be valid because parsed from declaration"),
-            );
+                .expect("index type should always contain key generic");
+            // Node type is third generic, it is `UnsizedNode` for keys with variable size.
+            if types
+                .nth(1)
+                .is_some_and(|node| node.to_string().contains("UnsizedNode"))
+            {
+                unsized_fields.insert(ident.clone());
+            }
+            field_types.insert(ident, key_type);
         }
-        let map = fields.into_iter().zip(types).collect::<HashMap<_, _>>();
 
         Self {
             struct_def,
-            field_types: map,
+            field_types,
+            unsized_fields,
+            rebuilt_fields,
+            bitmap_fields,
         }
     }
 
+    /// Returns fields of the index which are persisted, so all except hash
+    /// and bitmap indexes.
+    fn persisted_fields(&self) -> impl Iterator<Item = &syn::Field> {
+        self.struct_def.fields.iter().filter(|f| {
+            !self.rebuilt_fields.contains(
+                f.ident
+                    .as_ref()
+                    .expect("index fields should always be named fields"),
+            )
+        })
+    }
+
+    /// Checks whether index uses `UnsizedNode`, so it's persisted as `UnsizedIndexPage`.
+    pub fn is_unsized(&self, field: &Ident) -> bool {
+        self.unsized_fields.contains(field)
+    }
+
     /// Generates persisted index type. This type has same name as index, but with `Persisted` postfix. Field names of
     /// this type are same to index type, and values are `Vec<GeneralPage<IndexPage<T>>>`, where `T` is index key
     /// type.
@@ -95,7 +140,7 @@ impl Generator {
             .field_types
             .iter()
             .map(|(i, t)| {
-                if is_unsized(&t.to_string()) {
+                if self.is_unsized(i) {
                     let const_size = name_generator.get_page_inner_size_const_ident();
                     quote! {
                         #i: (Vec<GeneralPage<TableOfContentsPage<(#t, Link)>>>, Vec<GeneralPage<UnsizedIndexPage<#t, {#const_size as u32}>>>),
@@ -138,9 +183,7 @@ impl Generator {
         let index_extension = Literal::string(WT_INDEX_EXTENSION);
 
         let persist_logic = self
-            .struct_def
-            .fields
-            .iter()
+            .persisted_fields()
             .map(|f| {
                 f.ident
                     .as_ref()
@@ -182,9 +225,7 @@ impl Generator {
         let index_extension = Literal::string(WT_INDEX_EXTENSION);
 
         let field_names_literals: Vec<_> = self
-            .struct_def
-            .fields
-            .iter()
+            .persisted_fields()
             .map(|f| (
                 Literal::string(
                     f.ident
@@ -216,9 +257,7 @@ impl Generator {
             .collect();
 
         let idents = self
-            .struct_def
-            .fields
-            .iter()
+            .persisted_fields()
             .map(|f| {
                 f.ident
                     .as_ref()
@@ -263,9 +302,7 @@ impl Generator {
         let const_name = name_generator.get_page_inner_size_const_ident();
 
         let idents = self
-            .struct_def
-            .fields
-            .iter()
+            .persisted_fields()
             .map(|f| {
                 f.ident
                     .as_ref()
@@ -273,9 +310,7 @@ impl Generator {
             })
             .collect::<Vec<_>>();
         let field_names_init: Vec<_> = self
-            .struct_def
-            .fields
-            .iter()
+            .persisted_fields()
             .map(|f| {
                 f.ident
                     .as_ref()
@@ -286,7 +321,7 @@ impl Generator {
                     .field_types
                     .get(i)
                     .expect("should be available as constructed from same values");
-                if is_unsized(&ty.to_string()) {
+                if self.is_unsized(i) {
                     quote! {
                         let mut pages = vec![];
                         for node in self.#i.iter_nodes() {
@@ -328,9 +363,7 @@ impl Generator {
         let const_name = name_generator.get_page_inner_size_const_ident();
 
         let idents = self
-            .struct_def
-            .fields
-            .iter()
+            .persisted_fields()
             .map(|f| {
                 f.ident
                     .as_ref()
@@ -338,9 +371,7 @@ impl Generator {
             })
             .collect::<Vec<_>>();
         let index_gen = self
-            .struct_def
-            .fields
-            .iter()
+            .persisted_fields()
             .map(|f| {
                 let i = f
                     .ident
@@ -361,7 +392,7 @@ impl Generator {
                     .get(i)
                     .expect("should be available as constructed from same values");
 
-                if is_unsized(&ty.to_string()) {
+                if self.is_unsized(i) {
                     let node = if is_unique {
                         quote! {
                             let node = UnsizedNode::from_inner(page.inner.get_node(), #const_name);
@@ -433,12 +464,24 @@ impl Generator {
             })
             .collect::<Vec<_>>();
 
+        let rebuilt_idents = self
+            .rebuilt_fields
+            .iter()
+            .filter(|i| !self.bitmap_fields.contains(*i));
+        let bitmap_idents = self.bitmap_fields.iter();
+        // Bitmap indexes share row ids, so their bitmaps can be intersected.
+        let row_ids = (!self.bitmap_fields.is_empty())
+            .then(|| quote! { let row_ids = std::sync::Arc::new(RowIds::default()); });
+
         Ok(quote! {
             fn from_persisted(persisted: Self::PersistedIndex) -> Self {
                 #(#index_gen)*
+                #row_ids
 
                 Self {
                     #(#idents,)*
+                    #(#rebuilt_idents: Default::default(),)*
+                    #(#bitmap_idents: IndexBitmap::with_row_ids(row_ids.clone()),)*
                 }
             }
         })
@@ -484,4 +527,74 @@ mod tests {
             "String"
         );
     }
+
+    #[test]
+    fn correctly_collects_generic_and_unsized_fields() {
+        let input = quote! {
+            #[derive(Debug, Default, Clone)]
+            pub struct TestIndex {
+                bytes_idx: IndexMap<Vec<u8>, Link>,
+                name_idx: IndexMultiMap<Name, Link, UnsizedNode<IndexMultiPair<Name, Link>>>
+            }
+        };
+        let struct_ = Parser::parse_struct(input).unwrap();
+        let generator = Generator::new(struct_);
+
+        let bytes_idx = Ident::new("bytes_idx", Span::call_site());
+        let name_idx = Ident::new("name_idx", Span::call_site());
+        assert_eq!(
+            generator.field_types.get(&bytes_idx).unwrap().to_string(),
+            "Vec < u8 >"
+        );
+        assert!(!generator.is_unsized(&bytes_idx));
+        assert_eq!(
+            generator.field_types.get(&name_idx).unwrap().to_string(),
+            "Name"
+        );
+        assert!(generator.is_unsized(&name_idx));
+    }
+
+    #[test]
+    fn skips_rebuilt_fields() {
+        let input = quote! {
+            #[derive(Debug, Default, Clone)]
+            pub struct TestIndex {
+                test_idx: IndexMap<i64, Link>,
+                uuid_idx: IndexHashMap<String, Link>,
+                order_idx: IndexHashMultiMap<u64, Link>,
+                status_idx: IndexBitmap<Status>,
+                location_idx: IndexSpatial
+            }
+        };
+        let struct_ = Parser::parse_struct(input).unwrap();
+        let generator = Generator::new(struct_);
+
+        assert_eq!(generator.field_types.len(), 1);
+        assert!(
+            generator
+                .rebuilt_fields
+                .contains(&Ident::new("uuid_idx", Span::call_site()))
+        );
+        assert!(
+            generator
+                .rebuilt_fields
+                .contains(&Ident::new("order_idx", Span::call_site()))
+        );
+        assert!(
+            generator
+                .rebuilt_fields
+                .contains(&Ident::new("status_idx", Span::call_site()))
+        );
+        assert!(
+            generator
+                .rebuilt_fields
+                .contains(&Ident::new("location_idx", Span::call_site()))
+        );
+        assert_eq!(generator.bitmap_fields.len(), 1);
+        assert!(
+            generator
+                .bitmap_fields
+                .contains(&Ident::new("status_idx", Span::call_site()))
+        );
+    }
 }
@@ -238,6 +238,12 @@ impl Generator {
                 }
             })
             .collect();
+        // Hash, bitmap and spatial indexes have no events.
+        let rebuilt_arm = if self.rebuilt_fields.is_empty() {
+            quote! {}
+        } else {
+            quote! { _ => false, }
+        };
 
         if fields_matches.is_empty() {
             quote! {
@@ -249,7 +255,8 @@ impl Generator {
             quote! {
                 fn is_first_ev_is_split(&self, index: #avt_index_ident) -> bool {
                     match index {
-                        #(#fields_matches),*
+                        #(#fields_matches,)*
+                        #rebuilt_arm
                     }
                 }
             }
@@ -273,6 +280,12 @@ impl Generator {
                 }
             })
             .collect();
+        // Hash, bitmap and spatial indexes have no events.
+        let rebuilt_arm = if self.rebuilt_fields.is_empty() {
+            quote! {}
+        } else {
+            quote! { _ => false, }
+        };
 
         if fields_matches.is_empty() {
             quote! {
@@ -284,7 +297,8 @@ impl Generator {
             quote! {
                 fn contains_event(&self, index: #avt_index_ident, id: IndexChangeEventId) -> bool {
                     match index {
-                        #(#fields_matches),*
+                        #(#fields_matches,)*
+                        #rebuilt_arm
                     }
                 }
             }
@@ -1,7 +1,7 @@
 use proc_macro2::{Literal, TokenStream};
 use quote::quote;
 
-use crate::name_generator::{WorktableNameGenerator, is_unsized};
+use crate::name_generator::WorktableNameGenerator;
 use crate::persist_index::generator::Generator;
 
 impl Generator {
@@ -14,7 +14,7 @@ impl Generator {
             .field_types
             .iter()
             .map(|(i, t)| {
-                if is_unsized(&t.to_string()) {
+                if self.is_unsized(i) {
                     quote! {
                         #i: SpaceIndexUnsized<#t, { #inner_const_name as u32}>,
                     }
@@ -57,10 +57,10 @@ impl Generator {
     fn gen_space_secondary_index_from_table_files_path_fn(&self) -> TokenStream {
         let fields: Vec<_> = self
             .field_types
-            .iter()
-            .map(|(i, t)| {
+            .keys()
+            .map(|i| {
                 let literal_name = Literal::string(i.to_string().as_str());
-                if is_unsized(&t.to_string()) {
+                if self.is_unsized(i) {
                     quote! {
                         #i: SpaceIndexUnsized::secondary_from_table_files_path(path, #literal_name).await?,
                     }
@@ -208,11 +208,13 @@ impl Generator {
                 let engine: #engine_ident = PersistenceEngine::from_table_files_path(path)
                                 .await
                                 .expect("should not panic as SpaceFile is ok");
-                #wt_ident(
+                let table = #wt_ident(
                     table,
                     config,
                     #task_ident::run_engine(engine)
-                )
+                );
+                table.rebuild_in_memory_indexes();
+                table
             }
         }
     }
@@ -6,6 +6,44 @@ use proc_macro2::{Ident, Literal, Span, TokenStream};
 use quote::quote;
 
 impl Generator {
+    /// Returns names of the indexes which are persisted, so have field in
+    /// events type.
+    fn persisted_index_idents(&self) -> Vec<&Ident> {
+        self.columns
+            .indexes
+            .values()
+            .filter(|idx| !idx.is_rebuilt_on_load())
+            .map(|idx| &idx.name)
+            .collect()
+    }
+
+    /// Hash, bitmap and spatial indexes produce no change events and have no
+    /// field in events type, so their (empty) events are only typed and
+    /// dropped.
+    fn gen_rebuilt_events_drop(&self) -> TokenStream {
+        let drops = self
+            .columns
+            .indexes
+            .values()
+            .filter(|idx| idx.is_rebuilt_on_load())
+            .filter_map(|idx| {
+                let t = self.columns.index_key_type(idx).ok()?;
+                let t = if is_float(t.to_string().as_str()) {
+                    quote! { OrderedFloat<#t> }
+                } else {
+                    t
+                };
+                let i = &idx.name;
+                Some(quote! {
+                    let _: Vec<IndexChangeEvent<IndexPair<#t, Link>>> = #i;
+                })
+            });
+
+        quote! {
+            #(#drops)*
+        }
+    }
+
     pub fn gen_secondary_index_cdc_impl_def(&mut self) -> TokenStream {
         let name_generator = WorktableNameGenerator::from_table_name(self.name.to_string());
         let index_type_ident = name_generator.get_index_type_ident();
@@ -48,6 +86,22 @@ impl Generator {
                     .from_case(Case::Snake)
                     .to_case(Case::Pascal);
                 let index_variant: TokenStream = camel_case_name.parse().unwrap();
+                if let Some(keys) = self.gen_row_index_keys(idx, quote! { row }) {
+                    return quote! {
+                        let mut #index_field_name = vec![];
+                        for key in #keys {
+                            if let Some(events) = TableIndexCdc::insert_checked_cdc(&self.#index_field_name, key, link) {
+                                #index_field_name.extend(events.into_iter().map(|ev| ev.into()).collect::<Vec<_>>());
+                            } else {
+                                return Err(IndexError::AlreadyExists {
+                                    at: #available_index_ident::#index_variant,
+                                    inserted_already: inserted_indexes.clone(),
+                                });
+                            }
+                        }
+                        inserted_indexes.push(#available_index_ident::#index_variant);
+                    };
+                }
 
                 quote! {
                     let #index_field_name = if let Some(events) = self.#index_field_name.insert_checked_cdc(row.#i.clone(), link) {
@@ -62,18 +116,15 @@ impl Generator {
                 }
             })
             .collect::<Vec<_>>();
-        let idents = self
-            .columns
-            .indexes
-            .values()
-            .map(|idx| &idx.name)
-            .collect::<Vec<_>>();
+        let idents = self.persisted_index_idents();
+        let rebuilt_events_drop = self.gen_rebuilt_events_drop();
 
         quote! {
             fn save_row_cdc(&self, row: #row_type_ident, link: Link) -> Result<#events_ident, IndexError<#available_index_ident>> {
                 let mut inserted_indexes: Vec<#available_index_ident> = vec![];
 
                 #(#save_rows)*
+                #rebuilt_events_drop
                 core::result::Result::Ok(
                     #events_ident {
                         #(#idents,)*
@@ -100,6 +151,57 @@ impl Generator {
                     .from_case(Case::Snake)
                     .to_case(Case::Pascal);
                 let index_variant: TokenStream = camel_case_name.parse().unwrap();
+                if let (Some(keys_new), Some(keys_old)) = (
+                    self.gen_row_index_keys(idx, quote! { row_new }),
+                    self.gen_row_index_keys(idx, quote! { row_old }),
+                ) {
+                    if !idx.is_unique {
+                        let insert = quote! {
+                            let mut #index_field_name = vec![];
+                        };
+                        let remove = quote! {
+                            for key in #keys_new {
+                                let events = self.#index_field_name.insert_cdc(key, link_new).1;
+                                #index_field_name.extend(events.into_iter().map(|ev| ev.into()).collect::<Vec<_>>());
+                            }
+                            for key in #keys_old {
+                                let (_, events) = TableIndexCdc::remove_cdc(&self.#index_field_name, key, link_old);
+                                #index_field_name.extend(events.into_iter().map(|ev| ev.into()).collect::<Vec<_>>());
+                            }
+                        };
+                        return (insert, remove);
+                    }
+                    let insert = quote! {
+                        let mut #index_field_name = vec![];
+                        if #keys_new != #keys_old {
+                            for key in #keys_new {
+                                if let Some(events) = self.#index_field_name.insert_checked_cdc(key, link_new) {
+                                    #index_field_name.extend(events.into_iter().map(|ev| ev.into()).collect::<Vec<_>>());
+                                } else {
+                                    return Err(IndexError::AlreadyExists {
+                                        at: #available_index_ident::#index_variant,
+                                        inserted_already: inserted_indexes.clone(),
+                                    });
+                                }
+                            }
+                            inserted_indexes.push(#available_index_ident::#index_variant);
+                        }
+                    };
+                    let remove = quote! {
+                        if #keys_new == #keys_old {
+                            for key in #keys_new {
+                                let events = self.#index_field_name.insert_cdc(key, link_new).1;
+                                #index_field_name.extend(events.into_iter().map(|ev| ev.into()).collect::<Vec<_>>());
+                            }
+                        } else {
+                            for key in #keys_old {
+                                let (_, events) = TableIndexCdc::remove_cdc(&self.#index_field_name, key, link_old);
+                                #index_field_name.extend(events.into_iter().map(|ev| ev.into()).collect::<Vec<_>>());
+                            }
+                        }
+                    };
+                    return (insert, remove);
+                }
 
                 let remove = if idx.is_unique {
                     quote! {
@@ -145,12 +247,8 @@ impl Generator {
                 (insert, remove)
             })
             .unzip();
-        let idents = self
-            .columns
-            .indexes
-            .values()
-            .map(|idx| &idx.name)
-            .collect::<Vec<_>>();
+        let idents = self.persisted_index_idents();
+        let rebuilt_events_drop = self.gen_rebuilt_events_drop();
 
         quote! {
             fn reinsert_row_cdc(
@@ -164,6 +262,7 @@ impl Generator {
 
                 #(#insert_rows)*
                 #(#remove_rows)*
+                #rebuilt_events_drop
                 core::result::Result::Ok(
                     #events_ident {
                         #(#idents,)*
@@ -185,22 +284,28 @@ impl Generator {
             .iter()
             .map(|(i, idx)| {
                 let index_field_name = &idx.name;
+                if let Some(keys) = self.gen_row_index_keys(idx, quote! { row }) {
+                    return quote! {
+                        let mut #index_field_name = vec![];
+                        for key in #keys {
+                            let (_, events) = TableIndexCdc::remove_cdc(&self.#index_field_name, key, link);
+                            #index_field_name.extend(events.into_iter().map(|ev| ev.into()).collect::<Vec<_>>());
+                        }
+                    };
+                }
                 quote! {
                     let (_, events) = TableIndexCdc::remove_cdc(&self.#index_field_name, row.#i, link);
                     let #index_field_name = events.into_iter().map(|ev| ev.into()).collect();
                 }
             })
             .collect::<Vec<_>>();
-        let idents = self
-            .columns
-            .indexes
-            .values()
-            .map(|idx| &idx.name)
-            .collect::<Vec<_>>();
+        let idents = self.persisted_index_idents();
+        let rebuilt_events_drop = self.gen_rebuilt_events_drop();
 
         quote! {
             fn delete_row_cdc(&self, row: #row_type_ident, link: Link) -> Result<#events_ident, IndexError<#available_index_ident>> {
                 #(#delete_rows)*
+                #rebuilt_events_drop
                 core::result::Result::Ok(
                     #events_ident {
                         #(#idents,)*
@@ -219,8 +324,37 @@ impl Generator {
         let process_difference_rows = self.columns.indexes.iter().map(|(i, idx)| {
             let index_field_name = &idx.name;
             let diff_key = Literal::string(i.to_string().as_str());
+            if idx.is_partial() {
+                // Updates of partial index columns reinsert the row.
+                return quote! {
+                    let #index_field_name = vec![];
+                };
+            }
 
-            if let Some(t) = self.columns.columns_map.get(&idx.field) {
+            if let (Some(keys_old), Some(keys_new)) = (
+                self.gen_index_keys(idx, quote! { old }),
+                self.gen_index_keys(idx, quote! { new }),
+            ) {
+                let type_str = self.columns.index_value_type(idx).expect("index has value type").to_string();
+                let variant_ident = Ident::new(&map_to_uppercase(&type_str), Span::mixed_site());
+                return quote! {
+                    let #index_field_name = if let Some(diff) = difference.get(#diff_key) {
+                        let mut events = vec![];
+                        if let (#avt_type_ident::#variant_ident(old), #avt_type_ident::#variant_ident(new)) = (&diff.old, &diff.new) {
+                            let keys_new = #keys_new;
+                            for key in #keys_old.difference(&keys_new) {
+                                let (_, evs) = TableIndexCdc::remove_cdc(&self.#index_field_name, key.clone(), link);
+                                events.extend_from_slice(evs.as_ref());
+                            }
+                        }
+                        events
+                    } else {
+                        vec![]
+                    };
+                };
+            }
+
+            if let Some(t) = self.columns.index_value_type(idx) {
                 let type_str = t.to_string();
                 let variant_ident = Ident::new(&map_to_uppercase(&type_str), Span::mixed_site());
 
@@ -229,7 +363,7 @@ impl Generator {
                 } else if is_float(type_str.as_str()) {
                     quote! { OrderedFloat(*old) }
                 } else {
-                    quote! { *old }
+                    quote! { old.clone() }
                 };
 
                 quote! {
@@ -249,12 +383,8 @@ impl Generator {
                 quote! {}
             }
         });
-        let idents = self
-            .columns
-            .indexes
-            .values()
-            .map(|idx| &idx.name)
-            .collect::<Vec<_>>();
+        let idents = self.persisted_index_idents();
+        let rebuilt_events_drop = self.gen_rebuilt_events_drop();
 
         quote! {
             fn process_difference_remove_cdc(
@@ -263,6 +393,7 @@ impl Generator {
                 difference: std::collections::HashMap<&str, Difference<#avt_type_ident>>
             ) -> Result<#events_ident, IndexError<#available_index_ident>> {
                 #(#process_difference_rows)*
+                #rebuilt_events_drop
                 core::result::Result::Ok(
                     #events_ident {
                         #(#idents,)*
@@ -278,11 +409,56 @@ impl Generator {
         let available_index_ident = name_generator.get_available_indexes_ident();
         let events_ident = name_generator.get_space_secondary_index_events_ident();
 
-        let process_difference_insert_rows = self.columns.indexes.iter().map(|(i, idx)| {
+        let process_difference_insert_rows = self.indexes_with_array_last().into_iter().map(|(i, idx)| {
             let index_field_name = &idx.name;
             let diff_key = Literal::string(i.to_string().as_str());
+            if idx.is_partial() {
+                // Updates of partial index columns reinsert the row.
+                return quote! {
+                    let #index_field_name = vec![];
+                };
+            }
+
+            if let (Some(keys_old), Some(keys_new)) = (
+                self.gen_index_keys(idx, quote! { old }),
+                self.gen_index_keys(idx, quote! { new }),
+            ) {
+                let type_str = self.columns.index_value_type(idx).expect("index has value type").to_string();
+                let variant_ident = Ident::new(&map_to_uppercase(&type_str), Span::mixed_site());
+                let camel_case_name = index_field_name
+                    .to_string()
+                    .from_case(Case::Snake)
+                    .to_case(Case::Pascal);
+                let index_variant: TokenStream = camel_case_name.parse().unwrap();
+                return quote! {
+                    let #index_field_name = if let Some(diff) = difference.get(#diff_key) {
+                        let mut events = vec![];
+                        if let (#avt_type_ident::#variant_ident(old), #avt_type_ident::#variant_ident(new)) = (&diff.old, &diff.new) {
+                            let keys_old = #keys_old;
+                            let keys_new = #keys_new;
+                            let added: Vec<_> = keys_new.difference(&keys_old).collect();
+                            for &key in &added {
+                                if let Some(evs) = TableIndexCdc::insert_checked_cdc(&self.#index_field_name, key.clone(), link) {
+                                    events.extend_from_slice(evs.as_ref());
+                                } else {
+                                    return Err(IndexError::AlreadyExists {
+                                        at: #available_index_ident::#index_variant,
+                                        inserted_already: inserted_indexes.clone(),
+                                    });
+                                }
+                            }
+                            if !added.is_empty() {
+                                inserted_indexes.push(#available_index_ident::#index_variant);
+                            }
+                        }
+                        events
+                    } else {
+                        vec![]
+                    };
+                };
+            }
 
-            if let Some(t) = self.columns.columns_map.get(&idx.field) {
+            if let Some(t) = self.columns.index_value_type(idx) {
                 let type_str = t.to_string();
                 let variant_ident = Ident::new(&map_to_uppercase(&type_str), Span::mixed_site());
                 let camel_case_name = index_field_name
@@ -296,7 +472,7 @@ impl Generator {
                 } else if is_float(type_str.as_str()) {
                     quote! { OrderedFloat(*new) }
                 } else {
-                    quote! { *new }
+                    quote! { new.clone() }
                 };
 
                 quote! {
@@ -323,12 +499,8 @@ impl Generator {
                 quote! {}
             }
         });
-        let idents = self
-            .columns
-            .indexes
-            .values()
-            .map(|idx| &idx.name)
-            .collect::<Vec<_>>();
+        let idents = self.persisted_index_idents();
+        let rebuilt_events_drop = self.gen_rebuilt_events_drop();
 
         quote! {
             fn process_difference_insert_cdc(
@@ -339,6 +511,7 @@ impl Generator {
                 let mut inserted_indexes: Vec<#available_index_ident> = vec![];
 
                 #(#process_difference_insert_rows)*
+                #rebuilt_events_drop
                 core::result::Result::Ok(
                     #events_ident {
                         #(#idents,)*
@@ -25,7 +25,48 @@ impl Generator {
             let index_field_name = &idx.name;
             let index_name_str = index_field_name.to_string();
 
-            if idx.is_unique {
+            if idx.is_spatial {
+                quote! {
+                    info.push(IndexInfo {
+                        name: #index_name_str.to_string(),
+                        index_type: IndexKind::Spatial,
+                        key_count: self.#index_field_name.len(),
+                        capacity: self.#index_field_name.capacity(),
+                        heap_size: self.#index_field_name.heap_size(),
+                        used_size: self.#index_field_name.used_size(),
+                        node_count: self.#index_field_name.node_count(),
+                    });
+                }
+            } else if idx.is_bitmap {
+                quote! {
+                    info.push(IndexInfo {
+                        name: #index_name_str.to_string(),
+                        index_type: IndexKind::Bitmap,
+                        key_count: self.#index_field_name.len(),
+                        capacity: self.#index_field_name.capacity(),
+                        heap_size: self.#index_field_name.heap_size(),
+                        used_size: self.#index_field_name.used_size(),
+                        node_count: self.#index_field_name.value_count(),
+                    });
+                }
+            } else if idx.is_hash {
+                let kind = if idx.is_unique {
+                    quote! { IndexKind::UniqueHash }
+                } else {
+                    quote! { IndexKind::NonUniqueHash }
+                };
+                quote! {
+                    info.push(IndexInfo {
+                        name: #index_name_str.to_string(),
+                        index_type: #kind,
+                        key_count: self.#index_field_name.len(),
+                        capacity: self.#index_field_name.capacity(),
+                        heap_size: self.#index_field_name.heap_size(),
+                        used_size: self.#index_field_name.used_size(),
+                        node_count: self.#index_field_name.shard_count(),
+                    });
+                }
+            } else if idx.is_unique {
                 quote! {
                     info.push(IndexInfo {
                         name: #index_name_str.to_string(),
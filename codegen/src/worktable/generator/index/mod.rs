@@ -1,14 +1,133 @@
 mod cdc;
 mod info;
+mod row_expr;
 mod usual;
 
 use crate::name_generator::{WorktableNameGenerator, is_float, is_unsized};
 use crate::worktable::generator::Generator;
+use crate::worktable::model::Index;
 use convert_case::{Case, Casing};
-use proc_macro2::TokenStream;
+use proc_macro2::{Ident, TokenStream};
 use quote::quote;
 
 impl Generator {
+    /// Returns indexes with array indexes at the end. Array index insert
+    /// can't fail, so if it's done after all other indexes, it never needs to
+    /// be rolled back.
+    fn indexes_with_array_last(&self) -> Vec<(&Ident, &Index)> {
+        let mut indexes: Vec<_> = self.columns.indexes.iter().collect();
+        indexes.sort_by_key(|(_, idx)| idx.is_array);
+        indexes
+    }
+
+    /// Generates set of index keys of the column value for indexes which keys
+    /// are not just column values. Array index has key for each distinct
+    /// element, index on optional column has no key for `None` and `ci` index
+    /// has lowercased key. Partial index keys are got the same way, but they
+    /// are used only if predicate holds, see [`Self::gen_row_index_keys`].
+    /// Returns `None` for other indexes.
+    pub fn gen_index_keys(&self, idx: &Index, value: TokenStream) -> Option<TokenStream> {
+        self.has_key_set(idx)
+            .then(|| self.gen_filtered_index_keys(idx, value, quote! {}))
+    }
+
+    fn has_key_set(&self, idx: &Index) -> bool {
+        idx.is_array || idx.is_ci || idx.is_partial() || self.columns.is_optional(&idx.field)
+    }
+
+    /// Generates set of index keys of the `row`. It's same as
+    /// [`Self::gen_index_keys`] of the column value, but partial index has no
+    /// keys for rows which don't match its predicate and computed index key
+    /// is got from the whole row.
+    pub fn gen_row_index_keys(&self, idx: &Index, row: TokenStream) -> Option<TokenStream> {
+        let field = &idx.field;
+        let filter = if let Some(predicate) = self.gen_index_predicate(idx, &row) {
+            quote! { .filter(|_| #predicate) }
+        } else {
+            quote! {}
+        };
+        if let Some(key) = self.gen_computed_key(idx, &row) {
+            Some(self.gen_filtered_index_keys(idx, key, filter))
+        } else if self.has_key_set(idx) {
+            Some(self.gen_filtered_index_keys(idx, quote! { #row.#field }, filter))
+        } else {
+            None
+        }
+    }
+
+    fn gen_filtered_index_keys(
+        &self,
+        idx: &Index,
+        value: TokenStream,
+        filter: TokenStream,
+    ) -> TokenStream {
+        let is_optional = self.columns.is_optional(&idx.field);
+        let is_float_key = self
+            .columns
+            .index_key_type(idx)
+            .is_ok_and(|t| is_float(t.to_string().as_str()));
+        let values = if idx.is_array || is_optional {
+            quote! { #value.iter() }
+        } else {
+            quote! { std::iter::once(&#value) }
+        };
+        let key = if is_float_key {
+            quote! { .map(|v| OrderedFloat(*v)) }
+        } else if idx.is_ci {
+            quote! { .map(|v| v.to_lowercase()) }
+        } else {
+            quote! { .cloned() }
+        };
+        // Spatial index keys are float points, which have no order.
+        let collection = if idx.is_spatial {
+            quote! { Vec<_> }
+        } else {
+            quote! { std::collections::BTreeSet<_> }
+        };
+        quote! {
+            #values #filter #key.collect::<#collection>()
+        }
+    }
+
+    /// Generates expression which returns `Vec<Link>` of the rows with `by`
+    /// key in index, unique or not.
+    pub fn gen_index_links(idx: &Index, by: &TokenStream) -> TokenStream {
+        if idx.is_unique {
+            let index_field = &idx.name;
+            let link = if idx.is_hash {
+                quote! { self.0.indexes.#index_field.get(#by) }
+            } else {
+                quote! { self.0.indexes.#index_field.get(#by).map(|kv| kv.get().value) }
+            };
+            quote! { #link.into_iter().collect::<Vec<_>>() }
+        } else {
+            Self::gen_non_unique_index_links(idx, by)
+        }
+    }
+
+    /// Generates expression which returns `Vec<Link>` of the rows with `by`
+    /// key in non-unique index.
+    pub fn gen_non_unique_index_links(idx: &Index, by: &TokenStream) -> TokenStream {
+        let index_field = &idx.name;
+        if idx.is_hash {
+            quote! { self.0.indexes.#index_field.get(#by) }
+        } else if idx.is_bitmap {
+            quote! {
+                {
+                    let index = &self.0.indexes.#index_field;
+                    index.links(&index.get(#by))
+                }
+            }
+        } else {
+            quote! {
+                self.0.indexes.#index_field
+                    .get(#by)
+                    .map(|(_, link)| *link)
+                    .collect::<Vec<_>>()
+            }
+        }
+    }
+
     /// Generates index type and it's impls.
     pub fn gen_index_def(&mut self) -> syn::Result<TokenStream> {
         let type_def = self.gen_type_def()?;
@@ -34,6 +153,8 @@ impl Generator {
 
     /// Generates table's secondary index struct definition. It has fields with index names and types varying on index
     /// uniqueness. For unique index it's `TreeIndex<T, Link`, for non-unique `TreeIndex<T, Arc<LockFreeSet<Link>>>`.
+    /// Array index is non-unique index keyed by collection column elements. `hash` index is `IndexHashMap` or
+    /// `IndexHashMultiMap`, `spatial` index is `IndexSpatial`.
     /// Index also derives `PersistIndex` and `MemStat` macro.
     fn gen_type_def(&mut self) -> syn::Result<TokenStream> {
         let name_generator = WorktableNameGenerator::from_table_name(self.name.to_string());
@@ -43,12 +164,60 @@ impl Generator {
             .indexes
             .iter()
             .map(|(i, idx)| {
-                let Some(t) = self.columns.columns_map.get(i) else {
+                let t = self.columns.index_key_type(idx)?;
+                let unsized_key = self.columns.is_index_key_unsized(idx);
+                if (idx.is_ci || idx.is_prefix) && t.to_string() != "String" {
+                    return Err(syn::Error::new(
+                        idx.name.span(),
+                        format!("`ci` and `prefix` index `{}` needs `String` keys, but has `{t}`", idx.name),
+                    ));
+                }
+                if idx.is_computed() && self.columns.columns_map.contains_key(&idx.name) {
+                    return Err(syn::Error::new(
+                        idx.name.span(),
+                        format!("computed index `{}` can't be named as column", idx.name),
+                    ));
+                }
+                let spatial_columns = self.computed_index_columns(idx);
+                if idx.is_spatial
+                    && (spatial_columns.len() != 2
+                        || spatial_columns
+                            .iter()
+                            .any(|c| self.columns.columns_map[c].to_string() != "f64"))
+                {
+                    return Err(syn::Error::new(
+                        idx.name.span(),
+                        format!("`spatial` index `{}` needs two different `f64` columns", idx.name),
+                    ));
+                }
+                if idx.is_bitmap && self.columns.is_optional(&idx.field) {
+                    return Err(syn::Error::new(
+                        idx.name.span(),
+                        format!("`bitmap` index `{}` can't be used for optional column", idx.name),
+                    ));
+                }
+                if idx.is_array && is_float(t.to_string().as_str()) {
                     return Err(syn::Error::new(
-                        i.span(),
-                        format!("cannot find column `{i}` in this table"),
+                        idx.name.span(),
+                        format!("`array` index `{}` can't be used for float elements", idx.name),
                     ));
+                }
+                let is_unsized = if idx.is_array || idx.is_computed() {
+                    is_unsized(t.to_string().as_str())
+                } else {
+                    self.columns.is_unsized(i)
                 };
+                if self.is_persist && is_unsized && !unsized_key && !idx.is_rebuilt_on_load() {
+                    return Err(syn::Error::new(
+                        idx.name.span(),
+                        format!(
+                            "index `{}` key type `{t}` has variable size, so it can't be persisted using sized \
+                            index pages. Use a type which implements `VariableSizeMeasurable` and mark \
+                            column `{i}` with `unsized` flag",
+                            idx.name
+                        ),
+                    ));
+                }
                 let t = if is_float(t.to_string().as_str()) {
                     quote! { OrderedFloat<#t> }
                 } else {
@@ -57,8 +226,18 @@ impl Generator {
                 let i = &idx.name;
 
                 #[allow(clippy::collapsible_else_if)]
-                let res = if idx.is_unique {
-                    if is_unsized(&t.to_string()) {
+                let res = if idx.is_spatial {
+                    quote! {#i: IndexSpatial}
+                } else if idx.is_bitmap {
+                    quote! {#i: IndexBitmap<#t>}
+                } else if idx.is_hash {
+                    if idx.is_unique {
+                        quote! {#i: IndexHashMap<#t, Link>}
+                    } else {
+                        quote! {#i: IndexHashMultiMap<#t, Link>}
+                    }
+                } else if idx.is_unique {
+                    if unsized_key {
                         quote! {
                             #i: IndexMap<#t, Link, UnsizedNode<IndexPair<#t, Link>>>
                         }
@@ -66,7 +245,7 @@ impl Generator {
                         quote! {#i: IndexMap<#t, Link>}
                     }
                 } else {
-                    if is_unsized(&t.to_string()) {
+                    if unsized_key {
                         quote! {#i: IndexMultiMap<#t, Link, UnsizedNode<IndexMultiPair<#t, Link>>>}
                     } else {
                         quote! {#i: IndexMultiMap<#t, Link>}
@@ -102,24 +281,24 @@ impl Generator {
         let index_rows = self
             .columns
             .indexes
-            .iter()
-            .map(|(i, idx)| {
-                let Some(t) = self.columns.columns_map.get(i) else {
-                    return Err(syn::Error::new(
-                        i.span(),
-                        format!("cannot find column `{i}` in this table"),
-                    ));
-                };
+            .values()
+            .map(|idx| {
+                let t = self.columns.index_key_type(idx)?;
                 let t = if is_float(t.to_string().as_str()) {
                     quote! { OrderedFloat<#t> }
                 } else {
                     quote! { #t }
                 };
+                let unsized_key = self.columns.is_index_key_unsized(idx);
                 let i = &idx.name;
 
                 #[allow(clippy::collapsible_else_if)]
-                let res = if idx.is_unique {
-                    if is_unsized(&t.to_string()) {
+                let res = if idx.is_bitmap {
+                    quote! {#i: IndexBitmap::with_row_ids(row_ids.clone()),}
+                } else if idx.is_rebuilt_on_load() {
+                    quote! {#i: Default::default(),}
+                } else if idx.is_unique {
+                    if unsized_key {
                         quote! {
                             #i: IndexMap::with_maximum_node_size(#const_name),
                         }
@@ -127,7 +306,7 @@ impl Generator {
                         quote! {#i: IndexMap::with_maximum_node_size(get_index_page_size_from_data_length::<#t>(#const_name)),}
                     }
                 } else {
-                    if is_unsized(&t.to_string()) {
+                    if unsized_key {
                         quote! {#i: IndexMultiMap::with_maximum_node_size(#const_name), }
                     } else {
                         quote! {#i: IndexMultiMap::with_maximum_node_size(get_index_page_size_from_data_length::<#t>(#const_name)),}
@@ -138,9 +317,18 @@ impl Generator {
             })
             .collect::<Result<Vec<_>, syn::Error>>()?;
 
+        // Bitmap indexes share row ids, so their bitmaps can be intersected.
+        let row_ids = self
+            .columns
+            .indexes
+            .values()
+            .any(|idx| idx.is_bitmap)
+            .then(|| quote! { let row_ids = std::sync::Arc::new(RowIds::default()); });
+
         Ok(quote! {
             impl Default for #index_type_ident {
                 fn default() -> Self {
+                    #row_ids
                     Self {
                         #(#index_rows)*
                     }
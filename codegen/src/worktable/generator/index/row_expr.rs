@@ -0,0 +1,176 @@
+use std::collections::HashSet;
+
+use proc_macro2::{Ident, TokenStream};
+use quote::quote;
+use syn::{BinOp, Expr, parse_quote};
+
+use crate::worktable::generator::Generator;
+use crate::worktable::model::{Columns, Index};
+
+/// Column types which values can't be named by bare identifier, so bare
+/// identifiers compared with them are left as is (constants).
+const PRIMITIVE_TYPES: &[&str] = &[
+    "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64", "i128", "isize", "f32",
+    "f64", "bool", "char", "String",
+];
+
+impl Generator {
+    /// Generates predicate of the partial index for `row`. Column names in
+    /// predicate are replaced with `row` fields and bare identifiers compared
+    /// with column of non-primitive type are resolved in this type, so
+    /// `status == Open` becomes `row.status == Status::Open`. Returns `None`
+    /// for usual index.
+    pub fn gen_index_predicate(&self, idx: &Index, row: &TokenStream) -> Option<TokenStream> {
+        let (expr, _) = rewrite_row_expr(idx.predicate.as_ref()?, &self.columns, row);
+        Some(quote! { (#expr) })
+    }
+
+    /// Generates key of the computed index for `row`. Columns are named in
+    /// expression the same way as in partial index predicate. Returns `None`
+    /// for usual index.
+    pub fn gen_computed_key(&self, idx: &Index, row: &TokenStream) -> Option<TokenStream> {
+        let (expr, _) = rewrite_row_expr(idx.expr.as_ref()?, &self.columns, row);
+        Some(quote! { (#expr) })
+    }
+
+    /// Returns columns which are used in computed index expression, so their
+    /// updates change index key.
+    pub fn computed_index_columns(&self, idx: &Index) -> HashSet<Ident> {
+        idx.expr
+            .as_ref()
+            .map(|expr| rewrite_row_expr(expr, &self.columns, &quote! { row }).1)
+            .unwrap_or_default()
+    }
+
+    /// Returns columns which values decide whether row is in some partial
+    /// index and which keys it has there. Updates of these columns must
+    /// reinsert the row, as index change can't be got from the difference
+    /// of the updated columns only.
+    pub fn partial_index_columns(&self) -> HashSet<Ident> {
+        self.columns
+            .indexes
+            .values()
+            .filter_map(|idx| {
+                let (_, mut columns) =
+                    rewrite_row_expr(idx.predicate.as_ref()?, &self.columns, &quote! { row });
+                if idx.is_computed() {
+                    columns.extend(self.computed_index_columns(idx));
+                } else {
+                    columns.insert(idx.field.clone());
+                }
+                Some(columns)
+            })
+            .flatten()
+            .collect()
+    }
+}
+
+/// Returns expression over row columns rewritten for `row` and columns which
+/// are used in it.
+fn rewrite_row_expr(
+    tokens: &TokenStream,
+    columns: &Columns,
+    row: &TokenStream,
+) -> (Expr, HashSet<Ident>) {
+    let mut expr: Expr = syn::parse2(tokens.clone()).expect("expression is checked on parse");
+    let mut used = HashSet::new();
+    rewrite_expr(&mut expr, columns, row, &mut used);
+    (expr, used)
+}
+
+/// Returns column which is named by the single identifier path.
+fn column_of<'a>(expr: &Expr, columns: &'a Columns) -> Option<&'a Ident> {
+    let Expr::Path(path) = expr else {
+        return None;
+    };
+    let ident = path.path.get_ident()?;
+    columns.columns_map.get_key_value(ident).map(|(i, _)| i)
+}
+
+/// Replaces bare identifier compared with `column` by path in column's type,
+/// if column has non-primitive type.
+fn resolve_value(column: &Ident, value: &mut Expr, columns: &Columns) {
+    let Expr::Path(path) = value else {
+        return;
+    };
+    let Some(ident) = path.path.get_ident() else {
+        return;
+    };
+    if columns.columns_map.contains_key(ident) {
+        return;
+    }
+    let Ok(syn::Type::Path(ty)) = syn::parse2::<syn::Type>(columns.columns_map[column].clone())
+    else {
+        return;
+    };
+    let is_plain = ty.qself.is_none()
+        && ty
+            .path
+            .segments
+            .iter()
+            .all(|s| matches!(s.arguments, syn::PathArguments::None));
+    let is_primitive = ty
+        .path
+        .get_ident()
+        .is_some_and(|i| PRIMITIVE_TYPES.contains(&i.to_string().as_str()));
+    if !is_plain || is_primitive {
+        return;
+    }
+    let ty = &ty.path;
+    *value = parse_quote! { #ty::#ident };
+}
+
+fn rewrite_expr(expr: &mut Expr, columns: &Columns, row: &TokenStream, used: &mut HashSet<Ident>) {
+    if let Some(column) = column_of(expr, columns) {
+        used.insert(column.clone());
+        *expr = parse_quote! { #row.#column };
+        return;
+    }
+    match expr {
+        Expr::Binary(binary) => {
+            if matches!(
+                binary.op,
+                BinOp::Eq(_)
+                    | BinOp::Ne(_)
+                    | BinOp::Lt(_)
+                    | BinOp::Le(_)
+                    | BinOp::Gt(_)
+                    | BinOp::Ge(_)
+            ) {
+                if let Some(column) = column_of(&binary.left, columns) {
+                    resolve_value(column, &mut binary.right, columns);
+                } else if let Some(column) = column_of(&binary.right, columns) {
+                    resolve_value(column, &mut binary.left, columns);
+                }
+            }
+            rewrite_expr(&mut binary.left, columns, row, used);
+            rewrite_expr(&mut binary.right, columns, row, used);
+        }
+        Expr::Unary(unary) => rewrite_expr(&mut unary.expr, columns, row, used),
+        Expr::Paren(paren) => rewrite_expr(&mut paren.expr, columns, row, used),
+        Expr::Group(group) => rewrite_expr(&mut group.expr, columns, row, used),
+        Expr::Reference(reference) => rewrite_expr(&mut reference.expr, columns, row, used),
+        Expr::Field(field) => rewrite_expr(&mut field.base, columns, row, used),
+        Expr::Index(index) => {
+            rewrite_expr(&mut index.expr, columns, row, used);
+            rewrite_expr(&mut index.index, columns, row, used);
+        }
+        Expr::MethodCall(call) => {
+            rewrite_expr(&mut call.receiver, columns, row, used);
+            for arg in call.args.iter_mut() {
+                rewrite_expr(arg, columns, row, used);
+            }
+        }
+        Expr::Tuple(tuple) => {
+            for elem in tuple.elems.iter_mut() {
+                rewrite_expr(elem, columns, row, used);
+            }
+        }
+        Expr::Call(call) => {
+            for arg in call.args.iter_mut() {
+                rewrite_expr(arg, columns, row, used);
+            }
+        }
+        _ => {}
+    }
+}
@@ -52,15 +52,26 @@ impl Generator {
                     .from_case(Case::Snake)
                     .to_case(Case::Pascal);
                 let index_variant: TokenStream = camel_case_name.parse().unwrap();
+                if let Some(keys) = self.gen_row_index_keys(idx, quote! { row }) {
+                    return quote! {
+                        for key in #keys {
+                            if TableIndex::insert_checked(&self.#index_field_name, key, link).is_none() {
+                                return Err(IndexError::AlreadyExists {
+                                    at: #available_index_ident::#index_variant,
+                                    inserted_already: inserted_indexes.clone(),
+                                })
+                            }
+                        }
+                        inserted_indexes.push(#available_index_ident::#index_variant);
+                    };
+                }
 
-                let row = if is_float(
-                    self.columns
-                        .columns_map
-                        .get(i)
-                        .unwrap()
-                        .to_string()
-                        .as_str(),
-                ) {
+                let row = if self
+                    .columns
+                    .columns_map
+                    .get(i)
+                    .is_some_and(|t| is_float(t.to_string().as_str()))
+                {
                     quote! {
                         OrderedFloat(row.#i)
                     }
@@ -106,14 +117,53 @@ impl Generator {
                     .from_case(Case::Snake)
                     .to_case(Case::Pascal);
                 let index_variant: TokenStream = camel_case_name.parse().unwrap();
-                let row = if is_float(
-                    self.columns
-                        .columns_map
-                        .get(i)
-                        .unwrap()
-                        .to_string()
-                        .as_str(),
+                if let (Some(keys_new), Some(keys_old)) = (
+                    self.gen_row_index_keys(idx, quote! { row_new }),
+                    self.gen_row_index_keys(idx, quote! { row_old }),
                 ) {
+                    if !idx.is_unique {
+                        let remove = quote! {
+                            for key in #keys_new {
+                                self.#index_field_name.insert(key, link_new);
+                            }
+                            for key in #keys_old {
+                                TableIndex::remove(&self.#index_field_name, key, link_old);
+                            }
+                        };
+                        return (quote! {}, remove);
+                    }
+                    let insert = quote! {
+                        if #keys_new != #keys_old {
+                            for key in #keys_new {
+                                if self.#index_field_name.insert_checked(key, link_new).is_none() {
+                                    return Err(IndexError::AlreadyExists {
+                                        at: #available_index_ident::#index_variant,
+                                        inserted_already: inserted_indexes.clone(),
+                                    })
+                                }
+                            }
+                            inserted_indexes.push(#available_index_ident::#index_variant);
+                        }
+                    };
+                    let remove = quote! {
+                        if #keys_new == #keys_old {
+                            for key in #keys_new {
+                                self.#index_field_name.insert(key, link_new);
+                            }
+                        } else {
+                            for key in #keys_old {
+                                TableIndex::remove(&self.#index_field_name, key, link_old);
+                            }
+                        }
+                    };
+                    return (insert, remove);
+                }
+                let row = if self
+                    .columns
+                    .columns_map
+                    .get(i)
+                    .is_some_and(|t| is_float(t.to_string().as_str()))
+                {
                     quote! {
                         OrderedFloat(row.#i)
                     }
@@ -196,14 +246,12 @@ impl Generator {
             .iter()
             .map(|(i, idx)| {
                 let index_field_name = &idx.name;
-                let row = if is_float(
-                    self.columns
-                        .columns_map
-                        .get(i)
-                        .unwrap()
-                        .to_string()
-                        .as_str(),
-                ) {
+                let row = if self
+                    .columns
+                    .columns_map
+                    .get(i)
+                    .is_some_and(|t| is_float(t.to_string().as_str()))
+                {
                     quote! {
                         OrderedFloat(row.#i)
                     }
@@ -212,7 +260,13 @@ impl Generator {
                         row.#i
                     }
                 };
-                if idx.is_unique {
+                if let Some(keys) = self.gen_row_index_keys(idx, quote! { row }) {
+                    quote! {
+                        for key in #keys {
+                            TableIndex::remove(&self.#index_field_name, key, link);
+                        }
+                    }
+                } else if idx.is_unique {
                     quote! {
                         self.#index_field_name.remove(&#row);
                     }
@@ -233,7 +287,8 @@ impl Generator {
     }
 
     /// Generates `process_difference_remove` function of `TableIndex` trait for index. It updates `Link` for all secondary indexes.
-    /// Uses HashMap<&str, Difference<AvaialableTypes>> for storing all changes
+    /// Uses HashMap<&str, Difference<AvaialableTypes>> for storing all changes. Only removed elements are removed from
+    /// array indexes. Partial indexes are skipped, as rows are reinserted when their columns are updated.
     fn gen_process_difference_remove_index_fn(&self) -> TokenStream {
         let name_generator = WorktableNameGenerator::from_table_name(self.name.to_string());
         let avt_type_ident = name_generator.get_available_type_ident();
@@ -242,8 +297,30 @@ impl Generator {
         let process_difference_remove_rows = self.columns.indexes.iter().map(|(i, idx)| {
             let index_field_name = &idx.name;
             let diff_key = Literal::string(i.to_string().as_str());
+            if idx.is_partial() {
+                // Updates of partial index columns reinsert the row.
+                return quote! {};
+            }
 
-            if let Some(t) = self.columns.columns_map.get(&idx.field) {
+            if let (Some(keys_old), Some(keys_new)) = (
+                self.gen_index_keys(idx, quote! { old }),
+                self.gen_index_keys(idx, quote! { new }),
+            ) {
+                let type_str = self.columns.index_value_type(idx).expect("index has value type").to_string();
+                let variant_ident = Ident::new(&map_to_uppercase(&type_str), Span::mixed_site());
+                return quote! {
+                    if let Some(diff) = difference.get(#diff_key) {
+                        if let (#avt_type_ident::#variant_ident(old), #avt_type_ident::#variant_ident(new)) = (&diff.old, &diff.new) {
+                            let keys_new = #keys_new;
+                            for key in #keys_old.difference(&keys_new) {
+                                TableIndex::remove(&self.#index_field_name, key.clone(), link);
+                            }
+                        }
+                    }
+                };
+            }
+
+            if let Some(t) = self.columns.index_value_type(idx) {
                 let type_str = t.to_string();
                 let variant_ident = Ident::new(&map_to_uppercase(&type_str), Span::mixed_site());
 
@@ -252,7 +329,7 @@ impl Generator {
                 } else if is_float(type_str.as_str()) {
                     quote! { OrderedFloat(*old) }
                 } else {
-                    quote! { *old }
+                    quote! { old.clone() }
                 };
 
                 quote! {
@@ -281,17 +358,55 @@ impl Generator {
     }
 
     /// Generates `process_difference_insert` function of `TableIndex` trait for index. It updates `Link` for all secondary indexes.
-    /// Uses HashMap<&str, Difference<AvaialableTypes>> for storing all changes
+    /// Uses HashMap<&str, Difference<AvaialableTypes>> for storing all changes. Only added elements are inserted into
+    /// array indexes.
     fn gen_process_difference_insert_index_fn(&self) -> TokenStream {
         let name_generator = WorktableNameGenerator::from_table_name(self.name.to_string());
         let avt_type_ident = name_generator.get_available_type_ident();
         let avt_index_ident = name_generator.get_available_indexes_ident();
 
-        let process_difference_insert_rows = self.columns.indexes.iter().map(|(i, idx)| {
+        let process_difference_insert_rows = self.indexes_with_array_last().into_iter().map(|(i, idx)| {
             let index_field_name = &idx.name;
             let diff_key = Literal::string(i.to_string().as_str());
+            if idx.is_partial() {
+                // Updates of partial index columns reinsert the row.
+                return quote! {};
+            }
 
-            if let Some(t) = self.columns.columns_map.get(&idx.field) {
+            if let (Some(keys_old), Some(keys_new)) = (
+                self.gen_index_keys(idx, quote! { old }),
+                self.gen_index_keys(idx, quote! { new }),
+            ) {
+                let type_str = self.columns.index_value_type(idx).expect("index has value type").to_string();
+                let variant_ident = Ident::new(&map_to_uppercase(&type_str), Span::mixed_site());
+                let camel_case_name = index_field_name
+                    .to_string()
+                    .from_case(Case::Snake)
+                    .to_case(Case::Pascal);
+                let index_variant: TokenStream = camel_case_name.parse().unwrap();
+                return quote! {
+                    if let Some(diff) = difference.get(#diff_key) {
+                        if let (#avt_type_ident::#variant_ident(old), #avt_type_ident::#variant_ident(new)) = (&diff.old, &diff.new) {
+                            let keys_old = #keys_old;
+                            let keys_new = #keys_new;
+                            let added: Vec<_> = keys_new.difference(&keys_old).collect();
+                            for &key in &added {
+                                if TableIndex::insert_checked(&self.#index_field_name, key.clone(), link).is_none() {
+                                    return Err(IndexError::AlreadyExists {
+                                        at: #avt_index_ident::#index_variant,
+                                        inserted_already: inserted_indexes.clone(),
+                                    })
+                                }
+                            }
+                            if !added.is_empty() {
+                                inserted_indexes.push(#avt_index_ident::#index_variant);
+                            }
+                        }
+                    }
+                };
+            }
+
+            if let Some(t) = self.columns.index_value_type(idx) {
                 let type_str = t.to_string();
                 let variant_ident = Ident::new(&map_to_uppercase(&type_str), Span::mixed_site());
                 let camel_case_name = index_field_name
@@ -305,7 +420,7 @@ impl Generator {
                 } else if is_float(type_str.as_str()) {
                     quote! { OrderedFloat(*new) }
                 } else {
-                    quote! { *new }
+                    quote! { new.clone() }
                 };
 
                 quote! {
@@ -356,14 +471,12 @@ impl Generator {
                     .from_case(Case::Snake)
                     .to_case(Case::Pascal);
                 let index_variant: TokenStream = camel_case_name.parse().unwrap();
-                let row = if is_float(
-                    self.columns
-                        .columns_map
-                        .get(i)
-                        .unwrap()
-                        .to_string()
-                        .as_str(),
-                ) {
+                let row = if self
+                    .columns
+                    .columns_map
+                    .get(i)
+                    .is_some_and(|t| is_float(t.to_string().as_str()))
+                {
                     quote! {
                         OrderedFloat(row.#i)
                     }
@@ -372,7 +485,13 @@ impl Generator {
                         row.#i
                     }
                 };
-                let delete = if idx.is_unique {
+                let delete = if let Some(keys) = self.gen_row_index_keys(idx, quote! { row }) {
+                    quote! {
+                        for key in #keys {
+                            TableIndex::remove(&self.#index_field_name, key, link);
+                        }
+                    }
+                } else if idx.is_unique {
                     quote! {
                         self.#index_field_name.remove(&#row);
                     }
@@ -1,6 +1,4 @@
-use std::collections::HashMap;
-
-use crate::name_generator::{WorktableNameGenerator, is_unsized_vec};
+use crate::name_generator::WorktableNameGenerator;
 use crate::worktable::generator::Generator;
 use crate::worktable::model::{GeneratorType, PrimaryKey};
 
@@ -12,26 +10,10 @@ impl Generator {
     pub fn gen_primary_key_def(&mut self) -> syn::Result<TokenStream> {
         let name_generator = WorktableNameGenerator::from_table_name(self.name.to_string());
         let ident = name_generator.get_primary_key_type_ident();
-        let values = self
-            .columns
-            .primary_keys
-            .iter()
-            .map(|i| {
-                (
-                    i.clone(),
-                    self.columns
-                        .columns_map
-                        .get(i)
-                        .expect("should exist as got from definition")
-                        .clone(),
-                )
-            })
-            .collect::<HashMap<_, _>>();
-
         let def = self.gen_primary_key_type();
         let impl_ = self.gen_table_primary_key_impl()?;
 
-        self.pk = Some(PrimaryKey { ident, values });
+        self.pk = Some(PrimaryKey { ident });
 
         Ok(quote! {
             #def
@@ -56,14 +38,13 @@ impl Generator {
                     .expect("should exist as got from definition")
             })
             .collect::<Vec<_>>();
-        let unsized_derive =
-            if is_unsized_vec(&types.iter().map(|v| v.to_string()).collect::<Vec<_>>()) {
-                quote! {
-                    VariableSizeMeasure,
-                }
-            } else {
-                quote! {}
-            };
+        let unsized_derive = if self.columns.is_primary_key_unsized() {
+            quote! {
+                VariableSizeMeasure,
+            }
+        } else {
+            quote! {}
+        };
 
         quote! {
             #[derive(
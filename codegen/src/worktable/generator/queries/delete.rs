@@ -6,7 +6,7 @@ use quote::quote;
 
 use crate::name_generator::{WorktableNameGenerator, is_float};
 use crate::worktable::generator::Generator;
-use crate::worktable::model::Operation;
+use crate::worktable::model::{Index, Operation};
 
 impl Generator {
     pub fn gen_query_delete_impl(&mut self) -> syn::Result<TokenStream> {
@@ -14,7 +14,7 @@ impl Generator {
         let table_ident = name_generator.get_work_table_ident();
 
         let custom_deletes = if let Some(q) = &self.queries {
-            let custom_deletes = self.gen_custom_deletes(q.deletes.clone());
+            let custom_deletes = self.gen_custom_deletes(q.deletes.clone())?;
             quote! {
                 #custom_deletes
             }
@@ -109,7 +109,10 @@ impl Generator {
         }
     }
 
-    fn gen_custom_deletes(&mut self, deleted: HashMap<Ident, Operation>) -> TokenStream {
+    fn gen_custom_deletes(
+        &mut self,
+        deleted: HashMap<Ident, Operation>,
+    ) -> syn::Result<TokenStream> {
         let defs = deleted
             .iter()
             .map(|(name, op)| {
@@ -121,25 +124,33 @@ impl Generator {
                     format!("delete_{snake_case_name}").as_str(),
                     Span::mixed_site(),
                 );
-                let index = self.columns.indexes.values().find(|idx| idx.field == op.by);
-                let type_ = self.columns.columns_map.get(&op.by).unwrap();
+                let Some(by) = op.by_column() else {
+                    return Err(syn::Error::new(
+                        op.name.span(),
+                        "delete query can be `by` single column only",
+                    ));
+                };
+                let index = self
+                    .columns
+                    .value_indexes()
+                    .find(|(_, idx)| &idx.field == by)
+                    .map(|(_, idx)| idx);
+                let type_ = self.columns.columns_map.get(by).unwrap();
                 if let Some(index) = index {
-                    let index_name = &index.name;
-
                     if index.is_unique {
-                        Self::gen_unique_delete(type_, &method_ident, index_name)
+                        Ok(Self::gen_unique_delete(type_, &method_ident, index))
                     } else {
-                        Self::gen_non_unique_delete(type_, &method_ident, index_name)
+                        Ok(Self::gen_non_unique_delete(type_, &method_ident, index))
                     }
                 } else {
-                    Self::gen_brute_force_delete_field(&op.by, type_, &method_ident)
+                    Ok(Self::gen_brute_force_delete_field(by, type_, &method_ident))
                 }
             })
-            .collect::<Vec<_>>();
+            .collect::<syn::Result<Vec<_>>>()?;
 
-        quote! {
+        Ok(quote! {
             #(#defs)*
-        }
+        })
     }
 
     fn gen_brute_force_delete_field(
@@ -165,7 +176,7 @@ impl Generator {
         }
     }
 
-    fn gen_non_unique_delete(type_: &TokenStream, name: &Ident, index: &Ident) -> TokenStream {
+    fn gen_non_unique_delete(type_: &TokenStream, name: &Ident, index: &Index) -> TokenStream {
         let by = if is_float(type_.to_string().as_str()) {
             quote! {
                 &OrderedFloat(by)
@@ -175,11 +186,12 @@ impl Generator {
                 &by
             }
         };
+        let links = Self::gen_non_unique_index_links(index, &by);
         quote! {
             pub async fn #name(&self, by: #type_) -> core::result::Result<(), WorkTableError> {
-                let rows_to_update = self.0.indexes.#index.get(#by).map(|kv| kv.1).collect::<Vec<_>>();
+                let rows_to_update = #links;
                 for link in rows_to_update {
-                    let row = self.0.data.select_non_ghosted(*link).map_err(WorkTableError::PagesError)?;
+                    let row = self.0.data.select_non_ghosted(link).map_err(WorkTableError::PagesError)?;
                     self.delete(row.get_primary_key()).await?;
                 }
                 core::result::Result::Ok(())
@@ -187,7 +199,7 @@ impl Generator {
         }
     }
 
-    fn gen_unique_delete(type_: &TokenStream, name: &Ident, index: &Ident) -> TokenStream {
+    fn gen_unique_delete(type_: &TokenStream, name: &Ident, index: &Index) -> TokenStream {
         let by = if is_float(type_.to_string().as_str()) {
             quote! {
                 &OrderedFloat(by)
@@ -197,9 +209,15 @@ impl Generator {
                 &by
             }
         };
+        let index_name = &index.name;
+        let link = if index.is_hash {
+            quote! { self.0.indexes.#index_name.get(#by) }
+        } else {
+            quote! { self.0.indexes.#index_name.get(#by).map(|v| v.get().value) }
+        };
         quote! {
             pub async fn #name(&self, by: #type_) -> core::result::Result<(), WorkTableError> {
-                let row_to_update = self.0.indexes.#index.get(#by).map(|v| v.get().value);
+                let row_to_update = #link;
                 if let Some(link) = row_to_update {
                     let row = self.0.data.select_non_ghosted(link).map_err(WorkTableError::PagesError)?;
                     self.delete(row.get_primary_key()).await?;
@@ -0,0 +1,204 @@
+use convert_case::{Case, Casing};
+use proc_macro2::{Ident, Literal, Span, TokenStream};
+use quote::quote;
+
+use crate::name_generator::{WorktableNameGenerator, is_float};
+use crate::worktable::generator::Generator;
+use crate::worktable::model::{Index, Operation};
+
+impl Generator {
+    /// Generates `explain_update_<name>` and `explain_delete_<name>` fns for
+    /// custom queries and `explain_select_by_<column>` fns for unique indexes.
+    /// They don't modify table and return `QueryPlan` with access path used by
+    /// query and count of rows that it affects.
+    pub fn gen_query_explain_impl(&mut self) -> syn::Result<TokenStream> {
+        let name_generator = WorktableNameGenerator::from_table_name(self.name.to_string());
+        let table_ident = name_generator.get_work_table_ident();
+
+        let select_explains = self
+            .columns
+            .indexes
+            .iter()
+            .filter(|(_, idx)| idx.is_unique && !idx.is_computed())
+            .map(|(column, _)| {
+                let type_ = self.columns.columns_map.get(column).unwrap();
+                let method_ident = Ident::new(
+                    format!("explain_select_by_{column}").as_str(),
+                    Span::mixed_site(),
+                );
+                let plan = self.gen_by_plan(column);
+                quote! {
+                    pub fn #method_ident(&self, by: #type_) -> core::result::Result<QueryPlan, WorkTableError> {
+                        #plan
+                    }
+                }
+            })
+            .collect::<Vec<_>>();
+        let (update_explains, delete_explains) = if let Some(q) = &self.queries {
+            (
+                self.gen_explains("update", &q.updates)?,
+                self.gen_explains("delete", &q.deletes)?,
+            )
+        } else {
+            (vec![], vec![])
+        };
+
+        Ok(quote! {
+            impl #table_ident {
+                #(#select_explains)*
+                #(#update_explains)*
+                #(#delete_explains)*
+            }
+        })
+    }
+
+    fn gen_explains(
+        &self,
+        kind: &str,
+        ops: &std::collections::HashMap<Ident, Operation>,
+    ) -> syn::Result<Vec<TokenStream>> {
+        let name_generator = WorktableNameGenerator::from_table_name(self.name.to_string());
+        let pk_ident = name_generator.get_primary_key_type_ident();
+
+        let mut explains = vec![];
+        for (name, op) in ops {
+            let snake_case_name = name
+                .to_string()
+                .from_case(Case::Pascal)
+                .to_case(Case::Snake);
+            let method_ident = Ident::new(
+                format!("explain_{kind}_{snake_case_name}").as_str(),
+                Span::mixed_site(),
+            );
+            let is_pk = self.columns.is_primary_key(&op.by);
+            let is_indexed = self
+                .columns
+                .value_indexes()
+                .any(|(_, idx)| Some(&idx.field) == op.by_column());
+            if is_pk && !is_indexed {
+                explains.push(quote! {
+                    pub fn #method_ident<Pk>(&self, pk: Pk) -> core::result::Result<QueryPlan, WorkTableError>
+                    where #pk_ident: From<Pk>
+                    {
+                        let pk: #pk_ident = pk.into();
+                        Ok(QueryPlan {
+                            access_path: AccessPath::PrimaryKeyLookup,
+                            sort: None,
+                            estimated_rows: Some(1),
+                            actual_rows: self.0.pk_map.get(&pk).is_some() as usize,
+                        })
+                    }
+                });
+            } else if kind == "update" && !is_indexed {
+                // Update by several columns or by not indexed column gets
+                // rows by first indexed `by` column or by `pk_map` scan.
+                let by_ident = Ident::new(format!("{name}By").as_str(), Span::mixed_site());
+                let plan = self.gen_by_columns_plan(op)?;
+                explains.push(quote! {
+                    pub fn #method_ident(&self, by: #by_ident) -> core::result::Result<QueryPlan, WorkTableError> {
+                        #plan
+                    }
+                });
+            } else if let Some(by) = op.by_column() {
+                let type_ = self.columns.columns_map.get(by).unwrap();
+                let plan = self.gen_by_plan(by);
+                explains.push(quote! {
+                    pub fn #method_ident(&self, by: #type_) -> core::result::Result<QueryPlan, WorkTableError> {
+                        #plan
+                    }
+                });
+            } else {
+                return Err(syn::Error::new(
+                    op.name.span(),
+                    format!("{kind} query can be `by` single column only"),
+                ));
+            }
+        }
+        Ok(explains)
+    }
+
+    /// Generates `QueryPlan` for the update by several columns or by not
+    /// indexed column. Rows are got same way as update gets them.
+    fn gen_by_columns_plan(&self, op: &Operation) -> syn::Result<TokenStream> {
+        let (links, _) = self.gen_update_by_columns_links(op)?;
+        let (access_path, estimated_rows) =
+            if let Some((idx, key)) = self.update_by_columns_index(op) {
+                let index_name = Literal::string(&idx.name.to_string());
+                (
+                    quote! { AccessPath::IndexExact(#index_name) },
+                    Self::gen_index_count(idx, &key),
+                )
+            } else {
+                (
+                    quote! { AccessPath::FullScan },
+                    quote! { self.0.pk_map.len() },
+                )
+            };
+        Ok(quote! {
+            let links: Vec<_> = #links;
+            Ok(QueryPlan {
+                access_path: #access_path,
+                sort: None,
+                estimated_rows: Some(#estimated_rows),
+                actual_rows: links.len(),
+            })
+        })
+    }
+
+    /// Generates count of rows with `key` in the index without reading rows.
+    fn gen_index_count(idx: &Index, key: &TokenStream) -> TokenStream {
+        let index_field = &idx.name;
+        if idx.is_unique {
+            quote! { self.0.indexes.#index_field.get(#key).is_some() as usize }
+        } else if idx.is_hash {
+            quote! { self.0.indexes.#index_field.get(#key).len() }
+        } else if idx.is_bitmap {
+            quote! { self.0.indexes.#index_field.count(#key) }
+        } else {
+            quote! { self.0.indexes.#index_field.get(#key).count() }
+        }
+    }
+
+    /// Generates `QueryPlan` for the query which gets rows by `column` value.
+    /// Not indexed columns are checked by brute-force `iter_with_async`.
+    fn gen_by_plan(&self, column: &Ident) -> TokenStream {
+        let type_ = self.columns.columns_map.get(column).unwrap();
+        let by = if is_float(type_.to_string().as_str()) {
+            quote! { &OrderedFloat(by) }
+        } else {
+            quote! { &by }
+        };
+
+        if let Some((_, idx)) = self
+            .columns
+            .value_indexes()
+            .find(|(_, idx)| &idx.field == column)
+        {
+            let index_name = Literal::string(&idx.name.to_string());
+            let count = Self::gen_index_count(idx, &by);
+            let (estimated_rows, actual_rows) = if idx.is_unique {
+                (quote! { Some(1) }, count)
+            } else {
+                (quote! { Some(#count) }, count)
+            };
+            quote! {
+                Ok(QueryPlan {
+                    access_path: AccessPath::IndexExact(#index_name),
+                    sort: None,
+                    estimated_rows: #estimated_rows,
+                    actual_rows: #actual_rows,
+                })
+            }
+        } else {
+            quote! {
+                let actual_rows = self.select_all().where_by(|row| row.#column == by).execute()?.len();
+                Ok(QueryPlan {
+                    access_path: AccessPath::AsyncFullScan,
+                    sort: None,
+                    estimated_rows: Some(self.0.pk_map.len()),
+                    actual_rows,
+                })
+            }
+        }
+    }
+}
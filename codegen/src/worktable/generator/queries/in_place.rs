@@ -1,6 +1,6 @@
-use crate::name_generator::WorktableNameGenerator;
+use crate::name_generator::{WorktableNameGenerator, is_float};
 use crate::worktable::generator::Generator;
-use crate::worktable::model::Operation;
+use crate::worktable::model::{Index, Operation};
 use convert_case::{Case, Casing};
 use proc_macro2::{Ident, Span, TokenStream};
 use quote::quote;
@@ -12,7 +12,7 @@ impl Generator {
         let table_ident = name_generator.get_work_table_ident();
 
         let custom_in_place = if let Some(q) = &self.queries {
-            let custom_in_place = self.gen_in_place_queries(q.in_place.clone());
+            let custom_in_place = self.gen_in_place_queries(q.in_place.clone())?;
             quote! {
                 #custom_in_place
             }
@@ -27,7 +27,10 @@ impl Generator {
         })
     }
 
-    fn gen_in_place_queries(&self, in_place_queries: HashMap<Ident, Operation>) -> TokenStream {
+    fn gen_in_place_queries(
+        &self,
+        in_place_queries: HashMap<Ident, Operation>,
+    ) -> syn::Result<TokenStream> {
         let defs = in_place_queries
             .iter()
             .map(|(name, op)| {
@@ -35,35 +38,50 @@ impl Generator {
                     .to_string()
                     .from_case(Case::Pascal)
                     .to_case(Case::Snake);
-                let index = self.columns.indexes.values().find(|idx| idx.field == op.by);
+                // Indexes are not updated by in place queries, so indexed
+                // columns can't be changed.
+                if let Some(column) = op.columns.iter().find(|c| self.is_indexed_column(c)) {
+                    return Err(syn::Error::new(
+                        column.span(),
+                        format!("in place query can't update indexed column `{column}`"),
+                    ));
+                }
+                let index = self
+                    .columns
+                    .value_indexes()
+                    .find(|(_, idx)| Some(&idx.field) == op.by_column())
+                    .map(|(_, idx)| idx);
                 if let Some(index) = index {
-                    let _index_name = &index.name;
-
-                    if index.is_unique { todo!() } else { todo!() }
-                } else if self.columns.primary_keys.len() == 1 {
-                    self.gen_primary_key_in_place(snake_case_name, &op.columns)
+                    Ok(self.gen_index_in_place(snake_case_name, &op.columns, &index.field, index))
+                } else if self.columns.is_primary_key(&op.by) {
+                    Ok(self.gen_primary_key_in_place(snake_case_name, &op.columns))
                 } else {
-                    todo!()
+                    Err(syn::Error::new(
+                        op.name.span(),
+                        "in place query must be `by` primary key or indexed column",
+                    ))
                 }
             })
-            .collect::<Vec<_>>();
+            .collect::<syn::Result<Vec<_>>>()?;
 
-        quote! {
+        Ok(quote! {
             #(#defs)*
-        }
+        })
     }
 
-    fn gen_primary_key_in_place(&self, snake_case_name: String, columns: &[Ident]) -> TokenStream {
-        let name_generator = WorktableNameGenerator::from_table_name(self.name.to_string());
-        let pk_type = name_generator.get_primary_key_type_ident();
-        let lock_ident =
-            WorktableNameGenerator::get_update_in_place_query_lock_ident(&snake_case_name);
-
-        let method_ident = Ident::new(
-            format!("update_{snake_case_name}_in_place").as_str(),
-            Span::mixed_site(),
-        );
+    /// Checks whether column is part of primary key or is used by some
+    /// secondary index.
+    fn is_indexed_column(&self, column: &Ident) -> bool {
+        self.columns.primary_keys.contains(column)
+            || self.columns.indexes.values().any(|idx| {
+                &idx.field == column || self.computed_index_columns(idx).contains(column)
+            })
+            || self.partial_index_columns().contains(column)
+    }
 
+    /// Returns type of the closure argument and expression which gets it from
+    /// archived row. Single column is passed as is, many columns as tuple.
+    fn gen_in_place_closure_args(&self, columns: &[Ident]) -> (TokenStream, TokenStream) {
         let types = columns
             .iter()
             .map(|c| self.columns.columns_map.get(c).unwrap())
@@ -98,7 +116,106 @@ impl Generator {
                 ( #(#columns),* )
             }
         };
+        (column_types, column_fields)
+    }
+
+    /// Generates in place query by secondary index. All rows with `by` value
+    /// are locked first, then closure is applied to every locked row. Returns
+    /// [`WorkTableError::NotFound`] if there is no row with `by` value, same
+    /// as query by primary key.
+    fn gen_index_in_place(
+        &self,
+        snake_case_name: String,
+        columns: &[Ident],
+        by: &Ident,
+        index: &Index,
+    ) -> TokenStream {
+        let lock_ident =
+            WorktableNameGenerator::get_update_in_place_query_lock_ident(&snake_case_name);
+        let method_ident = Ident::new(
+            format!("update_{snake_case_name}_in_place").as_str(),
+            Span::mixed_site(),
+        );
+        let by_type = self.columns.columns_map.get(by).unwrap();
+        let by = if is_float(by_type.to_string().as_str()) {
+            quote! { &OrderedFloat(by) }
+        } else {
+            quote! { &by }
+        };
+        let links = Self::gen_index_links(index, &by);
+        let (column_types, column_fields) = self.gen_in_place_closure_args(columns);
+        let custom_lock = self.gen_custom_lock_for_update(lock_ident);
+        let persist_op = self.gen_in_place_persist_op();
+
+        quote! {
+            pub async fn #method_ident<F: FnMut(#column_types)>(
+                &self,
+                mut f: F,
+                by: #by_type,
+            ) -> eyre::Result<()>
+            {
+                let links: Vec<_> = #links;
+                let mut locks = std::collections::HashMap::new();
+                for link in links {
+                    // Row could be removed after links were got.
+                    let Ok(row) = self.0.data.select_non_ghosted(link) else {
+                        continue;
+                    };
+                    let pk = row.get_primary_key().clone();
+                    let lock = {
+                        #custom_lock
+                    };
+                    locks.insert(pk, lock);
+                }
+
+                // Links are got again, as rows could be moved while locks
+                // were taken. Rows which were not locked are skipped, so it's
+                // `NotFound` if no row is updated.
+                let links: Vec<_> = #links;
+                let mut res = Err(WorkTableError::NotFound);
+                for link in links {
+                    let Ok(row) = self.0.data.select_non_ghosted(link) else {
+                        continue;
+                    };
+                    if !locks.contains_key(&row.get_primary_key()) {
+                        continue;
+                    }
+                    res = unsafe {
+                        self.0
+                            .data
+                            .with_mut_ref(link, |archived| f(#column_fields))
+                            .map_err(WorkTableError::PagesError)
+                    }
+                    #persist_op;
+                    if res.is_err() {
+                        break;
+                    }
+                }
+
+                for (pk, lock) in locks {
+                    lock.unlock();
+                    self.0.lock_map.remove_with_lock_check(&pk).await;
+                }
+
+                Ok(res?)
+            }
+        }
+    }
+
+    fn gen_primary_key_in_place(&self, snake_case_name: String, columns: &[Ident]) -> TokenStream {
+        let name_generator = WorktableNameGenerator::from_table_name(self.name.to_string());
+        let pk_type = name_generator.get_primary_key_type_ident();
+        let lock_ident =
+            WorktableNameGenerator::get_update_in_place_query_lock_ident(&snake_case_name);
+
+        let method_ident = Ident::new(
+            format!("update_{snake_case_name}_in_place").as_str(),
+            Span::mixed_site(),
+        );
+
+        let (column_types, column_fields) = self.gen_in_place_closure_args(columns);
         let custom_lock = self.gen_custom_lock_for_update(lock_ident);
+        let persist_op = self.gen_in_place_persist_op();
 
         quote! {
             pub async fn #method_ident<Pk, F: FnMut(#column_types)>(
@@ -112,24 +229,55 @@ impl Generator {
                 let lock = {
                     #custom_lock
                 };
-                let link = self
+                let res = self
                     .0
                     .pk_map
                     .get(&pk)
                     .map(|v| v.get().value)
-                    .ok_or(WorkTableError::NotFound)?;
-                unsafe {
-                    self.0
-                        .data
-                        .with_mut_ref(link, move |archived| f(#column_fields))
-                        .map_err(WorkTableError::PagesError)?
-                    };
+                    .ok_or(WorkTableError::NotFound)
+                    .and_then(|link| unsafe {
+                        self.0
+                            .data
+                            .with_mut_ref(link, move |archived| f(#column_fields))
+                            .map_err(WorkTableError::PagesError)
+                            #persist_op
+                    });
 
                 lock.unlock();
                 self.0.lock_map.remove_with_lock_check(&pk).await;
 
-                Ok(())
+                Ok(res?)
             }
         }
     }
+
+    /// Generates `Update` operation with new row bytes for persisted tables.
+    /// It's chained to in place result, so it's sent while row is locked and
+    /// operations of one row are ordered same as changes.
+    fn gen_in_place_persist_op(&self) -> TokenStream {
+        let name_generator = WorktableNameGenerator::from_table_name(self.name.to_string());
+        let secondary_events_ident = name_generator.get_space_secondary_index_events_ident();
+        let primary_key_ident = name_generator.get_primary_key_type_ident();
+
+        if self.is_persist {
+            quote! {
+                .and_then(|_| {
+                    let op: Operation<
+                        <<#primary_key_ident as TablePrimaryKey>::Generator as PrimaryKeyGeneratorState>::State,
+                        #primary_key_ident,
+                        #secondary_events_ident
+                    > = Operation::Update(UpdateOperation {
+                        id: OperationId::Single(uuid::Uuid::now_v7()),
+                        secondary_keys_events: core::default::Default::default(),
+                        bytes: self.0.data.select_raw(link).map_err(WorkTableError::PagesError)?,
+                        link,
+                    });
+                    self.2.apply_operation(op);
+                    Ok(())
+                })
+            }
+        } else {
+            quote! {}
+        }
+    }
 }
@@ -1,4 +1,5 @@
 mod delete;
+mod explain;
 mod in_place;
 mod locks;
 mod select;
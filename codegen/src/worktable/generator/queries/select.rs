@@ -6,6 +6,7 @@ use quote::quote;
 impl Generator {
     pub fn gen_query_select_impl(&mut self) -> syn::Result<TokenStream> {
         let select_all = self.gen_select_all();
+        let read_archived_row = self.gen_read_archived_row();
 
         let name_generator = WorktableNameGenerator::from_table_name(self.name.to_string());
         let table_ident = name_generator.get_work_table_ident();
@@ -13,6 +14,7 @@ impl Generator {
         Ok(quote! {
             impl #table_ident {
                 #select_all
+                #read_archived_row
             }
         })
     }
@@ -22,18 +24,60 @@ impl Generator {
         let row_ident = name_generator.get_row_type_ident();
         let column_range_type = name_generator.get_column_range_type_ident();
         let row_fields_ident = name_generator.get_row_fields_enum_ident();
+        let actual_link = self.gen_actual_link();
 
         quote! {
             pub fn select_all(&self) -> SelectQueryBuilder<#row_ident,
-                                                           impl DoubleEndedIterator<Item = #row_ident> + '_ + Sized,
+                                                           impl DoubleEndedIterator<Item = #row_ident> + ArchivedFilter<'_, <#row_ident as rkyv::Archive>::Archived> + '_ + Sized,
                                                            #column_range_type,
                                                            #row_fields_ident>
             {
-                let iter = self.0.pk_map
-                    .iter()
-                    .filter_map(|(_, link)| self.0.data.select_non_ghosted(*link).ok());
+                let links = ChunkedIter::new(&self.0.pk_map)
+                    #actual_link
+                    .map(|(_, link)| link);
+                let iter = ArchivedRows::new(links, move |link, check| self.read_archived_row(link, check));
 
                 SelectQueryBuilder::new(iter)
+                    .with_access_path(AccessPath::FullScan, self.0.pk_map.len())
+            }
+        }
+    }
+
+    /// Unsized rows are reinserted on update, so links read by `ChunkedIter`
+    /// can become outdated before row is read. For such tables link is taken
+    /// from `pk_map` again.
+    fn gen_actual_link(&self) -> TokenStream {
+        if self.columns.is_sized {
+            quote! {}
+        } else {
+            quote! {
+                .filter_map(|(pk, _)| self.0.pk_map.get(&pk).map(|v| (pk, v.get().value)))
+            }
+        }
+    }
+
+    /// Generates row reader used by select fns iterators. If `check` is
+    /// set, it is applied to archived row first, so only matched rows are
+    /// deserialized.
+    fn gen_read_archived_row(&self) -> TokenStream {
+        let name_generator = WorktableNameGenerator::from_table_name(self.name.to_string());
+        let row_ident = name_generator.get_row_type_ident();
+
+        quote! {
+            fn read_archived_row(
+                &self,
+                link: Link,
+                check: ArchivedCheck<'_, <#row_ident as rkyv::Archive>::Archived>,
+            ) -> Option<#row_ident> {
+                if let Some(check) = check {
+                    let matched = self.0.data
+                        .with_ref(link, |archived| !archived.is_ghosted && check(&archived.inner))
+                        .unwrap_or(false);
+                    if !matched {
+                        return None;
+                    }
+                }
+                self.0.data.select_non_ghosted(link).ok()
             }
         }
     }
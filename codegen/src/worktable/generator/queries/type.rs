@@ -16,7 +16,14 @@ pub fn map_to_uppercase(str: &str) -> String {
             .replace(">", "");
         format!("Ordered{}", inner_type.to_uppercase().trim())
     } else {
-        str.to_uppercase()
+        // Type can be any path or generic/array type (`Vec < u8 >`,
+        // `[u8 ; 32]`), so everything except alphanumerics is replaced to get
+        // valid variant ident.
+        str.split(|c: char| !c.is_alphanumeric())
+            .filter(|part| !part.is_empty())
+            .collect::<Vec<_>>()
+            .join("_")
+            .to_uppercase()
     }
 }
 
@@ -28,8 +35,8 @@ impl Generator {
         let unique_types: HashSet<String> = self
             .columns
             .indexes
-            .iter()
-            .filter_map(|(_, idx)| self.columns.columns_map.get(&idx.field))
+            .values()
+            .filter_map(|idx| self.columns.index_value_type(idx))
             .map(|ty| ty.to_string())
             .collect();
 
@@ -142,11 +149,21 @@ impl Generator {
                 .values()
                 .map(|op| {
                     let ident = Ident::new(format!("{}By", &op.name).as_str(), Span::mixed_site());
-                    let field_type = self
-                        .columns
-                        .columns_map
-                        .get(&op.by)
-                        .ok_or(syn::Error::new(op.by.span(), "Unexpected column name"))?;
+                    let types = op
+                        .by
+                        .iter()
+                        .map(|by| {
+                            self.columns
+                                .columns_map
+                                .get(by)
+                                .ok_or(syn::Error::new(by.span(), "Unexpected column name"))
+                        })
+                        .collect::<Result<Vec<_>, _>>()?;
+                    let field_type = if let [t] = types.as_slice() {
+                        quote! { #t }
+                    } else {
+                        quote! { (#(#types),*) }
+                    };
 
                     Ok::<_, syn::Error>(quote! {
                         pub type #ident = #field_type;
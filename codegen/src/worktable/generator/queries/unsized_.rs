@@ -4,6 +4,12 @@ use quote::quote;
 use crate::name_generator::WorktableNameGenerator;
 use crate::worktable::generator::Generator;
 
+/// Generates size of the archived field. It is measured by `ArchivedSize`
+/// of the archived value, so field is not deserialized.
+fn gen_archived_field_size(field: TokenStream) -> TokenStream {
+    quote! { ArchivedSize::archived_size(&#field) }
+}
+
 impl Generator {
     pub fn gen_unsized_impls(&self) -> TokenStream {
         if self.columns.is_sized {
@@ -25,20 +31,15 @@ impl Generator {
         let unsized_fields: Vec<_> = self
             .columns
             .columns_map
-            .iter()
-            .filter_map(|(k, v)| {
-                if v.to_string() == "String" {
-                    Some(k)
-                } else {
-                    None
-                }
-            })
+            .keys()
+            .filter(|k| self.columns.is_unsized(k))
             .map(|f| {
                 let fn_ident = Ident::new(format!("get_{f}_size").as_str(), Span::call_site());
+                let size = gen_archived_field_size(quote! { row_ref.inner.#f });
                 quote! {
                     fn #fn_ident(&self, link: Link) -> core::result::Result<usize, WorkTableError> {
                         self.0.data
-                            .with_ref(link, |row_ref| row_ref.inner.#f.as_str().to_string().aligned_size())
+                            .with_ref(link, |row_ref| #size)
                             .map_err(WorkTableError::PagesError)
                     }
                 }
@@ -57,26 +58,21 @@ impl Generator {
             let query_impls: Vec<_> = q
                 .updates
                 .iter()
-                .filter(|(_, op)| {
-                    op.columns
-                        .iter()
-                        .any(|c| self.columns.columns_map.get(c).unwrap().to_string() == "String")
-                })
+                .filter(|(_, op)| op.columns.iter().any(|c| self.columns.is_unsized(c)))
                 .map(|(i, op)| {
                     let archived_ident =
                         Ident::new(format!("Archived{i}Query").as_str(), Span::call_site());
                     let unsized_fields: Vec<_> = op
                         .columns
                         .iter()
-                        .filter(|c| {
-                            self.columns.columns_map.get(c).unwrap().to_string() == "String"
-                        })
+                        .filter(|c| self.columns.is_unsized(c))
                         .map(|c| {
                             let fn_ident =
                                 Ident::new(format!("get_{c}_size").as_str(), Span::call_site());
+                            let size = gen_archived_field_size(quote! { self.#c });
                             quote! {
                                 pub fn #fn_ident(&self) -> usize {
-                                    self.#c.as_str().to_string().aligned_size()
+                                    #size
                                 }
                             }
                         })
@@ -3,7 +3,7 @@ use std::collections::HashMap;
 
 use crate::name_generator::{WorktableNameGenerator, is_float};
 use crate::worktable::generator::Generator;
-use crate::worktable::model::Operation;
+use crate::worktable::model::{Index, Operation};
 use convert_case::{Case, Casing};
 use proc_macro2::{Ident, Span, TokenStream};
 use quote::quote;
@@ -11,7 +11,7 @@ use quote::quote;
 impl Generator {
     pub fn gen_query_update_impl(&mut self) -> syn::Result<TokenStream> {
         let custom_updates = if let Some(q) = &self.queries {
-            let custom_updates = self.gen_custom_updates(q.updates.clone());
+            let custom_updates = self.gen_custom_updates(q.updates.clone())?;
 
             quote! {
                 #custom_updates
@@ -20,12 +20,14 @@ impl Generator {
             quote! {}
         };
         let full_row_update = self.gen_full_row_update();
+        let primary_key_update = self.gen_primary_key_update();
 
         let name_generator = WorktableNameGenerator::from_table_name(self.name.to_string());
         let table_ident = name_generator.get_work_table_ident();
         Ok(quote! {
             impl #table_ident {
                 #full_row_update
+                #primary_key_update
                 #custom_updates
             }
         })
@@ -46,20 +48,27 @@ impl Generator {
             })
             .collect::<Vec<_>>();
 
+        let idx_idents: Vec<_> = self
+            .columns
+            .indexes
+            .values()
+            .map(|idx| idx.field.clone())
+            .collect();
         let idents: Vec<_> = self
             .columns
             .indexes
             .values()
+            .filter(|idx| !idx.is_computed())
             .map(|idx| idx.field.clone())
             .collect();
 
         let diff_process_insert =
-            self.gen_process_diffs_insert_on_index(idents.as_slice(), Some(&idents));
-        let diff_process_remove = self.gen_process_diffs_remove_on_index(Some(&idents));
+            self.gen_process_diffs_insert_on_index(idents.as_slice(), Some(&idx_idents));
+        let diff_process_remove = self.gen_process_diffs_remove_on_index(Some(&idx_idents));
         let persist_call = self.gen_persist_call();
         let persist_op = self.gen_persist_op();
         let full_row_lock = self.gen_full_lock_for_update();
-        let size_check = if self.columns.is_sized {
+        let size_check = if self.columns.is_sized && self.partial_index_columns().is_empty() {
             quote! {}
         } else {
             quote! {
@@ -128,22 +137,115 @@ impl Generator {
         }
     }
 
-    fn gen_custom_updates(&mut self, updates: HashMap<Ident, Operation>) -> TokenStream {
+    /// Generates `update_primary_key` fn, which moves row to the new primary
+    /// key. Both keys are locked in their order, so opposite moves can't
+    /// deadlock.
+    fn gen_primary_key_update(&self) -> TokenStream {
+        let name_generator = WorktableNameGenerator::from_table_name(self.name.to_string());
+        let pk_ident = name_generator.get_primary_key_type_ident();
+        let full_row_lock = self.gen_full_lock_for_update();
+        let pk_updates = self
+            .columns
+            .primary_keys
+            .iter()
+            .enumerate()
+            .map(|(i, column)| {
+                let i = syn::Index::from(i);
+                quote! {
+                    row_new.#column = new_pk.#i.clone();
+                }
+            })
+            .collect::<Vec<_>>();
+        let reinsert = if self.is_persist {
+            quote! {
+                self.0.reinsert_with_primary_key_cdc(row_old, row_new).map(|(_, op)| {
+                    self.2.apply_operation(op);
+                })
+            }
+        } else {
+            quote! {
+                self.0.reinsert_with_primary_key(row_old, row_new).map(|_| ())
+            }
+        };
+
+        quote! {
+            pub async fn update_primary_key<Pk>(&self, old: Pk, new: Pk) -> core::result::Result<(), WorkTableError>
+            where #pk_ident: From<Pk>
+            {
+                let old_pk: #pk_ident = old.into();
+                let new_pk: #pk_ident = new.into();
+                if old_pk == new_pk {
+                    return self.0.pk_map.get(&old_pk).map(|_| ()).ok_or(WorkTableError::NotFound);
+                }
+
+                let (first_pk, second_pk) = if old_pk < new_pk {
+                    (old_pk.clone(), new_pk.clone())
+                } else {
+                    (new_pk.clone(), old_pk.clone())
+                };
+                let first_lock = {
+                    let pk = first_pk.clone();
+                    #full_row_lock
+                };
+                let second_lock = {
+                    let pk = second_pk.clone();
+                    #full_row_lock
+                };
+
+                let res = if let Some(row_old) = self.0.select(old_pk.clone()) {
+                    let mut row_new = row_old.clone();
+                    #(#pk_updates)*
+                    #reinsert
+                } else {
+                    Err(WorkTableError::NotFound)
+                };
+
+                first_lock.unlock();
+                second_lock.unlock();
+                self.0.lock_map.remove_with_lock_check(&first_pk).await;
+                self.0.lock_map.remove_with_lock_check(&second_pk).await;
+
+                res
+            }
+        }
+    }
+
+    fn gen_custom_updates(
+        &mut self,
+        updates: HashMap<Ident, Operation>,
+    ) -> syn::Result<TokenStream> {
+        let partial_index_columns = self.partial_index_columns();
         let defs = updates
             .iter()
             .map(|(name, op)| {
+                if let Some(pk) = op.columns.iter().find(|c| self.columns.primary_keys.contains(c)) {
+                    return Err(syn::Error::new(
+                        pk.span(),
+                        format!("Primary key column `{pk}` can't be updated by query, `update_primary_key` must be used"),
+                    ));
+                }
                 let snake_case_name = name
                     .to_string()
                     .from_case(Case::Pascal)
                     .to_case(Case::Snake);
-                let index = self.columns.indexes.values().find(|idx| idx.field == op.by);
+                let index = self
+                    .columns
+                    .value_indexes()
+                    .find(|(_, idx)| Some(&idx.field) == op.by_column())
+                    .map(|(_, idx)| idx);
 
                 let indexes_columns: Option<Vec<_>> = {
                     let columns: Vec<_> = self
                         .columns
                         .indexes
                         .values()
-                        .filter(|idx| op.columns.contains(&idx.field))
+                        .filter(|idx| {
+                            op.columns.contains(&idx.field)
+                                || self
+                                    .computed_index_columns(idx)
+                                    .iter()
+                                    .any(|c| op.columns.contains(c))
+                        })
                         .map(|idx| idx.field.clone())
                         .collect();
 
@@ -153,17 +255,17 @@ impl Generator {
                         Some(columns)
                     }
                 };
-                let unsized_columns = if self.columns.is_sized {
-                    None
-                } else {
+                let unsized_columns = {
                     let fields = op
                         .columns
                         .iter()
-                        .filter(|c| {
-                            self.columns.columns_map.get(c).unwrap().to_string() == "String"
-                        })
+                        .filter(|c| self.columns.is_unsized(c))
                         .collect::<Vec<_>>();
-                    if fields.is_empty() {
+                    // Row is reinserted when partial index columns are
+                    // updated, so keys are got from whole rows.
+                    let is_partial_updated =
+                        op.columns.iter().any(|c| partial_index_columns.contains(c));
+                    if fields.is_empty() && !is_partial_updated {
                         None
                     } else {
                         Some(fields)
@@ -172,50 +274,146 @@ impl Generator {
 
                 let idents = &op.columns;
                 if let Some(index) = index {
-                    let index_name = &index.name;
-
                     if index.is_unique {
-                        self.gen_unique_update(
+                        Ok(self.gen_unique_update(
                             snake_case_name,
                             name,
-                            index_name,
+                            index,
                             idents,
                             indexes_columns.as_ref(),
                             unsized_columns,
-                        )
+                        ))
                     } else {
-                        self.gen_non_unique_update(
-                            snake_case_name,
-                            name,
-                            index_name,
-                            idents,
-                            indexes_columns.as_ref(),
-                            unsized_columns,
-                        )
-                    }
-                } else if self.columns.primary_keys.len() == 1 {
-                    if *self.columns.primary_keys.first().unwrap() == op.by {
-                        self.gen_pk_update(
+                        let by = self.gen_update_by_key(index);
+                        let links = Self::gen_non_unique_index_links(index, &by);
+                        let field = &index.field;
+                        let check = quote! { row.#field == by };
+                        Ok(self.gen_non_unique_update(
                             snake_case_name,
                             name,
+                            links,
+                            check,
                             idents,
                             indexes_columns.as_ref(),
                             unsized_columns,
-                        )
-                    } else {
-                        todo!()
+                        ))
                     }
+                } else if self.columns.is_primary_key(&op.by) {
+                    Ok(self.gen_pk_update(
+                        snake_case_name,
+                        name,
+                        idents,
+                        indexes_columns.as_ref(),
+                        unsized_columns,
+                    ))
                 } else {
-                    todo!()
+                    let (links, check) = self.gen_update_by_columns_links(op)?;
+                    Ok(self.gen_non_unique_update(
+                        snake_case_name,
+                        name,
+                        links,
+                        check,
+                        idents,
+                        indexes_columns.as_ref(),
+                        unsized_columns,
+                    ))
                 }
             })
-            .collect::<Vec<_>>();
+            .collect::<syn::Result<Vec<_>>>()?;
 
-        quote! {
+        Ok(quote! {
             #(#defs)*
+        })
+    }
+
+    /// Generates index key from `by` argument of the update.
+    fn gen_update_by_key(&self, index: &Index) -> TokenStream {
+        let by_type = self.columns.columns_map.get(&index.field).unwrap();
+        if is_float(by_type.to_string().as_str()) {
+            quote! { &OrderedFloat(by) }
+        } else {
+            quote! { &by }
+        }
+    }
+
+    /// Returns `by` columns of the update with expressions of their values in
+    /// `by` argument.
+    fn update_by_values(op: &Operation) -> Vec<(&Ident, TokenStream)> {
+        if let [by] = op.by.as_slice() {
+            vec![(by, quote! { by })]
+        } else {
+            op.by
+                .iter()
+                .enumerate()
+                .map(|(i, by)| {
+                    let i = syn::Index::from(i);
+                    (by, quote! { by.#i })
+                })
+                .collect()
         }
     }
 
+    /// Returns index of the first indexed `by` column with its key from `by`
+    /// argument. It's used to get rows of update by several columns or by
+    /// not indexed column, rows are scanned if no `by` column is indexed.
+    pub(super) fn update_by_columns_index(&self, op: &Operation) -> Option<(&Index, TokenStream)> {
+        Self::update_by_values(op)
+            .into_iter()
+            .find_map(|(column, value)| {
+                let (_, index) = self
+                    .columns
+                    .value_indexes()
+                    .find(|(_, idx)| &idx.field == column)?;
+                let by_type = self.columns.columns_map.get(column)?;
+                let key = if is_float(by_type.to_string().as_str()) {
+                    quote! { &OrderedFloat(#value) }
+                } else {
+                    quote! { &#value }
+                };
+                Some((index, key))
+            })
+    }
+
+    /// Generates links of rows with `by` values for update which is not `by`
+    /// single indexed column or primary key and check of the `row` against
+    /// `by` values. If one of `by` columns is indexed, rows are got from
+    /// index, else all rows are scanned. Rows are then checked against all
+    /// `by` columns.
+    pub(super) fn gen_update_by_columns_links(
+        &self,
+        op: &Operation,
+    ) -> syn::Result<(TokenStream, TokenStream)> {
+        let by_values = Self::update_by_values(op);
+        for (column, _) in &by_values {
+            if !self.columns.columns_map.contains_key(column) {
+                return Err(syn::Error::new(column.span(), "Unexpected column name"));
+            }
+        }
+
+        let checks = by_values
+            .iter()
+            .map(|(column, value)| quote! { row.#column == #value });
+        let check = quote! { #(#checks)&&* };
+        let links = if let Some((index, key)) = self.update_by_columns_index(op) {
+            let links = Self::gen_index_links(index, &key);
+            quote! { #links.into_iter() }
+        } else {
+            quote! { self.0.pk_map.iter().map(|(_, link)| *link) }
+        };
+
+        let links = quote! {
+            #links
+                .filter(|link| {
+                    self.0
+                        .data
+                        .select_non_ghosted(*link)
+                        .is_ok_and(|row| #check)
+                })
+                .collect::<Vec<_>>()
+        };
+        Ok((links, check))
+    }
+
     fn gen_persist_call(&self) -> TokenStream {
         if self.is_persist {
             quote! {
@@ -242,6 +440,14 @@ impl Generator {
                     }
                 })
                 .collect();
+            let need_to_reinsert = if fields_check.is_empty() {
+                quote! { let need_to_reinsert = true; }
+            } else {
+                quote! {
+                    let mut need_to_reinsert = true;
+                    #(#fields_check)*
+                }
+            };
             let row_updates = idents
                 .iter()
                 .map(|i| {
@@ -253,8 +459,7 @@ impl Generator {
             let full_row_lock = self.gen_full_lock_for_update();
 
             quote! {
-                let mut need_to_reinsert = true;
-                #(#fields_check)*
+                #need_to_reinsert
                 if need_to_reinsert {
                     lock.unlock();
                     let lock = {
@@ -350,6 +555,44 @@ impl Generator {
         } else {
             vec![]
         };
+        let computed_diff = if let Some(idx_idents) = idx_idents {
+            let diffs = self
+                .columns
+                .indexes
+                .values()
+                .filter(|idx| idx.is_computed() && idx_idents.contains(&idx.field))
+                .map(|idx| {
+                    let diff_key = Literal::string(idx.field.to_string().as_str());
+                    let old = self.gen_computed_key(idx, &quote! { row_old });
+                    let new = self.gen_computed_key(idx, &quote! { row_merged });
+                    quote! {
+                        let old = #old;
+                        let new = #new;
+
+                        if old != new {
+                            let diff = Difference::<#avt_type_ident> {
+                                old: old.into(),
+                                new: new.into(),
+                            };
+
+                            diffs.insert(#diff_key, diff);
+                        }
+                    }
+                })
+                .collect::<Vec<_>>();
+            if diffs.is_empty() {
+                quote! {}
+            } else {
+                // Computed keys may use columns which are not updated, so
+                // they are got from the row with updated columns merged in.
+                quote! {
+                    let row_merged = row_new.clone().merge(row_old.clone());
+                    #(#diffs)*
+                }
+            }
+        } else {
+            quote! {}
+        };
 
         let process_difference = if self.is_persist {
             if idx_idents.is_some() {
@@ -401,6 +644,7 @@ impl Generator {
         quote! {
             #diff_container
             #(#diff)*
+            #computed_diff
             #process_difference
         }
     }
@@ -499,11 +743,16 @@ impl Generator {
         }
     }
 
+    /// Generates update of all rows from `links`, which is expression of
+    /// `Vec<Link>` got by `by` value. Rows can be changed before their locks
+    /// are taken, so each row is checked again with `check` under its lock.
+    #[allow(clippy::too_many_arguments)]
     fn gen_non_unique_update(
         &self,
         snake_case_name: String,
         name: &Ident,
-        index: &Ident,
+        links: TokenStream,
+        check: TokenStream,
         idents: &[Ident],
         idx_idents: Option<&Vec<Ident>>,
         unsized_fields: Option<Vec<&Ident>>,
@@ -536,6 +785,14 @@ impl Generator {
                     }
                 })
                 .collect();
+            let need_to_reinsert = if fields_check.is_empty() {
+                quote! { let need_to_reinsert = true; }
+            } else {
+                quote! {
+                    let mut need_to_reinsert = true;
+                    #(#fields_check)*
+                }
+            };
             let row_updates = idents
                 .iter()
                 .map(|i| {
@@ -547,8 +804,7 @@ impl Generator {
             let full_row_lock = self.gen_full_lock_for_update();
 
             quote! {
-                let mut need_to_reinsert = true;
-                #(#fields_check)*
+                #need_to_reinsert
                 if need_to_reinsert {
                     let op_lock = locks.remove(&pk).expect("should not be deleted as links are unique");
                     op_lock.unlock();
@@ -561,6 +817,7 @@ impl Generator {
                     if let Err(e) = self.reinsert(row_old, row_new) {
                         self.0.update_state.remove(&pk);
                         lock.unlock();
+                        self.0.lock_map.remove_with_lock_check(&pk).await;
 
                         return Err(e);
                     }
@@ -580,62 +837,71 @@ impl Generator {
         let diff_process_remove = self.gen_process_diffs_remove_on_index(idx_idents);
         let persist_call = self.gen_persist_call();
         let persist_op = self.gen_persist_op();
-        let by = if is_float(by_ident.to_string().as_str()) {
-            quote! {
-                &OrderedFloat(by)
-            }
-        } else {
-            quote! {
-                &by
-            }
-        };
         let custom_lock = self.gen_custom_lock_for_update(lock_ident);
 
         quote! {
             pub async fn #method_ident(&self, row: #query_ident, by: #by_ident) -> core::result::Result<(), WorkTableError> {
-                let links: Vec<_> = self.0.indexes.#index.get(#by).map(|(_, l)| *l).collect();
+                let links: Vec<_> = #links;
 
                 let mut locks = std::collections::HashMap::new();
-                for link in links.iter() {
-                    let pk = self.0.data.select_non_ghosted(*link)?.get_primary_key().clone();
+                let mut pks = vec![];
+                for link in links {
+                    // Rows deleted after links were got are skipped.
+                    let Ok(row) = self.0.data.select_non_ghosted(link) else {
+                        continue;
+                    };
+                    let pk = row.get_primary_key().clone();
                     let op_lock = {
                         #custom_lock
                     };
-                    locks.insert(pk, op_lock);
+                    locks.insert(pk.clone(), op_lock);
+                    pks.push(pk);
                 }
 
-                let links: Vec<_> = self.0.indexes.#index.get(#by).map(|(_, l)| *l).collect();
                 let mut pk_to_unlock: std::collections::HashMap<_, std::sync::Arc<Lock>> = std::collections::HashMap::new();
-                let op_id = OperationId::Multi(uuid::Uuid::now_v7());
-                for link in links.into_iter() {
-                    let pk = self.0.data.select_non_ghosted(link)?.get_primary_key().clone();
-                    let mut bytes = rkyv::to_bytes::<rkyv::rancor::Error>(&row)
-                        .map_err(|_| WorkTableError::SerializeError)?;
-
-                    let mut archived_row = unsafe {
-                        rkyv::access_unchecked_mut::<<#query_ident as rkyv::Archive>::Archived>(&mut bytes[..])
-                            .unseal_unchecked()
-                    };
+                let res = async {
+                    let op_id = OperationId::Multi(uuid::Uuid::now_v7());
+                    for pk in pks {
+                        // Row could be moved, deleted or changed before its
+                        // lock was taken, so it is got and checked again.
+                        let Some(link) = self.0.pk_map.get(&pk).map(|kv| kv.get().value) else {
+                            continue;
+                        };
+                        if !self.0.data.select_non_ghosted(link).is_ok_and(|row| #check) {
+                            continue;
+                        }
+                        let mut bytes = rkyv::to_bytes::<rkyv::rancor::Error>(&row)
+                            .map_err(|_| WorkTableError::SerializeError)?;
+
+                        let mut archived_row = unsafe {
+                            rkyv::access_unchecked_mut::<<#query_ident as rkyv::Archive>::Archived>(&mut bytes[..])
+                                .unseal_unchecked()
+                        };
 
-                    #size_check
-                    #diff_process_insert
-                    #persist_op
+                        #size_check
+                        #diff_process_insert
+                        #persist_op
 
-                    unsafe {
-                        self.0.data.with_mut_ref(link, |archived| {
-                            #(#row_updates)*
-                        }).map_err(WorkTableError::PagesError)?;
-                    }
+                        unsafe {
+                            self.0.data.with_mut_ref(link, |archived| {
+                                #(#row_updates)*
+                            }).map_err(WorkTableError::PagesError)?;
+                        }
 
-                    #diff_process_remove
+                        #diff_process_remove
 
-                    #persist_call
+                        #persist_call
+                    }
+                    core::result::Result::Ok::<(), WorkTableError>(())
                 }
-                for (pk, lock) in pk_to_unlock {
+                .await;
+
+                // Locks are released on error too, so rows are not left locked.
+                for (pk, lock) in pk_to_unlock.into_iter().chain(locks) {
                     lock.unlock();
                     self.0.lock_map.remove_with_lock_check(&pk).await;
                 }
-                core::result::Result::Ok(())
+                res
             }
         }
     }
@@ -644,7 +910,7 @@ impl Generator {
         &self,
         snake_case_name: String,
         name: &Ident,
-        index: &Ident,
+        index: &Index,
         idents: &[Ident],
         idx_idents: Option<&Vec<Ident>>,
         unsized_fields: Option<Vec<&Ident>>,
@@ -671,16 +937,14 @@ impl Generator {
         let diff_process_remove = self.gen_process_diffs_remove_on_index(idx_idents);
         let persist_call = self.gen_persist_call();
         let persist_op = self.gen_persist_op();
-        let by = if is_float(by_ident.to_string().as_str()) {
-            quote! {
-                &OrderedFloat(by)
-            }
+        let by = self.gen_update_by_key(index);
+        let custom_lock = self.gen_custom_lock_for_update(lock_ident);
+        let index_name = &index.name;
+        let link = if index.is_hash {
+            quote! { self.0.indexes.#index_name.get(#by) }
         } else {
-            quote! {
-                &by
-            }
+            quote! { self.0.indexes.#index_name.get(#by).map(|kv| kv.get().value) }
         };
-        let custom_lock = self.gen_custom_lock_for_update(lock_ident);
 
         quote! {
             pub async fn #method_ident(&self, row: #query_ident, by: #by_ident) -> core::result::Result<(), WorkTableError> {
@@ -692,20 +956,14 @@ impl Generator {
                         .unseal_unchecked()
                 };
 
-                let link = self.0.indexes.#index
-                    .get(#by)
-                    .map(|kv| kv.get().value)
-                    .ok_or(WorkTableError::NotFound)?;
+                let link = #link.ok_or(WorkTableError::NotFound)?;
                 let pk = self.0.data.select_non_ghosted(link)?.get_primary_key().clone();
 
                 let lock = {
                     #custom_lock
                 };
 
-                let link = self.0.indexes.#index
-                    .get(#by)
-                    .map(|kv| kv.get().value)
-                    .ok_or(WorkTableError::NotFound)?;
+                let link = #link.ok_or(WorkTableError::NotFound)?;
 
                 let op_id = OperationId::Single(uuid::Uuid::now_v7());
                 #size_check
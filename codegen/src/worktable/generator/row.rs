@@ -1,4 +1,4 @@
-use crate::name_generator::WorktableNameGenerator;
+use crate::name_generator::{WorktableNameGenerator, is_float};
 use crate::worktable::generator::Generator;
 use convert_case::{Case, Casing};
 use proc_macro2::{Ident, Span, TokenStream};
@@ -10,12 +10,14 @@ impl Generator {
         let def = self.gen_row_type();
         let table_row_impl = self.gen_row_table_row_impl();
         let row_fields_enum = self.gen_row_fields_enum();
+        let column_value_enum = self.gen_column_value_enum();
         let query_impl = self.gen_query_impl();
 
         quote! {
             #def
             #table_row_impl
             #row_fields_enum
+            #column_value_enum
             #query_impl
         }
     }
@@ -26,31 +28,16 @@ impl Generator {
         let ident = name_generator.get_row_type_ident();
         let primary_key_ident = name_generator.get_primary_key_type_ident();
 
-        let primary_key = self
-            .pk
-            .clone()
-            .expect("should be set in `Generator` at this point");
-        let primary_key_columns_clone = if primary_key.values.len() == 1 {
-            let pk_field = primary_key
-                .values
-                .keys()
-                .next()
-                .expect("should exist as length is checked");
+        // Columns are taken from definition, as primary key tuple must follow
+        // declaration order.
+        let primary_keys = &self.columns.primary_keys;
+        let primary_key_columns_clone = if let [pk_field] = primary_keys.as_slice() {
             quote! {
                 self.#pk_field.clone().into()
             }
         } else {
-            let vals = primary_key
-                .values
-                .keys()
-                .map(|i| {
-                    quote! {
-                        self.#i.clone()
-                    }
-                })
-                .collect::<Vec<_>>();
             quote! {
-                (#(#vals),*).into()
+                (#(self.#primary_keys.clone()),*).into()
             }
         };
 
@@ -137,6 +124,80 @@ impl Generator {
             }
         }
     }
+
+    /// Generates `ColumnValue` enum which holds value of one of the row's
+    /// columns, and `get_column_value` fn for row to get it by `RowFields`.
+    fn gen_column_value_enum(&self) -> TokenStream {
+        let name_generator = WorktableNameGenerator::from_table_name(self.name.to_string());
+        let row_ident = name_generator.get_row_type_ident();
+        let row_fields_ident = name_generator.get_row_fields_enum_ident();
+        let ident = name_generator.get_column_value_type_ident();
+
+        let mut variants = vec![];
+        let mut matches = vec![];
+        let mut cmp_matches = vec![];
+        for (name, type_) in &self.columns.columns_map {
+            let name_pascal = Ident::new(
+                name.to_string().to_case(Case::Pascal).as_str(),
+                Span::mixed_site(),
+            );
+            variants.push(quote! { #name_pascal(#type_), });
+            matches.push(quote! {
+                #row_fields_ident::#name_pascal => #ident::#name_pascal(self.#name.clone()),
+            });
+            let is_float_value = self
+                .columns
+                .value_type(name)
+                .is_some_and(|ty| is_float(ty.to_string().as_str()));
+            let cmp = match is_float_value {
+                true if self.columns.is_optional(name) => {
+                    quote! {
+                        match (a, b) {
+                            (Some(a), Some(b)) => a.total_cmp(b),
+                            _ => a.is_some().cmp(&b.is_some()),
+                        }
+                    }
+                }
+                true => quote! { a.total_cmp(b) },
+                false => quote! { a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal) },
+            };
+            cmp_matches.push(quote! {
+                (Self::#name_pascal(a), Self::#name_pascal(b)) => #cmp,
+            });
+        }
+        let other_variants = if self.columns.columns_map.len() > 1 {
+            quote! {
+                _ => self.partial_cmp(other).unwrap_or(std::cmp::Ordering::Equal),
+            }
+        } else {
+            quote! {}
+        };
+
+        quote! {
+            #[derive(Debug, Clone, PartialEq, PartialOrd)]
+            #[allow(private_interfaces)]
+            pub enum #ident {
+                #(#variants)*
+            }
+
+            impl TotalCmp for #ident {
+                fn total_cmp(&self, other: &Self) -> std::cmp::Ordering {
+                    match (self, other) {
+                        #(#cmp_matches)*
+                        #other_variants
+                    }
+                }
+            }
+
+            impl #row_ident {
+                pub fn get_column_value(&self, column: &#row_fields_ident) -> #ident {
+                    match column {
+                        #(#matches)*
+                    }
+                }
+            }
+        }
+    }
 }
 
 // TODO: tests...
@@ -0,0 +1,402 @@
+use convert_case::{Case, Casing};
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::{ToTokens, quote};
+use syn::Type;
+
+use crate::name_generator::{WorktableNameGenerator, is_float};
+use crate::worktable::generator::Generator;
+use crate::worktable::generator::table::select_executor::is_numeric_type;
+
+impl Generator {
+    fn numeric_columns(&self) -> Vec<&Ident> {
+        self.columns
+            .columns_map
+            .iter()
+            .filter(|(_, ty)| {
+                is_numeric_type(&syn::parse_str::<Type>(&ty.to_token_stream().to_string()).unwrap())
+            })
+            .map(|(column, _)| column)
+            .collect()
+    }
+
+    /// Generates helper fns for `ColumnValue` which are used to aggregate
+    /// rows and `SelectAggregateExecutor`/`SelectGroupExecutor` impls for
+    /// table's `SelectQueryBuilder`.
+    pub fn gen_table_select_aggregate_impl(&self) -> TokenStream {
+        let name_generator = WorktableNameGenerator::from_table_name(self.name.to_string());
+        let row_type = name_generator.get_row_type_ident();
+        let column_range_type = name_generator.get_column_range_type_ident();
+        let row_fields_ident = name_generator.get_row_fields_enum_ident();
+        let column_value_ident = name_generator.get_column_value_type_ident();
+
+        let numeric_columns = self.numeric_columns();
+        let mut zero_matches = vec![];
+        let mut add_matches = vec![];
+        let mut numeric_matches = vec![];
+        for column in &numeric_columns {
+            let column_variant = Ident::new(
+                &column.to_string().to_case(Case::Pascal),
+                Span::mixed_site(),
+            );
+            let ty = self
+                .columns
+                .columns_map
+                .get(*column)
+                .expect("numeric column should exist");
+            zero_matches.push(quote! {
+                #row_fields_ident::#column_variant => Ok(Self::#column_variant(0 as #ty)),
+            });
+            // Integer sum is checked, so overflow is returned as error
+            // instead of panic or wrapped value.
+            add_matches.push(if is_float(ty.to_string().as_str()) {
+                quote! {
+                    Self::#column_variant(sum) => *sum += row.#column,
+                }
+            } else {
+                quote! {
+                    Self::#column_variant(sum) => {
+                        *sum = sum.checked_add(row.#column).ok_or_else(|| {
+                            WorkTableError::SumOverflow(format!("{:?}", #row_fields_ident::#column_variant))
+                        })?
+                    }
+                }
+            });
+            numeric_matches.push(quote! {
+                #row_fields_ident::#column_variant => Ok(row.#column as f64),
+            });
+        }
+        let not_numeric = if numeric_columns.len() == self.columns.columns_map.len() {
+            quote! {}
+        } else {
+            quote! {
+                _ => Err(WorkTableError::NotNumericColumn(format!("{:?}", column))),
+            }
+        };
+        let not_numeric_sum = if numeric_columns.len() == self.columns.columns_map.len() {
+            quote! {}
+        } else {
+            quote! {
+                _ => unreachable!("sum is started with numeric column zero"),
+            }
+        };
+
+        quote! {
+            impl #column_value_ident {
+                fn sum_zero(column: &#row_fields_ident) -> Result<Self, WorkTableError> {
+                    match column {
+                        #(#zero_matches)*
+                        #not_numeric
+                    }
+                }
+
+                fn add_to_sum(&mut self, row: &#row_type) -> Result<(), WorkTableError> {
+                    match self {
+                        #(#add_matches)*
+                        #not_numeric_sum
+                    }
+                    Ok(())
+                }
+
+                fn numeric_value(row: &#row_type, column: &#row_fields_ident) -> Result<f64, WorkTableError> {
+                    match column {
+                        #(#numeric_matches)*
+                        #not_numeric
+                    }
+                }
+
+                fn sum_rows(rows: impl Iterator<Item = #row_type>, column: &#row_fields_ident) -> Result<Self, WorkTableError> {
+                    let mut sum = Self::sum_zero(column)?;
+                    for row in rows {
+                        sum.add_to_sum(&row)?;
+                    }
+                    Ok(sum)
+                }
+
+                fn avg_rows(rows: impl Iterator<Item = #row_type>, column: &#row_fields_ident) -> Result<Option<f64>, WorkTableError> {
+                    Self::sum_zero(column)?;
+                    let (mut sum, mut count) = (0f64, 0usize);
+                    for row in rows {
+                        sum += Self::numeric_value(&row, column)?;
+                        count += 1;
+                    }
+                    Ok((count != 0).then(|| sum / count as f64))
+                }
+
+                fn min_of(min: &mut Option<Self>, value: Self) {
+                    if min.as_ref().is_none_or(|m| value < *m) {
+                        *min = Some(value);
+                    }
+                }
+
+                fn max_of(max: &mut Option<Self>, value: Self) {
+                    if max.as_ref().is_none_or(|m| value > *m) {
+                        *max = Some(value);
+                    }
+                }
+
+                /// Folds rows of each distinct `column` value into its own
+                /// accumulator. Groups are ordered by `TotalCmp` of their
+                /// keys, so `NaN` values are in one group.
+                fn group_rows<A>(
+                    rows: impl Iterator<Item = #row_type>,
+                    column: &#row_fields_ident,
+                    mut init: impl FnMut() -> A,
+                    mut f: impl FnMut(&mut A, #row_type) -> Result<(), WorkTableError>,
+                ) -> Result<Vec<(Self, A)>, WorkTableError> {
+                    let mut groups = std::collections::BTreeMap::new();
+                    for row in rows {
+                        let acc = groups
+                            .entry(TotalOrd(row.get_column_value(column)))
+                            .or_insert_with(&mut init);
+                        f(acc, row)?;
+                    }
+                    Ok(groups.into_iter().map(|(key, acc)| (key.0, acc)).collect())
+                }
+            }
+
+            impl<I> SelectAggregateExecutor<#row_fields_ident>
+            for SelectQueryBuilder<#row_type, I, #column_range_type, #row_fields_ident>
+            where
+                I: DoubleEndedIterator<Item = #row_type> + Sized,
+            {
+                type Value = #column_value_ident;
+
+                fn count(self) -> Result<usize, WorkTableError> {
+                    Ok(self.rows().count())
+                }
+
+                fn sum(self, column: #row_fields_ident) -> Result<#column_value_ident, WorkTableError> {
+                    #column_value_ident::sum_rows(self.rows(), &column)
+                }
+
+                fn min(self, column: #row_fields_ident) -> Result<Option<#column_value_ident>, WorkTableError> {
+                    let mut min = None;
+                    for row in self.rows() {
+                        #column_value_ident::min_of(&mut min, row.get_column_value(&column));
+                    }
+                    Ok(min)
+                }
+
+                fn max(self, column: #row_fields_ident) -> Result<Option<#column_value_ident>, WorkTableError> {
+                    let mut max = None;
+                    for row in self.rows() {
+                        #column_value_ident::max_of(&mut max, row.get_column_value(&column));
+                    }
+                    Ok(max)
+                }
+
+                fn avg(self, column: #row_fields_ident) -> Result<Option<f64>, WorkTableError> {
+                    #column_value_ident::avg_rows(self.rows(), &column)
+                }
+            }
+
+            impl<I> SelectGroupExecutor<#row_fields_ident>
+            for SelectGroupBuilder<SelectQueryBuilder<#row_type, I, #column_range_type, #row_fields_ident>, #row_fields_ident>
+            where
+                I: DoubleEndedIterator<Item = #row_type> + Sized,
+            {
+                type Value = #column_value_ident;
+
+                fn count(self) -> Result<Vec<(#column_value_ident, usize)>, WorkTableError> {
+                    #column_value_ident::group_rows(self.query.rows(), &self.column, || 0, |count, _| {
+                        *count += 1;
+                        Ok(())
+                    })
+                }
+
+                fn sum(self, column: #row_fields_ident) -> Result<Vec<(#column_value_ident, #column_value_ident)>, WorkTableError> {
+                    let zero = #column_value_ident::sum_zero(&column)?;
+                    #column_value_ident::group_rows(self.query.rows(), &self.column, || zero.clone(), |sum, row| {
+                        sum.add_to_sum(&row)
+                    })
+                }
+
+                fn min(self, column: #row_fields_ident) -> Result<Vec<(#column_value_ident, #column_value_ident)>, WorkTableError> {
+                    let groups = #column_value_ident::group_rows(self.query.rows(), &self.column, || None, |min, row| {
+                        #column_value_ident::min_of(min, row.get_column_value(&column));
+                        Ok(())
+                    })?;
+                    Ok(groups
+                        .into_iter()
+                        .map(|(key, min)| (key, min.expect("group is not empty")))
+                        .collect())
+                }
+
+                fn max(self, column: #row_fields_ident) -> Result<Vec<(#column_value_ident, #column_value_ident)>, WorkTableError> {
+                    let groups = #column_value_ident::group_rows(self.query.rows(), &self.column, || None, |max, row| {
+                        #column_value_ident::max_of(max, row.get_column_value(&column));
+                        Ok(())
+                    })?;
+                    Ok(groups
+                        .into_iter()
+                        .map(|(key, max)| (key, max.expect("group is not empty")))
+                        .collect())
+                }
+
+                fn avg(self, column: #row_fields_ident) -> Result<Vec<(#column_value_ident, f64)>, WorkTableError> {
+                    #column_value_ident::sum_zero(&column)?;
+                    let groups = #column_value_ident::group_rows(self.query.rows(), &self.column, || (0f64, 0usize), |(sum, count), row| {
+                        *sum += #column_value_ident::numeric_value(&row, &column)?;
+                        *count += 1;
+                        Ok(())
+                    })?;
+                    Ok(groups
+                        .into_iter()
+                        .map(|(key, (sum, count))| (key, sum / count as f64))
+                        .collect())
+                }
+            }
+        }
+    }
+
+    /// Generates `count_grouped_by` fn for table. For indexed columns rows are
+    /// counted using index only, for others `select_all` is used.
+    pub fn gen_table_count_grouped_by_fn(&self) -> TokenStream {
+        let name_generator = WorktableNameGenerator::from_table_name(self.name.to_string());
+        let ident = name_generator.get_work_table_ident();
+        let row_fields_ident = name_generator.get_row_fields_enum_ident();
+        let column_value_ident = name_generator.get_column_value_type_ident();
+
+        let index_matches = self.columns.ordered_value_indexes().map(|(column, idx)| {
+            let column_variant = Ident::new(
+                &column.to_string().to_case(Case::Pascal),
+                Span::mixed_site(),
+            );
+            let index_field = &idx.name;
+            let ty = self
+                .columns
+                .columns_map
+                .get(column)
+                .expect("index column should exist");
+            let key = if is_float(ty.to_string().as_str()) {
+                quote! { key.0 }
+            } else {
+                quote! { key.clone() }
+            };
+            if idx.is_unique {
+                quote! {
+                    #row_fields_ident::#column_variant => Ok(self.0.indexes.#index_field
+                        .iter()
+                        .map(|(key, _)| (#column_value_ident::#column_variant(#key), 1))
+                        .collect()),
+                }
+            } else {
+                quote! {
+                    #row_fields_ident::#column_variant => {
+                        let mut groups: Vec<(#column_value_ident, usize)> = vec![];
+                        for (key, _) in self.0.indexes.#index_field.iter() {
+                            let key = #column_value_ident::#column_variant(#key);
+                            match groups.last_mut() {
+                                Some((last, count)) if *last == key => *count += 1,
+                                _ => groups.push((key, 1)),
+                            }
+                        }
+                        Ok(groups)
+                    }
+                }
+            }
+        });
+        let fallback =
+            if self.columns.ordered_value_indexes().count() == self.columns.columns_map.len() {
+                quote! {}
+            } else {
+                quote! {
+                    _ => self.select_all().group_by(column).count(),
+                }
+            };
+
+        quote! {
+            impl #ident {
+                /// Returns rows count for each distinct value of `column`.
+                /// Indexed columns are counted without reading rows data.
+                pub fn count_grouped_by(&self, column: #row_fields_ident) -> Result<Vec<(#column_value_ident, usize)>, WorkTableError> {
+                    match column {
+                        #(#index_matches)*
+                        #fallback
+                    }
+                }
+            }
+        }
+    }
+
+    /// Generates `distinct_values` fn for table. For indexed columns only
+    /// index keys are read: non-unique index is seeked to the next key after
+    /// each found one, so duplicates are skipped without iterating over them.
+    pub fn gen_table_distinct_values_fn(&self) -> TokenStream {
+        let name_generator = WorktableNameGenerator::from_table_name(self.name.to_string());
+        let ident = name_generator.get_work_table_ident();
+        let row_fields_ident = name_generator.get_row_fields_enum_ident();
+        let column_value_ident = name_generator.get_column_value_type_ident();
+
+        let index_matches = self.columns.ordered_value_indexes().map(|(column, idx)| {
+            let column_variant = Ident::new(
+                &column.to_string().to_case(Case::Pascal),
+                Span::mixed_site(),
+            );
+            let index_field = &idx.name;
+            let ty = self
+                .columns
+                .columns_map
+                .get(column)
+                .expect("index column should exist");
+            let key = if is_float(ty.to_string().as_str()) {
+                quote! { key.0 }
+            } else {
+                quote! { key.clone() }
+            };
+            if idx.is_unique {
+                quote! {
+                    #row_fields_ident::#column_variant => Ok(self.0.indexes.#index_field
+                        .iter()
+                        .map(|(key, _)| #column_value_ident::#column_variant(#key))
+                        .collect()),
+                }
+            } else {
+                quote! {
+                    #row_fields_ident::#column_variant => {
+                        let mut values = vec![];
+                        let mut next = self.0.indexes.#index_field
+                            .iter()
+                            .next()
+                            .map(|(key, _)| key.clone());
+                        while let Some(key) = next {
+                            next = self.0.indexes.#index_field
+                                .range((std::ops::Bound::Excluded(key.clone()), std::ops::Bound::Unbounded))
+                                .next()
+                                .map(|(key, _)| key.clone());
+                            values.push(#column_value_ident::#column_variant(#key));
+                        }
+                        Ok(values)
+                    }
+                }
+            }
+        });
+        let fallback =
+            if self.columns.ordered_value_indexes().count() == self.columns.columns_map.len() {
+                quote! {}
+            } else {
+                quote! {
+                    _ => Ok(self
+                        .select_all()
+                        .group_by(column)
+                        .count()?
+                        .into_iter()
+                        .map(|(value, _)| value)
+                        .collect()),
+                }
+            };
+
+        quote! {
+            impl #ident {
+                /// Returns distinct values of `column` in ascending order.
+                /// Indexed columns are read without reading rows data.
+                pub fn distinct_values(&self, column: #row_fields_ident) -> Result<Vec<#column_value_ident>, WorkTableError> {
+                    match column {
+                        #(#index_matches)*
+                        #fallback
+                    }
+                }
+            }
+        }
+    }
+}
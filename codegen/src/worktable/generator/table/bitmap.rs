@@ -0,0 +1,113 @@
+use proc_macro2::{Literal, TokenStream};
+use quote::quote;
+
+use crate::name_generator::{WorktableNameGenerator, is_float};
+use crate::worktable::generator::Generator;
+
+impl Generator {
+    /// Generates `<Name>BitmapFilter` type with optional value for every
+    /// `bitmap` indexed column and `select_by_bitmaps`/`count_by_bitmaps` fns.
+    /// Bitmaps of the set values are intersected, so only matching rows are
+    /// read from data pages.
+    pub fn gen_table_bitmap_fns(&self) -> syn::Result<TokenStream> {
+        let name_generator = WorktableNameGenerator::from_table_name(self.name.to_string());
+        let ident = name_generator.get_work_table_ident();
+        let row_ident = name_generator.get_row_type_ident();
+        let column_range_type = name_generator.get_column_range_type_ident();
+        let row_fields_ident = name_generator.get_row_fields_enum_ident();
+        let filter_ident = name_generator.get_bitmap_filter_ident();
+
+        let mut indexes: Vec<_> = self
+            .columns
+            .indexes
+            .iter()
+            .filter(|(_, idx)| idx.is_bitmap)
+            .collect();
+        if indexes.is_empty() {
+            return Ok(quote! {});
+        }
+        indexes.sort_by_key(|(i, _)| i.to_string());
+
+        let mut fields = vec![];
+        let mut intersections = vec![];
+        let mut checks = vec![];
+        for (i, idx) in &indexes {
+            let type_ = self.columns.index_key_type(idx)?;
+            let index_field = &idx.name;
+            let index_name = Literal::string(&idx.name.to_string());
+            let key = if is_float(type_.to_string().as_str()) {
+                quote! { &OrderedFloat(*v) }
+            } else {
+                quote! { v }
+            };
+            fields.push(quote! {
+                pub #i: Option<#type_>,
+            });
+            intersections.push(quote! {
+                if let Some(v) = &filter.#i {
+                    let values = self.0.indexes.#index_field.get(#key);
+                    bitmap = Some(match bitmap {
+                        Some(bitmap) => bitmap.intersection(&values),
+                        None => values,
+                    });
+                    used.push(#index_name);
+                }
+            });
+            checks.push(quote! {
+                filter.#i.as_ref().is_none_or(|v| &r.#i == v)
+            });
+        }
+        let first_index = &indexes[0].1.name;
+
+        Ok(quote! {
+            #[derive(Clone, Debug, Default)]
+            pub struct #filter_ident {
+                #(#fields)*
+            }
+
+            impl #ident {
+                /// Returns intersection of the bitmaps for the values set in
+                /// filter and names of the used indexes. Returns `None` if no
+                /// value is set.
+                fn bitmap_of(&self, filter: &#filter_ident) -> (Option<RowBitmap>, Vec<&'static str>) {
+                    let mut bitmap: Option<RowBitmap> = None;
+                    let mut used = vec![];
+                    #(#intersections)*
+                    (bitmap, used)
+                }
+
+                pub fn select_by_bitmaps(&self, filter: #filter_ident) -> SelectQueryBuilder<#row_ident,
+                                                                          impl DoubleEndedIterator<Item = #row_ident> + ArchivedFilter<'_, <#row_ident as rkyv::Archive>::Archived> + '_,
+                                                                          #column_range_type,
+                                                                          #row_fields_ident>
+                {
+                    let (bitmap, used) = self.bitmap_of(&filter);
+                    let (links, access_path): (Vec<_>, _) = if let Some(bitmap) = bitmap {
+                        (
+                            self.0.indexes.#first_index.links(&bitmap),
+                            AccessPath::BitmapIntersection(used),
+                        )
+                    } else {
+                        (
+                            self.0.pk_map.iter().map(|(_, link)| *link).collect(),
+                            AccessPath::FullScan,
+                        )
+                    };
+                    let estimated_rows = links.len();
+                    let rows = ArchivedRows::new(links.into_iter(), move |link, check| {
+                        self.read_archived_row(link, check).filter(|r| true #(&& #checks)*)
+                    });
+
+                    SelectQueryBuilder::new(rows).with_access_path(access_path, estimated_rows)
+                }
+
+                pub fn count_by_bitmaps(&self, filter: #filter_ident) -> usize {
+                    match self.bitmap_of(&filter).0 {
+                        Some(bitmap) => bitmap.len(),
+                        None => self.0.pk_map.len(),
+                    }
+                }
+            }
+        })
+    }
+}
@@ -1,7 +1,7 @@
 use proc_macro2::TokenStream;
 use quote::quote;
 
-use crate::name_generator::{WorktableNameGenerator, is_unsized_vec};
+use crate::name_generator::{WorktableNameGenerator, is_float};
 use crate::worktable::generator::Generator;
 use crate::worktable::model::GeneratorType;
 
@@ -21,6 +21,7 @@ impl Generator {
         let iter_with_async_fn = self.gen_table_iter_with_async_fn();
         let count_fn = self.gen_table_count_fn();
         let system_info_fn = self.gen_system_info_fn();
+        let rebuild_in_memory_indexes_fn = self.gen_table_rebuild_in_memory_indexes_fn();
 
         quote! {
             impl #ident {
@@ -35,6 +36,62 @@ impl Generator {
                 #iter_with_fn
                 #iter_with_async_fn
                 #system_info_fn
+                #rebuild_in_memory_indexes_fn
+            }
+        }
+    }
+
+    /// Generates `rebuild_in_memory_indexes` fn for persisted table. Hash and
+    /// bitmap indexes are not persisted, so they are filled from rows when
+    /// table is loaded.
+    fn gen_table_rebuild_in_memory_indexes_fn(&self) -> TokenStream {
+        if !self.is_persist {
+            return quote! {};
+        }
+
+        let inserts = self
+            .columns
+            .indexes
+            .iter()
+            .filter(|(_, idx)| idx.is_rebuilt_on_load())
+            .map(|(i, idx)| {
+                let index_field = &idx.name;
+                if let Some(keys) = self.gen_row_index_keys(idx, quote! { row }) {
+                    return quote! {
+                        for key in #keys {
+                            TableIndex::insert(&self.0.indexes.#index_field, key, *link);
+                        }
+                    };
+                }
+                let key = if self
+                    .columns
+                    .columns_map
+                    .get(i)
+                    .is_some_and(|t| is_float(t.to_string().as_str()))
+                {
+                    quote! { OrderedFloat(row.#i) }
+                } else {
+                    quote! { row.#i.clone() }
+                };
+                quote! {
+                    TableIndex::insert(&self.0.indexes.#index_field, #key, *link);
+                }
+            })
+            .collect::<Vec<_>>();
+        if inserts.is_empty() {
+            return quote! {
+                fn rebuild_in_memory_indexes(&self) {}
+            };
+        }
+
+        quote! {
+            fn rebuild_in_memory_indexes(&self) {
+                for (_, link) in self.0.pk_map.iter() {
+                    let Ok(row) = self.0.data.select_non_ghosted(*link) else {
+                        continue;
+                    };
+                    #(#inserts)*
+                }
             }
         }
     }
@@ -49,19 +106,7 @@ impl Generator {
         let const_name = name_generator.get_page_inner_size_const_ident();
 
         if self.is_persist {
-            let pk_types = &self
-                .columns
-                .primary_keys
-                .iter()
-                .map(|i| {
-                    self.columns
-                        .columns_map
-                        .get(i)
-                        .expect("should exist as got from definition")
-                        .to_string()
-                })
-                .collect::<Vec<_>>();
-            let pk_types_unsized = is_unsized_vec(pk_types);
+            let pk_types_unsized = self.columns.is_primary_key_unsized();
             let index_size = if pk_types_unsized {
                 quote! {
                     let size = #const_name;
@@ -1,6 +1,4 @@
-use std::collections::HashMap;
-
-use proc_macro2::{Ident, Span, TokenStream};
+use proc_macro2::{Ident, Literal, Span, TokenStream};
 use quote::quote;
 
 use crate::name_generator::{WorktableNameGenerator, is_float};
@@ -15,18 +13,27 @@ impl Generator {
         let column_range_type = name_generator.get_column_range_type_ident();
         let row_fields_ident = name_generator.get_row_fields_enum_ident();
 
+        // Spatial index has own select fns, see `gen_table_spatial_fns`.
         let fn_defs = self
             .columns
             .indexes
             .iter()
+            .filter(|(_, idx)| !idx.is_spatial)
             .map(|(i, idx)| {
-                if idx.is_unique {
-                    Self::gen_unique_index_fn(i, idx, &self.columns.columns_map, row_ident.clone())
+                if idx.is_array {
+                    self.gen_array_index_fn(
+                        i,
+                        idx,
+                        row_ident.clone(),
+                        &column_range_type,
+                        &row_fields_ident,
+                    )
+                } else if idx.is_unique {
+                    self.gen_unique_index_fn(i, idx, row_ident.clone())
                 } else {
-                    Self::gen_non_unique_index_fn(
+                    self.gen_non_unique_index_fn(
                         i,
                         idx,
-                        &self.columns.columns_map,
                         row_ident.clone(),
                         &column_range_type,
                         &row_fields_ident,
@@ -34,56 +41,241 @@ impl Generator {
                 }
             })
             .collect::<Result<Vec<_>, syn::Error>>()?;
+        let count_fn_defs = self
+            .columns
+            .indexes
+            .iter()
+            .filter(|(_, idx)| !idx.is_spatial)
+            .map(|(i, idx)| {
+                if idx.is_array {
+                    self.gen_array_index_count_fns(i, idx)
+                } else {
+                    self.gen_index_count_fns(i, idx)
+                }
+            })
+            .collect::<Result<Vec<_>, syn::Error>>()?;
+        let optional_fn_defs = self
+            .columns
+            .indexes
+            .iter()
+            .filter(|(i, _)| self.columns.is_optional(i))
+            .map(|(i, idx)| {
+                self.gen_optional_index_fns(
+                    i,
+                    idx,
+                    row_ident.clone(),
+                    &column_range_type,
+                    &row_fields_ident,
+                )
+            })
+            .collect::<Result<Vec<_>, syn::Error>>()?;
+        let prefix_fn_defs = self
+            .columns
+            .indexes
+            .iter()
+            .filter(|(_, idx)| idx.is_prefix)
+            .map(|(i, idx)| {
+                self.gen_prefix_index_fn(
+                    i,
+                    idx,
+                    row_ident.clone(),
+                    &column_range_type,
+                    &row_fields_ident,
+                )
+            })
+            .collect::<Vec<_>>();
+        let pk_count_range_fn = self.gen_pk_count_range_fn();
 
         Ok(quote! {
             impl #ident {
                 #(#fn_defs)*
+                #(#count_fn_defs)*
+                #(#optional_fn_defs)*
+                #(#prefix_fn_defs)*
+                #pk_count_range_fn
+            }
+        })
+    }
+
+    /// Generates `count_by_<column>`, `exists_by_<column>` and
+    /// `count_range_by_<column>` fns. They use only index, so no row is read
+    /// from data pages. `hash` and `bitmap` indexes have no
+    /// `count_range_by_<column>`.
+    fn gen_index_count_fns(&self, i: &Ident, idx: &Index) -> syn::Result<TokenStream> {
+        let type_ = self.columns.index_key_type(idx)?;
+        let count_fn_name = Ident::new(format!("count_by_{i}").as_str(), Span::mixed_site());
+        let exists_fn_name = Ident::new(format!("exists_by_{i}").as_str(), Span::mixed_site());
+        let count_range_fn_name =
+            Ident::new(format!("count_range_by_{i}").as_str(), Span::mixed_site());
+        let field_ident = &idx.name;
+        let (by, bound) = if is_float(type_.to_string().as_str()) {
+            (
+                quote! {
+                    &OrderedFloat(by)
+                },
+                quote! {
+                    .map(OrderedFloat)
+                },
+            )
+        } else if idx.is_ci {
+            (
+                quote! {
+                    &by.to_lowercase()
+                },
+                quote! {
+                    .map(|v| v.to_lowercase())
+                },
+            )
+        } else {
+            (
+                quote! {
+                    &by
+                },
+                quote! {},
+            )
+        };
+        let count = if idx.is_unique {
+            quote! {
+                self.0.indexes.#field_ident.contains_key(#by) as usize
+            }
+        } else if idx.is_hash {
+            quote! {
+                self.0.indexes.#field_ident.get(#by).len()
+            }
+        } else if idx.is_bitmap {
+            quote! {
+                self.0.indexes.#field_ident.count(#by)
+            }
+        } else {
+            quote! {
+                self.0.indexes.#field_ident.get(#by).count()
+            }
+        };
+        let count_range_fn = if !idx.is_ordered() {
+            quote! {}
+        } else {
+            quote! {
+                pub fn #count_range_fn_name<R>(&self, range: R) -> usize
+                where
+                    R: std::ops::RangeBounds<#type_>,
+                {
+                    let range = (
+                        range.start_bound().cloned()#bound,
+                        range.end_bound().cloned()#bound,
+                    );
+                    self.0.indexes.#field_ident.range(range).count()
+                }
             }
+        };
+
+        Ok(quote! {
+            pub fn #count_fn_name(&self, by: #type_) -> usize {
+                #count
+            }
+
+            pub fn #exists_fn_name(&self, by: #type_) -> bool {
+                self.0.indexes.#field_ident.contains_key(#by)
+            }
+
+            #count_range_fn
         })
     }
 
+    /// Generates `count_by_<column>_contains` and `exists_by_<column>_contains`
+    /// fns for array index.
+    fn gen_array_index_count_fns(&self, i: &Ident, idx: &Index) -> syn::Result<TokenStream> {
+        let type_ = self.columns.index_key_type(idx)?;
+        let count_fn_name = Ident::new(
+            format!("count_by_{i}_contains").as_str(),
+            Span::mixed_site(),
+        );
+        let exists_fn_name = Ident::new(
+            format!("exists_by_{i}_contains").as_str(),
+            Span::mixed_site(),
+        );
+        let field_ident = &idx.name;
+
+        Ok(quote! {
+            pub fn #count_fn_name(&self, elem: #type_) -> usize {
+                self.0.indexes.#field_ident.get(&elem).count()
+            }
+
+            pub fn #exists_fn_name(&self, elem: #type_) -> bool {
+                self.0.indexes.#field_ident.contains_key(&elem)
+            }
+        })
+    }
+
+    /// Generates `count_range` fn which counts rows with primary key in
+    /// provided range using `pk_map` only.
+    fn gen_pk_count_range_fn(&self) -> TokenStream {
+        let name_generator = WorktableNameGenerator::from_table_name(self.name.to_string());
+        let primary_key_type = name_generator.get_primary_key_type_ident();
+
+        quote! {
+            pub fn count_range<Pk, R>(&self, range: R) -> usize
+            where
+                R: std::ops::RangeBounds<Pk>,
+                Pk: Clone,
+                #primary_key_type: From<Pk>,
+            {
+                let range: (std::ops::Bound<#primary_key_type>, std::ops::Bound<#primary_key_type>) = (
+                    range.start_bound().cloned().map(Into::into),
+                    range.end_bound().cloned().map(Into::into),
+                );
+                self.0.pk_map.range(range).count()
+            }
+        }
+    }
+
     fn gen_unique_index_fn(
+        &self,
         i: &Ident,
         idx: &Index,
-        columns_map: &HashMap<Ident, TokenStream>,
         row_ident: Ident,
     ) -> syn::Result<TokenStream> {
-        let type_ = columns_map
-            .get(i)
-            .ok_or(syn::Error::new(i.span(), "Row not found"))?;
+        let type_ = self.columns.index_key_type(idx)?;
         let fn_name = Ident::new(format!("select_by_{i}").as_str(), Span::mixed_site());
         let field_ident = &idx.name;
         let by = if is_float(type_.to_string().as_str()) {
             quote! {
                 &OrderedFloat(by)
             }
+        } else if idx.is_ci {
+            quote! {
+                &by.to_lowercase()
+            }
         } else {
             quote! {
                 &by
             }
         };
 
+        let link = if idx.is_hash {
+            quote! { self.0.indexes.#field_ident.get(#by)? }
+        } else {
+            quote! { self.0.indexes.#field_ident.get(#by).map(|kv| kv.get().value)? }
+        };
+
         Ok(quote! {
             pub fn #fn_name(&self, by: #type_) -> Option<#row_ident> {
-                let link = self.0.indexes.#field_ident.get(#by).map(|kv| kv.get().value)?;
+                let link = #link;
                 self.0.data.select_non_ghosted(link).ok()
             }
         })
     }
 
     fn gen_non_unique_index_fn(
+        &self,
         i: &Ident,
         idx: &Index,
-        columns_map: &HashMap<Ident, TokenStream>,
         row_ident: Ident,
         column_range_type: &Ident,
         row_fields_ident: &Ident,
     ) -> syn::Result<TokenStream> {
-        let type_ = columns_map
-            .get(i)
-            .ok_or(syn::Error::new(i.span(), "Row not found"))?;
+        let type_ = self.columns.index_key_type(idx)?;
         let fn_name = Ident::new(format!("select_by_{i}").as_str(), Span::mixed_site());
-        let field_ident = &idx.name;
+        let index_name = Literal::string(&idx.name.to_string());
         let row_field_ident = &idx.field;
         let by = if is_float(type_.to_string().as_str()) {
             quote! {
@@ -94,20 +286,202 @@ impl Generator {
                 &by
             }
         };
+        let filter = match (self.columns.is_optional(i), idx.is_ci) {
+            _ if idx.is_computed() => {
+                let key = self.gen_computed_key(idx, &quote! { r });
+                quote! { #key == by }
+            }
+            (true, true) => {
+                quote! { r.#row_field_ident.as_ref().is_some_and(|v| v.to_lowercase() == by) }
+            }
+            (true, false) => quote! { r.#row_field_ident.as_ref() == Some(&by) },
+            (false, true) => quote! { r.#row_field_ident.to_lowercase() == by },
+            (false, false) => quote! { &r.#row_field_ident == &by },
+        };
+        let filter = match self.gen_index_predicate(idx, &quote! { r }) {
+            Some(predicate) => quote! { #filter && #predicate },
+            None => filter,
+        };
+        let normalize = if idx.is_ci {
+            quote! { let by = by.to_lowercase(); }
+        } else {
+            quote! {}
+        };
+        let links = Self::gen_non_unique_index_links(idx, &by);
 
         Ok(quote! {
             pub fn #fn_name(&self, by: #type_) -> SelectQueryBuilder<#row_ident,
-                                                                     impl DoubleEndedIterator<Item = #row_ident> + '_,
+                                                                     impl DoubleEndedIterator<Item = #row_ident> + ArchivedFilter<'_, <#row_ident as rkyv::Archive>::Archived> + '_,
                                                                      #column_range_type,
                                                                      #row_fields_ident>
             {
-                let rows = self.0.indexes.#field_ident
-                    .get(#by)
-                    .into_iter()
-                    .filter_map(|(_, link)| self.0.data.select_non_ghosted(*link).ok())
-                    .filter(move |r| &r.#row_field_ident == &by);
+                #normalize
+                // Links are collected first to not hold index locks while
+                // rows are read.
+                let links: Vec<_> = #links;
+                let estimated_rows = links.len();
+                let rows = ArchivedRows::new(links.into_iter(), move |link, check| {
+                    self.read_archived_row(link, check).filter(|r| #filter)
+                });
+
+                SelectQueryBuilder::new(rows)
+                    .with_access_path(AccessPath::IndexExact(#index_name), estimated_rows)
+            }
+        })
+    }
+
+    /// Generates `select_by_<column>_is_none` and `select_range_by_<column>`
+    /// fns for index on optional column. `None` values are not indexed, so
+    /// `select_by_<column>_is_none` checks all rows and range is got over
+    /// `Some` values only. `hash` and `bitmap` indexes have no range fn.
+    fn gen_optional_index_fns(
+        &self,
+        i: &Ident,
+        idx: &Index,
+        row_ident: Ident,
+        column_range_type: &Ident,
+        row_fields_ident: &Ident,
+    ) -> syn::Result<TokenStream> {
+        let type_ = self.columns.index_key_type(idx)?;
+        let is_none_fn_name = Ident::new(
+            format!("select_by_{i}_is_none").as_str(),
+            Span::mixed_site(),
+        );
+        let range_fn_name = Ident::new(format!("select_range_by_{i}").as_str(), Span::mixed_site());
+        let field_ident = &idx.name;
+        let index_name = Literal::string(&idx.name.to_string());
+        let bound = if is_float(type_.to_string().as_str()) {
+            quote! {
+                .map(OrderedFloat)
+            }
+        } else if idx.is_ci {
+            quote! {
+                .map(|v| v.to_lowercase())
+            }
+        } else {
+            quote! {}
+        };
+
+        let is_none_fn = quote! {
+            pub fn #is_none_fn_name(&self) -> SelectQueryBuilder<#row_ident,
+                                                                 impl DoubleEndedIterator<Item = #row_ident> + ArchivedFilter<'_, <#row_ident as rkyv::Archive>::Archived> + '_ + Sized,
+                                                                 #column_range_type,
+                                                                 #row_fields_ident>
+            {
+                self.select_all().where_archived(|r| r.#i.is_none())
+            }
+        };
+        if !idx.is_ordered() {
+            return Ok(is_none_fn);
+        }
+
+        Ok(quote! {
+            #is_none_fn
+
+            pub fn #range_fn_name<R>(&self, range: R) -> SelectQueryBuilder<#row_ident,
+                                                                             impl DoubleEndedIterator<Item = #row_ident> + ArchivedFilter<'_, <#row_ident as rkyv::Archive>::Archived> + '_,
+                                                                             #column_range_type,
+                                                                             #row_fields_ident>
+            where
+                R: std::ops::RangeBounds<#type_>,
+            {
+                let range = (
+                    range.start_bound().cloned()#bound,
+                    range.end_bound().cloned()#bound,
+                );
+                let links: Vec<_> = self.0.indexes.#field_ident
+                    .range(range)
+                    .map(|(_, link)| *link)
+                    .collect();
+                let estimated_rows = links.len();
+                let rows = ArchivedRows::new(links.into_iter(), move |link, check| self.read_archived_row(link, check));
+
+                SelectQueryBuilder::new(rows)
+                    .with_access_path(AccessPath::IndexRange(#index_name), estimated_rows)
+            }
+        })
+    }
+
+    /// Generates `select_by_<column>_prefix` fn, which returns rows which
+    /// column value starts with provided prefix. Index is scanned from prefix
+    /// until first key without it.
+    fn gen_prefix_index_fn(
+        &self,
+        i: &Ident,
+        idx: &Index,
+        row_ident: Ident,
+        column_range_type: &Ident,
+        row_fields_ident: &Ident,
+    ) -> TokenStream {
+        let fn_name = Ident::new(format!("select_by_{i}_prefix").as_str(), Span::mixed_site());
+        let field_ident = &idx.name;
+        let index_name = Literal::string(&idx.name.to_string());
+        let prefix = if idx.is_ci {
+            quote! { prefix.to_lowercase() }
+        } else {
+            quote! { prefix.to_string() }
+        };
+
+        quote! {
+            pub fn #fn_name(&self, prefix: &str) -> SelectQueryBuilder<#row_ident,
+                                                                       impl DoubleEndedIterator<Item = #row_ident> + ArchivedFilter<'_, <#row_ident as rkyv::Archive>::Archived> + '_,
+                                                                       #column_range_type,
+                                                                       #row_fields_ident>
+            {
+                let prefix = #prefix;
+                let links: Vec<_> = self.0.indexes.#field_ident
+                    .range((std::ops::Bound::Included(prefix.clone()), std::ops::Bound::Unbounded))
+                    .take_while(|(key, _)| key.starts_with(prefix.as_str()))
+                    .map(|(_, link)| *link)
+                    .collect();
+                let estimated_rows = links.len();
+                let rows = ArchivedRows::new(links.into_iter(), move |link, check| self.read_archived_row(link, check));
+
+                SelectQueryBuilder::new(rows)
+                    .with_access_path(AccessPath::IndexRange(#index_name), estimated_rows)
+            }
+        }
+    }
+
+    /// Generates `select_by_<column>_contains` fn for array index, which
+    /// returns rows which collection column contains provided element.
+    fn gen_array_index_fn(
+        &self,
+        i: &Ident,
+        idx: &Index,
+        row_ident: Ident,
+        column_range_type: &Ident,
+        row_fields_ident: &Ident,
+    ) -> syn::Result<TokenStream> {
+        let type_ = self.columns.index_key_type(idx)?;
+        let fn_name = Ident::new(
+            format!("select_by_{i}_contains").as_str(),
+            Span::mixed_site(),
+        );
+        let field_ident = &idx.name;
+        let index_name = Literal::string(&idx.name.to_string());
+        let row_field_ident = &idx.field;
+        let predicate = self
+            .gen_index_predicate(idx, &quote! { r })
+            .map(|predicate| quote! { && #predicate });
+
+        Ok(quote! {
+            pub fn #fn_name(&self, elem: #type_) -> SelectQueryBuilder<#row_ident,
+                                                                       impl DoubleEndedIterator<Item = #row_ident> + ArchivedFilter<'_, <#row_ident as rkyv::Archive>::Archived> + '_,
+                                                                       #column_range_type,
+                                                                       #row_fields_ident>
+            {
+                let links: Vec<_> = self.0.indexes.#field_ident
+                    .get(&elem)
+                    .map(|(_, link)| *link)
+                    .collect();
+                let estimated_rows = links.len();
+                let rows = ArchivedRows::new(links.into_iter(), move |link, check| {
+                    self.read_archived_row(link, check).filter(|r| r.#row_field_ident.iter().any(|e| e == &elem) #predicate)
+                });
 
                 SelectQueryBuilder::new(rows)
+                    .with_access_path(AccessPath::IndexExact(#index_name), estimated_rows)
             }
         })
     }
@@ -0,0 +1,128 @@
+use std::collections::HashSet;
+
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::quote;
+
+use crate::name_generator::{WorktableNameGenerator, is_float};
+use crate::worktable::generator::Generator;
+
+/// Types which can be used as hash join keys. Floats are hashed as
+/// `OrderedFloat`.
+fn is_hashable(ty: &str) -> bool {
+    matches!(
+        ty,
+        "i8" | "i16"
+            | "i32"
+            | "i64"
+            | "i128"
+            | "u8"
+            | "u16"
+            | "u32"
+            | "u64"
+            | "u128"
+            | "f32"
+            | "f64"
+            | "bool"
+            | "char"
+            | "String"
+    )
+}
+
+impl Generator {
+    /// Generates `join_on_<column>` fns and `join_on_primary_key` fn. They
+    /// return `TableJoin` which can be used as right side of the join. Join
+    /// strategy is chosen by column: indexed columns and single primary key
+    /// column use index nested-loop join, other columns use hash join.
+    pub fn gen_table_join_fns(&self) -> TokenStream {
+        let name_generator = WorktableNameGenerator::from_table_name(self.name.to_string());
+        let ident = name_generator.get_work_table_ident();
+        let row_type = name_generator.get_row_type_ident();
+        let primary_key_type = name_generator.get_primary_key_type_ident();
+
+        let mut joined = HashSet::new();
+        let mut fns = vec![];
+        if let [pk] = self.columns.primary_keys.as_slice() {
+            let ty = self
+                .columns
+                .columns_map
+                .get(pk)
+                .expect("primary key column should exist");
+            let fn_name = Ident::new(format!("join_on_{pk}").as_str(), Span::mixed_site());
+            fns.push(quote! {
+                pub fn #fn_name(&self) -> TableJoin<'_, #ty, #row_type> {
+                    TableJoin::new(move |key: &#ty| Ok(self.select(key.clone()).into_iter().collect()))
+                }
+            });
+            joined.insert(pk.clone());
+        }
+        for (column, idx) in self.columns.value_indexes() {
+            if !joined.insert(column.clone()) {
+                continue;
+            }
+            let ty = self
+                .columns
+                .columns_map
+                .get(column)
+                .expect("index column should exist");
+            let fn_name = Ident::new(format!("join_on_{column}").as_str(), Span::mixed_site());
+            let select_fn = Ident::new(format!("select_by_{column}").as_str(), Span::mixed_site());
+            let lookup = if idx.is_unique {
+                quote! {
+                    Ok(self.#select_fn(key.clone()).into_iter().collect())
+                }
+            } else {
+                quote! {
+                    self.#select_fn(key.clone()).execute()
+                }
+            };
+            fns.push(quote! {
+                pub fn #fn_name(&self) -> TableJoin<'_, #ty, #row_type> {
+                    TableJoin::new(move |key: &#ty| #lookup)
+                }
+            });
+        }
+        let mut not_indexed: Vec<_> = self
+            .columns
+            .columns_map
+            .iter()
+            .filter(|(column, ty)| {
+                !joined.contains(*column) && is_hashable(ty.to_string().as_str())
+            })
+            .collect();
+        not_indexed.sort_by_key(|(column, _)| column.to_string());
+        for (column, ty) in not_indexed {
+            let fn_name = Ident::new(format!("join_on_{column}").as_str(), Span::mixed_site());
+            let (row_key, key) = if is_float(ty.to_string().as_str()) {
+                (
+                    quote! { OrderedFloat(r.#column) },
+                    quote! { OrderedFloat(*key) },
+                )
+            } else {
+                (quote! { r.#column.clone() }, quote! { key.clone() })
+            };
+            fns.push(quote! {
+                pub fn #fn_name(&self) -> TableJoin<'_, #ty, #row_type> {
+                    TableJoin::hash(
+                        move || self.select_all().execute(),
+                        |r: &#row_type| #row_key,
+                        |key: &#ty| #key,
+                    )
+                }
+            });
+        }
+
+        quote! {
+            impl #ident {
+                #(#fns)*
+
+                pub fn join_on_primary_key<Pk>(&self) -> TableJoin<'_, Pk, #row_type>
+                where
+                    Pk: Clone,
+                    #primary_key_type: From<Pk>,
+                {
+                    TableJoin::new(move |key: &Pk| Ok(self.select(key.clone()).into_iter().collect()))
+                }
+            }
+        }
+    }
+}
@@ -1,12 +1,18 @@
 use proc_macro2::{Literal, TokenStream};
 use quote::quote;
 
-use crate::name_generator::{WorktableNameGenerator, is_unsized_vec};
+use crate::name_generator::WorktableNameGenerator;
 use crate::worktable::generator::Generator;
 
+mod aggregate;
+mod bitmap;
 mod impls;
 mod index_fns;
+mod join;
+mod page;
 mod select_executor;
+mod spatial;
+mod text_query;
 
 impl Generator {
     pub fn gen_table_def(&mut self) -> syn::Result<TokenStream> {
@@ -17,6 +23,15 @@ impl Generator {
         let index_fns = self.gen_table_index_fns()?;
         let select_query_executor_impl = self.gen_table_select_query_executor_impl();
         let column_range_type = self.gen_table_column_range_type();
+        let select_aggregate_impl = self.gen_table_select_aggregate_impl();
+        let count_grouped_by_fn = self.gen_table_count_grouped_by_fn();
+        let distinct_values_fn = self.gen_table_distinct_values_fn();
+        let page_def = self.gen_table_page_def();
+        let after_fn = self.gen_table_after_fn();
+        let join_fns = self.gen_table_join_fns();
+        let text_query_impl = self.gen_table_text_query_impl();
+        let bitmap_fns = self.gen_table_bitmap_fns()?;
+        let spatial_fns = self.gen_table_spatial_fns()?;
 
         Ok(quote! {
             #page_size_consts
@@ -26,6 +41,15 @@ impl Generator {
             #index_fns
             #select_query_executor_impl
             #column_range_type
+            #select_aggregate_impl
+            #count_grouped_by_fn
+            #distinct_values_fn
+            #page_def
+            #after_fn
+            #join_fns
+            #text_query_impl
+            #bitmap_fns
+            #spatial_fns
         })
     }
 
@@ -70,19 +94,7 @@ impl Generator {
             quote! {}
         };
 
-        let pk_types = &self
-            .columns
-            .primary_keys
-            .iter()
-            .map(|i| {
-                self.columns
-                    .columns_map
-                    .get(i)
-                    .expect("should exist as got from definition")
-                    .to_string()
-            })
-            .collect::<Vec<_>>();
-        let pk_types_unsized = is_unsized_vec(pk_types);
+        let pk_types_unsized = self.columns.is_primary_key_unsized();
         let derive = if self.is_persist {
             if pk_types_unsized {
                 quote! {
@@ -0,0 +1,195 @@
+use convert_case::{Case, Casing};
+use proc_macro2::{Ident, Literal, Span, TokenStream};
+use quote::quote;
+
+use crate::name_generator::{WorktableNameGenerator, is_float};
+use crate::worktable::generator::Generator;
+
+impl Generator {
+    /// Generates `Cursor` type that is used for keyset pagination and
+    /// `SelectPageExecutor` impl for table's `SelectQueryBuilder`.
+    pub fn gen_table_page_def(&self) -> TokenStream {
+        let name_generator = WorktableNameGenerator::from_table_name(self.name.to_string());
+        let row_type = name_generator.get_row_type_ident();
+        let primary_key_type = name_generator.get_primary_key_type_ident();
+        let column_range_type = name_generator.get_column_range_type_ident();
+        let row_fields_ident = name_generator.get_row_fields_enum_ident();
+        let column_value_ident = name_generator.get_column_value_type_ident();
+        let cursor_ident = name_generator.get_cursor_type_ident();
+
+        let pk_orders = self.columns.primary_keys.iter().map(|column| {
+            let column_variant = Ident::new(
+                &column.to_string().to_case(Case::Pascal),
+                Span::mixed_site(),
+            );
+            quote! {
+                self.params.order.push_back((Order::Asc, #row_fields_ident::#column_variant));
+            }
+        });
+
+        quote! {
+            /// Opaque position of the last row of the page returned by
+            /// `execute_page`.
+            #[derive(Debug, Clone, PartialEq)]
+            pub struct #cursor_ident {
+                order: Vec<(Order, #row_fields_ident, #column_value_ident)>,
+                primary_key: #primary_key_type,
+            }
+
+            impl PageCursor<#row_type, #row_fields_ident> for #cursor_ident {
+                fn order(&self) -> Vec<(Order, #row_fields_ident)> {
+                    self.order.iter().map(|(o, column, _)| (*o, column.clone())).collect()
+                }
+
+                fn is_passed_by(&self, row: &#row_type) -> bool {
+                    for (order, column, value) in &self.order {
+                        let cmp = row
+                            .get_column_value(column)
+                            .partial_cmp(value)
+                            .unwrap_or(std::cmp::Ordering::Equal);
+                        let cmp = match order {
+                            Order::Asc => cmp,
+                            Order::Desc => cmp.reverse(),
+                        };
+                        if cmp != std::cmp::Ordering::Equal {
+                            return cmp == std::cmp::Ordering::Greater;
+                        }
+                    }
+                    row.get_primary_key() > self.primary_key
+                }
+            }
+
+            impl<I> SelectPageExecutor<#row_type, #cursor_ident>
+            for SelectQueryBuilder<#row_type, I, #column_range_type, #row_fields_ident>
+            where
+                I: DoubleEndedIterator<Item = #row_type> + Sized,
+            {
+                fn execute_page(mut self, page_size: usize) -> Result<SelectPage<#row_type, #cursor_ident>, WorkTableError> {
+                    let order: Vec<_> = self.params.order.iter().cloned().collect();
+                    if !order.is_empty() {
+                        // Primary key is used as tie-breaker to have stable order.
+                        #(#pk_orders)*
+                    }
+                    self.params.limit = Some(page_size);
+
+                    let rows = self.execute()?;
+                    let cursor = if rows.len() == page_size {
+                        rows.last().map(|row| #cursor_ident {
+                            order: order
+                                .into_iter()
+                                .map(|(o, column)| {
+                                    let value = row.get_column_value(&column);
+                                    (o, column, value)
+                                })
+                                .collect(),
+                            primary_key: row.get_primary_key(),
+                        })
+                    } else {
+                        None
+                    };
+
+                    Ok(SelectPage { rows, cursor })
+                }
+            }
+        }
+    }
+
+    /// Generates `after` fn for table which returns `SelectQueryBuilder` for
+    /// rows after provided cursor. If cursor has no order, `pk_map` range is
+    /// used. If first order column is indexed, index range is used. Cursors
+    /// ordered by not indexed column are rejected, as all rows would be read
+    /// and sorted for each page.
+    pub fn gen_table_after_fn(&self) -> TokenStream {
+        let name_generator = WorktableNameGenerator::from_table_name(self.name.to_string());
+        let ident = name_generator.get_work_table_ident();
+        let row_type = name_generator.get_row_type_ident();
+        let column_range_type = name_generator.get_column_range_type_ident();
+        let row_fields_ident = name_generator.get_row_fields_enum_ident();
+        let column_value_ident = name_generator.get_column_value_type_ident();
+        let cursor_ident = name_generator.get_cursor_type_ident();
+
+        let index_arms = self.columns.ordered_value_indexes().map(|(column, idx)| {
+            let column_variant = Ident::new(
+                &column.to_string().to_case(Case::Pascal),
+                Span::mixed_site(),
+            );
+            let index_field = &idx.name;
+            let index_name = Literal::string(&idx.name.to_string());
+            let ty = self
+                .columns
+                .columns_map
+                .get(column)
+                .expect("index column should exist");
+            let key = if is_float(ty.to_string().as_str()) {
+                quote! { OrderedFloat(*value) }
+            } else {
+                quote! { value.clone() }
+            };
+            quote! {
+                Some((order, #row_fields_ident::#column_variant, #column_value_ident::#column_variant(value))) => {
+                    let bounds = match order {
+                        Order::Asc => (std::ops::Bound::Included(#key), std::ops::Bound::Unbounded),
+                        Order::Desc => (std::ops::Bound::Unbounded, std::ops::Bound::Included(#key)),
+                    };
+                    (
+                        Box::new(
+                            self.0.indexes.#index_field
+                                .range(bounds)
+                                .filter_map(|(_, link)| self.0.data.select_non_ghosted(*link).ok()),
+                        ),
+                        AccessPath::IndexRange(#index_name),
+                    )
+                }
+            }
+        });
+
+        quote! {
+            impl #ident {
+                /// Returns `SelectQueryBuilder` for all table rows which go
+                /// after `cursor` got from `execute_page`. Order of the
+                /// previous query is restored from cursor, but its source
+                /// and filters are not, so it continues `select_all` pages.
+                /// Query's `after` must be used for filtered queries and
+                /// queries ordered by not indexed column.
+                pub fn after(&self, cursor: &#cursor_ident) -> Result<SelectQueryBuilder<#row_type,
+                                                                                        impl DoubleEndedIterator<Item = #row_type> + '_ + Sized,
+                                                                                        #column_range_type,
+                                                                                        #row_fields_ident>, WorkTableError>
+                {
+                    // Is got before index iterators are created as they
+                    // hold index locks.
+                    let estimated_rows = self.0.pk_map.len();
+                    #[allow(clippy::type_complexity)]
+                    let (iter, access_path): (Box<dyn DoubleEndedIterator<Item = #row_type> + '_>, _) = match cursor.order.first() {
+                        None => (
+                            Box::new(
+                                self.0.pk_map
+                                    .range((
+                                        std::ops::Bound::Excluded(cursor.primary_key.clone()),
+                                        std::ops::Bound::Unbounded,
+                                    ))
+                                    .filter_map(|(_, link)| self.0.data.select_non_ghosted(*link).ok()),
+                            ),
+                            AccessPath::PrimaryKeyRange,
+                        ),
+                        #(#index_arms)*
+                        Some((_, column, _)) => {
+                            return Err(WorkTableError::NotIndexedCursor(format!("{:?}", column)));
+                        }
+                    };
+
+                    let order = cursor.order.clone();
+                    let cursor = cursor.clone();
+                    let iter = iter.filter(move |row| cursor.is_passed_by(row));
+
+                    let mut builder = SelectQueryBuilder::new(iter)
+                        .with_access_path(access_path, estimated_rows);
+                    for (o, column, _) in order {
+                        builder = builder.order_on(column, o);
+                    }
+                    Ok(builder)
+                }
+            }
+        }
+    }
+}
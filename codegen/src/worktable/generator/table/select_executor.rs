@@ -11,7 +11,7 @@ use syn::Type;
 
 const RANGE_VARIANTS: &[&str] = &["", "Inclusive", "From", "To", "ToInclusive"];
 
-fn is_numeric_type(ty: &Type) -> bool {
+pub(super) fn is_numeric_type(ty: &Type) -> bool {
     matches!(
         ty.to_token_stream().to_string().as_str(),
         "i8" | "i16"
@@ -88,12 +88,56 @@ impl Generator {
             }
         });
 
+        let row_type = name_generator.get_row_type_ident();
+        let row_fields_ident = name_generator.get_row_fields_enum_ident();
+        let contains_matches: Vec<_> = self
+            .columns
+            .columns_map
+            .iter()
+            .filter(|(_, ty)| {
+                is_numeric_type(&syn::parse_str::<Type>(&ty.to_token_stream().to_string()).unwrap())
+            })
+            .flat_map(|(column, ty)| {
+                let row_fields_ident = &row_fields_ident;
+                let column_variant =
+                    Ident::new(&column.to_string().to_case(Case::Pascal), Span::mixed_site());
+                RANGE_VARIANTS.iter().map(move |v| {
+                    let variant_ident = Ident::new(
+                        &format!("{}{}", ty.to_string().to_case(Case::Pascal), v),
+                        Span::call_site(),
+                    );
+                    quote! {
+                        (#row_fields_ident::#column_variant, Self::#variant_ident(range)) => range.contains(&row.#column),
+                    }
+                })
+            })
+            .collect();
+        let contains = if contains_matches.is_empty() {
+            quote! {
+                match *self {}
+            }
+        } else {
+            quote! {
+                match (column, self) {
+                    #(#contains_matches)*
+                    _ => unreachable!(),
+                }
+            }
+        };
+
         quote! {
             #[derive(Debug, Clone)]
             pub enum #column_range_type {
                 #(#column_range_variants)*
             }
 
+            impl #column_range_type {
+                /// Checks whether `column` value of the `row` is in this range.
+                pub fn contains(&self, column: &#row_fields_ident, row: &#row_type) -> bool {
+                    #contains
+                }
+            }
+
             #(#from_impls)*
         }
     }
@@ -120,52 +164,6 @@ impl Generator {
             }
         });
 
-        let range_matches = self
-            .columns
-            .columns_map
-            .iter()
-            .filter(|(_, ty)| {
-                is_numeric_type(&syn::parse_str::<Type>(&ty.to_token_stream().to_string()).unwrap())
-            })
-            .map(|(column, ty)| {
-                let variants: Vec<_> = RANGE_VARIANTS
-                    .iter()
-                    .map(|v| {
-                        let column_variant = Ident::new(&column.to_string().to_case(Case::Pascal), Span::mixed_site());
-                        let col_ident = Ident::new(&column.to_string(), Span::call_site());
-                        let variant_ident = Ident::new(
-                            &format!("{}{}", ty.to_string().to_case(Case::Pascal), v),
-                            Span::call_site(),
-                        );
-                        quote! {
-                            (#row_fields_ident::#column_variant, #column_range_type::#variant_ident(range)) => {
-                                Box::new(iter.filter(move |row| range.contains(&row.#col_ident)))
-                                    as Box<dyn DoubleEndedIterator<Item = #row_type>>
-                            },
-                        }
-                    })
-                    .collect();
-
-                quote! {
-                    #(#variants)*
-                }
-            }).collect::<Vec<_>>();
-
-        let range = if range_matches.is_empty() {
-            quote! {}
-        } else {
-            quote! {
-                if !self.params.range.is_empty() {
-                for (range, column) in &self.params.range {
-                    iter = match (column, range.clone().into()) {
-                        #(#range_matches)*
-                        _ => unreachable!(),
-                    };
-                }
-            }
-            }
-        };
-
         quote! {
             impl<I> SelectQueryExecutor<#row_type, I, #column_range_type, #row_fields_ident>
             for SelectQueryBuilder<#row_type, I, #column_range_type, #row_fields_ident>
@@ -186,40 +184,89 @@ impl Generator {
                     }
                 }
 
-                fn execute(self) -> Result<Vec<#row_type>, WorkTableError> {
-                    let mut iter: Box<dyn DoubleEndedIterator<Item = #row_type>> = Box::new(self.iter);
-
-                    #range
+                fn where_archived<'a, F>(mut self, predicate: F) -> Self
+                where
+                    F: Fn(&<#row_type as rkyv::Archive>::Archived) -> bool + 'a,
+                    I: ArchivedFilter<'a, <#row_type as rkyv::Archive>::Archived>,
+                {
+                    self.iter.push_archived_filter(Box::new(predicate));
+                    self
+                }
 
-                    if !self.params.order.is_empty() {
-                        let mut items: Vec<#row_type> = iter.collect();
+                fn filtered(mut self) -> SelectQueryBuilder<#row_type,
+                                                            impl DoubleEndedIterator<Item = #row_type> + Sized,
+                                                            #column_range_type,
+                                                            #row_fields_ident>
+                {
+                    let ranges = std::mem::take(&mut self.params.range);
+                    let distinct = self.params.distinct.take();
+                    let mut seen = std::collections::BTreeSet::new();
+                    SelectQueryBuilder {
+                        params: self.params,
+                        iter: self.iter.filter(move |row| {
+                            ranges.iter().all(|(range, column)| range.contains(column, row))
+                                && distinct
+                                    .as_ref()
+                                    .is_none_or(|column| seen.insert(TotalOrd(row.get_column_value(column))))
+                        }),
+                    }
+                }
 
-                        items.sort_by(|a, b| {
-                            for (order, col) in &self.params.order {
+                fn rows(mut self) -> impl Iterator<Item = #row_type> {
+                    let offset = self.params.offset.unwrap_or(0);
+                    let limit = self.params.limit.unwrap_or(usize::MAX);
+                    // Not ordered query rows are read lazily, ordered query
+                    // rows are sorted first.
+                    let (query, sorted) = if self.params.order.is_empty() {
+                        (Some(self), None)
+                    } else {
+                        let distinct = self.params.distinct.take();
+                        let order = std::mem::take(&mut self.params.order);
+                        let cmp = |a: &#row_type, b: &#row_type| {
+                            for (order, col) in &order {
                                 match col {
                                     #(#order_matches)*
                                     _ => continue,
                                 }
                             }
                             std::cmp::Ordering::Equal
-                        });
-
-                        iter = Box::new(items.into_iter());
-                    }
+                        };
 
-                    let iter_result: Box<dyn Iterator<Item = #row_type>> = if let Some(offset) = self.params.offset {
-                        Box::new(iter.skip(offset))
-                    } else {
-                        Box::new(iter)
-                    };
+                        let is_top_k = self.params.limit.is_some() && distinct.is_none();
+                        let rows = self.filtered().iter;
+                        let items = if is_top_k {
+                            // Only first `offset + limit` rows are needed, so
+                            // bounded heap is used instead of full sort. It
+                            // can't be used with `distinct_on` as duplicates
+                            // are removed after sort.
+                            let mut top = TopK::new(offset.saturating_add(limit), cmp);
+                            for row in rows {
+                                top.push(row);
+                            }
+                            top.into_sorted_vec()
+                        } else {
+                            let mut items: Vec<#row_type> = rows.collect();
+                            items.sort_by(cmp);
+                            items
+                        };
 
-                    let iter_result: Box<dyn Iterator<Item = #row_type>> = if let Some(limit) = self.params.limit {
-                        Box::new(iter_result.take(limit))
-                    } else {
-                        Box::new(iter_result)
+                        // `distinct_on` keeps first rows in the result order,
+                        // so it is applied after sort.
+                        let mut sorted = SelectQueryBuilder::<#row_type, _, #column_range_type, #row_fields_ident>::new(items.into_iter());
+                        sorted.params.distinct = distinct;
+                        (None, Some(sorted))
                     };
+                    query
+                        .map(|q| q.filtered().iter)
+                        .into_iter()
+                        .flatten()
+                        .chain(sorted.map(|q| q.filtered().iter).into_iter().flatten())
+                        .skip(offset)
+                        .take(limit)
+                }
 
-                    Ok(iter_result.collect())
+                fn execute(self) -> Result<Vec<#row_type>, WorkTableError> {
+                    Ok(self.rows().collect())
                 }
             }
         }
@@ -0,0 +1,82 @@
+use proc_macro2::{Literal, TokenStream};
+use quote::quote;
+
+use crate::name_generator::WorktableNameGenerator;
+use crate::worktable::generator::Generator;
+
+impl Generator {
+    /// Generates `select_within_box` and `select_nearest` fns for `spatial`
+    /// index. Only one spatial index can be declared, so fns are not named by
+    /// index.
+    pub fn gen_table_spatial_fns(&self) -> syn::Result<TokenStream> {
+        let name_generator = WorktableNameGenerator::from_table_name(self.name.to_string());
+        let ident = name_generator.get_work_table_ident();
+        let row_ident = name_generator.get_row_type_ident();
+        let column_range_type = name_generator.get_column_range_type_ident();
+        let row_fields_ident = name_generator.get_row_fields_enum_ident();
+
+        let indexes: Vec<_> = self
+            .columns
+            .indexes
+            .values()
+            .filter(|idx| idx.is_spatial)
+            .collect();
+        let idx = match indexes.as_slice() {
+            [] => return Ok(quote! {}),
+            [idx] => idx,
+            [_, idx, ..] => {
+                return Err(syn::Error::new(
+                    idx.name.span(),
+                    "only one `spatial` index can be declared for table",
+                ));
+            }
+        };
+        let index_field = &idx.name;
+        let index_name = Literal::string(&idx.name.to_string());
+        let point = self
+            .gen_computed_key(idx, &quote! { r })
+            .expect("spatial index is computed");
+        let predicate = self
+            .gen_index_predicate(idx, &quote! { r })
+            .map(|predicate| quote! { && #predicate });
+
+        Ok(quote! {
+            impl #ident {
+                /// Returns rows which points are inside the box, bounds
+                /// included.
+                pub fn select_within_box(&self, min: (f64, f64), max: (f64, f64)) -> SelectQueryBuilder<#row_ident,
+                                                                                        impl DoubleEndedIterator<Item = #row_ident> + ArchivedFilter<'_, <#row_ident as rkyv::Archive>::Archived> + '_,
+                                                                                        #column_range_type,
+                                                                                        #row_fields_ident>
+                {
+                    let links = self.0.indexes.#index_field.within(min, max);
+                    let estimated_rows = links.len();
+                    let rows = ArchivedRows::new(links.into_iter(), move |link, check| {
+                        self.read_archived_row(link, check).filter(|r| {
+                            let (x, y) = #point;
+                            min.0 <= x && x <= max.0 && min.1 <= y && y <= max.1 #predicate
+                        })
+                    });
+
+                    SelectQueryBuilder::new(rows)
+                        .with_access_path(AccessPath::Spatial(#index_name), estimated_rows)
+                }
+
+                /// Returns `k` rows which points are nearest to `point`,
+                /// ordered by distance.
+                pub fn select_nearest(&self, point: (f64, f64), k: usize) -> SelectQueryBuilder<#row_ident,
+                                                                                 impl DoubleEndedIterator<Item = #row_ident> + ArchivedFilter<'_, <#row_ident as rkyv::Archive>::Archived> + '_,
+                                                                                 #column_range_type,
+                                                                                 #row_fields_ident>
+                {
+                    let links = self.0.indexes.#index_field.nearest(point, k);
+                    let estimated_rows = links.len();
+                    let rows = ArchivedRows::new(links.into_iter(), move |link, check| self.read_archived_row(link, check));
+
+                    SelectQueryBuilder::new(rows)
+                        .with_access_path(AccessPath::Spatial(#index_name), estimated_rows)
+                }
+            }
+        })
+    }
+}
@@ -0,0 +1,133 @@
+use convert_case::{Case, Casing};
+use proc_macro2::{Ident, Literal, Span, TokenStream};
+use quote::{ToTokens, quote};
+use syn::Type;
+
+use crate::name_generator::WorktableNameGenerator;
+use crate::worktable::generator::Generator;
+use crate::worktable::generator::table::select_executor::is_numeric_type;
+
+/// Returns `true` if value of type can be parsed from string with `parse`.
+fn is_parsable(ty: &str) -> bool {
+    is_numeric_type(&syn::parse_str::<Type>(ty).unwrap())
+        || matches!(ty, "bool" | "Uuid" | "uuid :: Uuid")
+}
+
+impl Generator {
+    /// Generates `TextQueryColumn` impl for `RowFields` and `query_text` fn
+    /// for table. Values are parsed only for numeric, `bool`, `String` and
+    /// `Uuid` columns (and their optional versions).
+    pub fn gen_table_text_query_impl(&self) -> TokenStream {
+        let name_generator = WorktableNameGenerator::from_table_name(self.name.to_string());
+        let ident = name_generator.get_work_table_ident();
+        let row_type = name_generator.get_row_type_ident();
+        let row_fields_ident = name_generator.get_row_fields_enum_ident();
+        let column_value_ident = name_generator.get_column_value_type_ident();
+
+        let mut columns: Vec<_> = self.columns.columns_map.iter().collect();
+        columns.sort_by_key(|(name, _)| self.columns.field_positions.get(*name));
+
+        let variants: Vec<_> = columns
+            .iter()
+            .map(|(name, _)| {
+                Ident::new(
+                    name.to_string().to_case(Case::Pascal).as_str(),
+                    Span::mixed_site(),
+                )
+            })
+            .collect();
+        let names = columns
+            .iter()
+            .map(|(name, _)| Literal::string(&name.to_string()));
+        let parse_matches = columns.iter().zip(&variants).map(|((name, ty), variant)| {
+            let ty_str = ty.to_token_stream().to_string();
+            let inner = ["core :: option :: Option < ", "std :: option :: Option < ", "Option < "]
+                .iter()
+                .find_map(|prefix| ty_str.strip_prefix(prefix))
+                .and_then(|t| t.strip_suffix(" >"));
+            let parse = |ty: &str| {
+                if ty == "String" {
+                    Some(quote! { Ok::<_, WorkTableError>(value.to_string()) })
+                } else if is_parsable(ty) {
+                    let ty: TokenStream = ty.parse().unwrap();
+                    let error = Literal::string(&format!("Can't parse `{{}}` as `{ty}` for column `{name}`"));
+                    Some(quote! {
+                        value.parse::<#ty>().map_err(|_| WorkTableError::QueryParseError(format!(#error, value)))
+                    })
+                } else {
+                    None
+                }
+            };
+            let parse = match inner {
+                Some(inner) => parse(inner).map(|parse| {
+                    quote! {
+                        if value == "null" {
+                            Ok(None)
+                        } else {
+                            #parse.map(Some)
+                        }
+                    }
+                }),
+                None => parse(&ty_str),
+            };
+            match parse {
+                Some(parse) => quote! {
+                    #row_fields_ident::#variant => (#parse).map(#column_value_ident::#variant),
+                },
+                None => {
+                    let error = Literal::string(&format!("Column `{name}` can't be used in query filter"));
+                    quote! {
+                        #row_fields_ident::#variant => Err(WorkTableError::QueryParseError(#error.to_string())),
+                    }
+                }
+            }
+        });
+        let format_matches = columns.iter().zip(&variants).map(|((_, ty), variant)| {
+            if ty.to_token_stream().to_string() == "String" {
+                quote! { #column_value_ident::#variant(v) => v.clone(), }
+            } else {
+                quote! { #column_value_ident::#variant(v) => format!("{:?}", v), }
+            }
+        });
+
+        quote! {
+            impl TextQueryColumn for #row_fields_ident {
+                type Row = #row_type;
+                type Value = #column_value_ident;
+
+                fn columns() -> Vec<Self> {
+                    vec![#(#row_fields_ident::#variants),*]
+                }
+
+                fn name(&self) -> &'static str {
+                    match self {
+                        #(#row_fields_ident::#variants => #names,)*
+                    }
+                }
+
+                fn parse_value(&self, value: &str) -> Result<#column_value_ident, WorkTableError> {
+                    match self {
+                        #(#parse_matches)*
+                    }
+                }
+
+                fn get_value(&self, row: &#row_type) -> #column_value_ident {
+                    row.get_column_value(self)
+                }
+
+                fn format_value(value: &#column_value_ident) -> String {
+                    match value {
+                        #(#format_matches)*
+                    }
+                }
+            }
+
+            impl #ident {
+                /// Parses and executes `TextQuery` over all table rows.
+                pub fn query_text(&self, query: &str) -> Result<TextQueryResult, WorkTableError> {
+                    TextQuery::parse(query)?.execute(self.select_all())
+                }
+            }
+        }
+    }
+}
@@ -60,6 +60,7 @@ pub fn expand(input: TokenStream) -> syn::Result<TokenStream> {
     let update_impls = generator.gen_query_update_impl()?;
     let update_in_place_impls = generator.gen_query_in_place_impl()?;
     let delete_impls = generator.gen_query_delete_impl()?;
+    let explain_impls = generator.gen_query_explain_impl()?;
     let unsized_impl = generator.gen_unsized_impls();
 
     Ok(quote! {
@@ -76,6 +77,7 @@ pub fn expand(input: TokenStream) -> syn::Result<TokenStream> {
         #update_impls
         #update_in_place_impls
         #delete_impls
+        #explain_impls
         #unsized_impl
     })
 }
@@ -1,18 +1,21 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
+use crate::name_generator::is_unsized;
 use crate::worktable::model::GeneratorType;
 use crate::worktable::model::index::Index;
-use proc_macro2::{Ident, TokenStream};
+use proc_macro2::{Ident, TokenStream, TokenTree};
 use quote::quote;
 use syn::spanned::Spanned;
 
-fn is_sized(ident: &Ident) -> bool {
-    !matches!(ident.to_string().as_str(), "String")
-}
-
 #[derive(Debug, Clone)]
 pub struct Columns {
     pub is_sized: bool,
+    /// Columns which archived size depends on value.
+    pub unsized_columns: HashSet<Ident>,
+    /// Columns which can be used as `UnsizedNode` based index keys. These are
+    /// `String` columns and columns marked with `unsized` flag, which types
+    /// implement `VariableSizeMeasurable`.
+    pub unsized_keys: HashSet<Ident>,
     pub columns_map: HashMap<Ident, TokenStream>,
     pub field_positions: HashMap<Ident, usize>,
     pub indexes: HashMap<Ident, Index>,
@@ -23,24 +26,29 @@ pub struct Columns {
 #[derive(Debug)]
 pub struct Row {
     pub name: Ident,
-    pub type_: Ident,
+    pub type_: TokenStream,
     pub is_primary_key: bool,
     pub gen_type: GeneratorType,
     pub optional: bool,
+    pub is_unsized: bool,
 }
 
 impl Columns {
     pub fn try_from_rows(rows: Vec<Row>, input: &TokenStream) -> syn::Result<Self> {
         let mut columns_map = HashMap::new();
         let mut field_positions = HashMap::new();
-        let mut sized = true;
+        let mut unsized_columns = HashSet::new();
+        let mut unsized_keys = HashSet::new();
         let mut pk = vec![];
         let mut gen_type = None;
 
         for (pos, row) in rows.into_iter().enumerate() {
             let type_ = &row.type_;
-            if sized {
-                sized = is_sized(type_)
+            if row.is_unsized || is_unsized(&type_.to_string()) {
+                unsized_columns.insert(row.name.clone());
+            }
+            if row.is_unsized || type_.to_string() == "String" {
+                unsized_keys.insert(row.name.clone());
             }
             let type_ = if row.optional {
                 quote! { core::option::Option<#type_> }
@@ -67,7 +75,9 @@ impl Columns {
         }
 
         Ok(Self {
-            is_sized: sized,
+            is_sized: unsized_columns.is_empty(),
+            unsized_columns,
+            unsized_keys,
             columns_map,
             indexes: Default::default(),
             primary_keys: pk,
@@ -75,4 +85,186 @@ impl Columns {
             field_positions,
         })
     }
+    /// Checks whether column's archived size depends on value, so row must be
+    /// reinserted when it's updated.
+    pub fn is_unsized(&self, column: &Ident) -> bool {
+        self.unsized_columns.contains(column)
+    }
+
+    /// Checks whether index on column uses `UnsizedNode`.
+    pub fn is_unsized_key(&self, column: &Ident) -> bool {
+        self.unsized_keys.contains(column)
+    }
+
+    /// Checks whether primary key index uses `UnsizedNode`.
+    pub fn is_primary_key_unsized(&self) -> bool {
+        self.primary_keys.iter().any(|pk| self.is_unsized_key(pk))
+    }
+
+    /// Checks whether columns are all primary key columns, in any order.
+    pub fn is_primary_key(&self, columns: &[Ident]) -> bool {
+        columns.len() == self.primary_keys.len()
+            && self.primary_keys.iter().all(|pk| columns.contains(pk))
+    }
+
+    /// Checks whether column has `Option<T>` type, so it can have no value.
+    pub fn is_optional(&self, column: &Ident) -> bool {
+        self.columns_map
+            .get(column)
+            .and_then(option_inner_type)
+            .is_some()
+    }
+
+    /// Returns `T` for `Option<T>` column and column type for other columns.
+    pub fn value_type(&self, column: &Ident) -> Option<TokenStream> {
+        let ty = self.columns_map.get(column)?;
+        option_inner_type(ty).or_else(|| Some(ty.clone()))
+    }
+
+    /// Returns indexes which keys are whole column values, so rows can be got
+    /// from them by column value. Array indexes are keyed by elements,
+    /// indexes on optional columns are keyed by `Some` values, `ci` indexes
+    /// are keyed by lowercased values, partial indexes have only matching
+    /// rows and computed indexes have no column.
+    pub fn value_indexes(&self) -> impl Iterator<Item = (&Ident, &Index)> {
+        self.indexes.iter().filter(|(_, idx)| {
+            !idx.is_array
+                && !idx.is_ci
+                && !idx.is_partial()
+                && !idx.is_computed()
+                && !self.is_optional(&idx.field)
+        })
+    }
+
+    /// Returns value indexes which keys are ordered, so they can be iterated
+    /// and scanned by range. Hash indexes are not ordered.
+    pub fn ordered_value_indexes(&self) -> impl Iterator<Item = (&Ident, &Index)> {
+        self.value_indexes().filter(|(_, idx)| idx.is_ordered())
+    }
+
+    /// Returns index key type. It's column type for usual index, element type
+    /// for array index and `T` for index on `Option<T>` column. Computed index
+    /// has declared type or type of the only column used in expression.
+    pub fn index_key_type(&self, idx: &Index) -> syn::Result<TokenStream> {
+        if let Some(expr) = &idx.expr {
+            if let Some(ty) = &idx.expr_type {
+                return Ok(ty.clone());
+            }
+            let mut used = HashSet::new();
+            collect_columns(expr.clone(), &self.columns_map, &mut used);
+            return match used.into_iter().collect::<Vec<_>>().as_slice() {
+                [column] => Ok(self.columns_map[column].clone()),
+                _ => Err(syn::Error::new(
+                    idx.name.span(),
+                    format!(
+                        "computed index `{}` key type can't be got from expression, declare it with `as <type>`",
+                        idx.name
+                    ),
+                )),
+            };
+        }
+        let Some(ty) = self.columns_map.get(&idx.field) else {
+            return Err(syn::Error::new(
+                idx.field.span(),
+                format!("cannot find column `{}` in this table", idx.field),
+            ));
+        };
+        if idx.is_array {
+            collection_element_type(ty).ok_or(syn::Error::new(
+                idx.name.span(),
+                format!(
+                    "`array` index `{}` needs collection column, but `{}` has type `{ty}`",
+                    idx.name, idx.field
+                ),
+            ))
+        } else {
+            Ok(option_inner_type(ty).unwrap_or(ty.clone()))
+        }
+    }
+
+    /// Returns type of the values which are compared to get index change on
+    /// update. It's column type for usual index and key type for computed.
+    pub fn index_value_type(&self, idx: &Index) -> Option<TokenStream> {
+        if idx.is_computed() {
+            self.index_key_type(idx).ok()
+        } else {
+            self.columns_map.get(&idx.field).cloned()
+        }
+    }
+
+    /// Checks whether index uses `UnsizedNode`.
+    pub fn is_index_key_unsized(&self, idx: &Index) -> bool {
+        self.index_key_type(idx)
+            .is_ok_and(|ty| ty.to_string() == "String")
+            || (!idx.is_array && self.is_unsized_key(&idx.field))
+    }
+}
+
+/// Collects columns which are named in tokens. Identifiers after `.` and
+/// `::` are fields, methods and paths, so they are skipped.
+fn collect_columns(
+    tokens: TokenStream,
+    columns_map: &HashMap<Ident, TokenStream>,
+    used: &mut HashSet<Ident>,
+) {
+    let mut after_path = false;
+    for tt in tokens {
+        match tt {
+            TokenTree::Ident(ident) => {
+                if !after_path && columns_map.contains_key(&ident) {
+                    used.insert(ident);
+                }
+                after_path = false;
+            }
+            TokenTree::Group(group) => {
+                collect_columns(group.stream(), columns_map, used);
+                after_path = false;
+            }
+            TokenTree::Punct(p) => after_path = matches!(p.as_char(), '.' | ':'),
+            TokenTree::Literal(_) => after_path = false,
+        }
+    }
+}
+
+/// Returns name of the generic type and its first type argument, like `Vec`
+/// and `T` for `Vec<T>`.
+fn first_type_argument(ty: &syn::Type) -> Option<(String, &syn::Type)> {
+    let syn::Type::Path(path) = ty else {
+        return None;
+    };
+    let segment = path.path.segments.last()?;
+    let syn::PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return None;
+    };
+    let Some(syn::GenericArgument::Type(arg)) = args.args.first() else {
+        return None;
+    };
+    Some((segment.ident.to_string(), arg))
+}
+
+/// Returns `T` for `Option<T>` type.
+fn option_inner_type(ty: &TokenStream) -> Option<TokenStream> {
+    let ty: syn::Type = syn::parse2(ty.clone()).ok()?;
+    match first_type_argument(&ty)? {
+        (name, arg) if name == "Option" => Some(quote! { #arg }),
+        _ => None,
+    }
+}
+
+/// Returns element type of the collection type: `Vec<T>`, `VecDeque<T>`,
+/// `HashSet<T>`, `BTreeSet<T>`, `Box<[T]>` or `[T; N]`.
+fn collection_element_type(ty: &TokenStream) -> Option<TokenStream> {
+    let ty: syn::Type = syn::parse2(ty.clone()).ok()?;
+    let elem = if let syn::Type::Array(array) = &ty {
+        &*array.elem
+    } else {
+        match first_type_argument(&ty)? {
+            (name, arg) if matches!(name.as_str(), "Vec" | "VecDeque" | "HashSet" | "BTreeSet") => {
+                arg
+            }
+            (name, syn::Type::Slice(slice)) if name == "Box" => &*slice.elem,
+            _ => return None,
+        }
+    };
+    Some(quote! { #elem })
 }
@@ -1,8 +1,52 @@
-use proc_macro2::Ident;
+use proc_macro2::{Ident, TokenStream};
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone)]
 pub struct Index {
     pub name: Ident,
     pub field: Ident,
     pub is_unique: bool,
+    /// Index has separate entry for each element of the collection column.
+    pub is_array: bool,
+    /// Index keys are lowercased, so lookups are case-insensitive.
+    pub is_ci: bool,
+    /// `select_by_<column>_prefix` is generated for index.
+    pub is_prefix: bool,
+    /// Index is hash map, so it supports only lookups by whole key.
+    pub is_hash: bool,
+    /// Index keeps bitmap of the rows for every value, so it's used for
+    /// columns with few distinct values.
+    pub is_bitmap: bool,
+    /// Spatial index is R-tree over point of two `f64` columns. It's computed
+    /// index keyed by `(f64, f64)` tuple.
+    pub is_spatial: bool,
+    /// Predicate of the partial index. Row is indexed only while it holds.
+    pub predicate: Option<TokenStream>,
+    /// Expression of the computed index. Index is keyed by its value, which
+    /// is got from the row, instead of column value.
+    pub expr: Option<TokenStream>,
+    /// Key type of the computed index declared by `as <type>`.
+    pub expr_type: Option<TokenStream>,
+}
+
+impl Index {
+    /// Hash, bitmap and spatial indexes are not persisted, they are rebuilt
+    /// from rows on load.
+    pub fn is_rebuilt_on_load(&self) -> bool {
+        self.is_hash || self.is_bitmap || self.is_spatial
+    }
+
+    /// Index keeps keys in order, so it supports range scans.
+    pub fn is_ordered(&self) -> bool {
+        !self.is_hash && !self.is_bitmap && !self.is_spatial
+    }
+
+    /// Partial index has keys only for rows which match its predicate.
+    pub fn is_partial(&self) -> bool {
+        self.predicate.is_some()
+    }
+
+    /// Computed index is keyed by expression over row columns.
+    pub fn is_computed(&self) -> bool {
+        self.expr.is_some()
+    }
 }
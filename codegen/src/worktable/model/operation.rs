@@ -4,5 +4,17 @@ use proc_macro2::Ident;
 pub struct Operation {
     pub name: Ident,
     pub columns: Vec<Ident>,
-    pub by: Ident,
+    /// Columns which rows are got by. Query can be `by` several columns,
+    /// like `by id, test` for composite primary key.
+    pub by: Vec<Ident>,
+}
+
+impl Operation {
+    /// Returns `by` column if query is by single column.
+    pub fn by_column(&self) -> Option<&Ident> {
+        match self.by.as_slice() {
+            [by] => Some(by),
+            _ => None,
+        }
+    }
 }
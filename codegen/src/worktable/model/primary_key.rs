@@ -1,10 +1,8 @@
-use proc_macro2::{Ident, TokenStream};
-use std::collections::HashMap;
+use proc_macro2::Ident;
 
 #[derive(Debug, Clone)]
 pub struct PrimaryKey {
     pub ident: Ident,
-    pub values: HashMap<Ident, TokenStream>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
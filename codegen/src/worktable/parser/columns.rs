@@ -1,9 +1,18 @@
-use proc_macro2::{Delimiter, TokenTree};
+use proc_macro2::{Delimiter, TokenStream, TokenTree};
+use quote::ToTokens as _;
 use syn::spanned::Spanned as _;
 
 use crate::worktable::Parser;
 use crate::worktable::model::{Columns, GeneratorType, Row};
 
+const COLUMN_FLAGS: [&str; 5] = [
+    "primary_key",
+    "autoincrement",
+    "custom",
+    "optional",
+    "unsized",
+];
+
 impl Parser {
     pub fn parse_columns(&mut self) -> syn::Result<Columns> {
         let ident = self.input_iter.next().ok_or(syn::Error::new(
@@ -68,15 +77,7 @@ impl Parser {
 
         self.parse_colon()?;
 
-        let type_ = self.input_iter.next().ok_or(syn::Error::new(
-            self.input.span(),
-            "Expected column type in declaration",
-        ))?;
-        let type_ = if let TokenTree::Ident(type_) = type_ {
-            type_
-        } else {
-            return Err(syn::Error::new(type_.span(), "Expected type."));
-        };
+        let type_ = self.parse_column_type()?;
 
         let is_primary_key = if let Some(TokenTree::Ident(index)) = self.input_iter.peek() {
             if index.to_string().as_str() == "primary_key" {
@@ -114,6 +115,17 @@ impl Parser {
             false
         };
 
+        let is_unsized = if let Some(TokenTree::Ident(flag)) = self.input_iter.peek() {
+            if flag.to_string().as_str() == "unsized" {
+                self.input_iter.next();
+                true
+            } else {
+                false
+            }
+        } else {
+            false
+        };
+
         self.try_parse_comma()?;
 
         Ok(Row {
@@ -122,8 +134,40 @@ impl Parser {
             is_primary_key,
             gen_type,
             optional,
+            is_unsized,
         })
     }
+
+    /// Parses column type, which can be any Rust type (`Vec<u8>`, `[u8; 32]`,
+    /// `std::net::Ipv4Addr` etc.). Type's tokens are read until comma or
+    /// column flag (`primary_key`, `optional` etc.) outside of generic args.
+    fn parse_column_type(&mut self) -> syn::Result<TokenStream> {
+        let mut tokens = TokenStream::new();
+        let mut depth = 0usize;
+        while let Some(tt) = self.input_iter.peek() {
+            match tt {
+                TokenTree::Punct(p) if p.as_char() == ',' && depth == 0 => break,
+                TokenTree::Ident(i)
+                    if depth == 0 && COLUMN_FLAGS.contains(&i.to_string().as_str()) =>
+                {
+                    break;
+                }
+                TokenTree::Punct(p) if p.as_char() == '<' => depth += 1,
+                TokenTree::Punct(p) if p.as_char() == '>' => depth = depth.saturating_sub(1),
+                _ => {}
+            }
+            tokens.extend(self.input_iter.next());
+        }
+        if tokens.is_empty() {
+            return Err(syn::Error::new(
+                self.input.span(),
+                "Expected column type in declaration",
+            ));
+        }
+        let type_ = syn::parse2::<syn::Type>(tokens)?;
+
+        Ok(type_.to_token_stream())
+    }
 }
 
 #[cfg(test)]
@@ -157,6 +201,19 @@ mod tests {
         assert_eq!(map.get("test"), Some(&"u64".to_string()));
     }
 
+    #[test]
+    fn test_columns_parse_unsized_flag() {
+        let tokens = quote! {columns: {
+            id: i64 primary_key,
+            status: Status,
+            name: Name unsized,
+            addr: std::net::Ipv4Addr optional,
+        }};
+        let mut parser = Parser::new(tokens);
+        let columns = parser.parse_columns().unwrap();
+        assert_eq!(columns.unsized_columns.len(), 1);
+    }
+
     #[test]
     fn test_columns_parse_no_last_comma() {
         let tokens = quote! {columns: {
@@ -319,5 +376,26 @@ mod tests {
             assert!(row.optional);
             assert!(!row.is_primary_key)
         }
+
+        #[test]
+        fn test_row_parse_type_path() {
+            let row_tokens = quote! {
+                a: HashMap<u64, Vec<u8>> optional,
+                b: [u8; 32],
+                c: std::net::Ipv4Addr primary_key
+            };
+
+            let mut parser = Parser::new(row_tokens);
+            let a = parser.parse_row().unwrap();
+            let b = parser.parse_row().unwrap();
+            let c = parser.parse_row().unwrap();
+
+            assert_eq!(a.type_.to_string(), "HashMap < u64 , Vec < u8 > >");
+            assert!(a.optional);
+            assert_eq!(b.type_.to_string(), "[u8 ; 32]");
+            assert_eq!(c.type_.to_string(), "std :: net :: Ipv4Addr");
+            assert!(c.is_primary_key);
+            assert!(!parser.has_next())
+        }
     }
 }
@@ -1,9 +1,13 @@
 use crate::worktable::Parser;
 use crate::worktable::model::Index;
-use proc_macro2::{Delimiter, Ident, TokenTree};
+use proc_macro2::{Delimiter, Ident, TokenStream, TokenTree};
+use quote::quote;
 use std::collections::HashMap;
 use syn::spanned::Spanned;
 
+/// Keywords which can follow index column.
+const INDEX_MODIFIERS: &[&str] = &["unique", "array", "ci", "prefix", "hash", "bitmap", "where"];
+
 impl Parser {
     pub fn parse_indexes(&mut self) -> syn::Result<HashMap<Ident, Index>> {
         let ident = self.input_iter.next().ok_or(syn::Error::new(
@@ -52,7 +56,6 @@ impl Parser {
             rows.insert(name, row);
             ind = parser.has_next()
         }
-
         self.try_parse_comma()?;
 
         Ok(rows)
@@ -80,18 +83,85 @@ impl Parser {
         } else {
             return Err(syn::Error::new(row_name.span(), "Expected row name"));
         };
-
-        let is_unique = if let Some(TokenTree::Ident(unique)) = self.input_iter.peek() {
-            if unique.to_string().as_str() == "unique" {
-                self.input_iter.next();
-                true
-            } else {
-                false
-            }
+        let (expr, expr_type) = self.parse_index_expr(&row_name)?;
+        let is_spatial = expr.is_none() && row_name == "spatial";
+        let (expr, expr_type) = if is_spatial {
+            let (x, y) = self.parse_spatial_columns(&row_name)?;
+            (Some(quote! { (#x, #y) }), Some(quote! { (f64, f64) }))
         } else {
-            false
+            (expr, expr_type)
+        };
+        // Computed index has no column, so it's keyed by its name.
+        let row_name = if expr.is_some() {
+            ident.clone()
+        } else {
+            row_name
         };
 
+        let mut is_unique = false;
+        let mut is_array = false;
+        let mut is_ci = false;
+        let mut is_prefix = false;
+        let mut is_hash = false;
+        let mut is_bitmap = false;
+        while let Some(TokenTree::Ident(modifier)) = self.input_iter.peek() {
+            match modifier.to_string().as_str() {
+                "unique" => is_unique = true,
+                "array" => is_array = true,
+                "ci" => is_ci = true,
+                "prefix" => is_prefix = true,
+                "hash" => is_hash = true,
+                "bitmap" => is_bitmap = true,
+                _ => break,
+            }
+            self.input_iter.next();
+        }
+        let predicate = self.parse_index_predicate()?;
+        if is_unique && is_array {
+            return Err(syn::Error::new(
+                ident.span(),
+                "`array` index can't be `unique`",
+            ));
+        }
+        if is_prefix && is_array {
+            return Err(syn::Error::new(
+                ident.span(),
+                "`array` index can't be `prefix`",
+            ));
+        }
+        if is_hash && (is_array || is_prefix) {
+            return Err(syn::Error::new(
+                ident.span(),
+                "`hash` index can't be `array` or `prefix` as it supports only lookups by whole key",
+            ));
+        }
+        if is_spatial && (is_unique || is_array || is_ci || is_prefix || is_hash || is_bitmap) {
+            return Err(syn::Error::new(
+                ident.span(),
+                "`spatial` index can't be combined with other modifiers",
+            ));
+        }
+        if is_array && expr.is_some() {
+            return Err(syn::Error::new(
+                ident.span(),
+                "computed index can't be `array`",
+            ));
+        }
+        if is_bitmap
+            && (is_unique
+                || is_array
+                || is_ci
+                || is_prefix
+                || is_hash
+                || predicate.is_some()
+                || expr.is_some())
+        {
+            return Err(syn::Error::new(
+                ident.span(),
+                "`bitmap` index can't be combined with other modifiers",
+            ));
+        }
+
         self.try_parse_comma()?;
 
         Ok((
@@ -100,7 +170,312 @@ impl Parser {
                 name: ident,
                 field: row_name,
                 is_unique,
+                is_array,
+                is_ci,
+                is_prefix,
+                is_hash,
+                is_bitmap,
+                is_spatial,
+                predicate,
+                expr,
+                expr_type,
             },
         ))
     }
+
+    /// Parses `expr(<expression>) as <type>` of the computed index if
+    /// `row_name` is `expr` followed by parentheses. Type is optional.
+    fn parse_index_expr(
+        &mut self,
+        row_name: &Ident,
+    ) -> syn::Result<(Option<TokenStream>, Option<TokenStream>)> {
+        if row_name != "expr" {
+            return Ok((None, None));
+        }
+        let expr = match self.input_iter.peek() {
+            Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Parenthesis => {
+                group.stream()
+            }
+            _ => return Ok((None, None)),
+        };
+        self.input_iter.next();
+        syn::parse2::<syn::Expr>(expr.clone())?;
+
+        match self.input_iter.peek() {
+            Some(TokenTree::Ident(ident)) if ident == "as" => {}
+            _ => return Ok((Some(expr), None)),
+        }
+        let as_ident = self.input_iter.next().expect("was just peeked");
+        let mut type_ = TokenStream::new();
+        let mut depth = 0;
+        while let Some(tt) = self.input_iter.peek() {
+            match tt {
+                TokenTree::Punct(p) if p.as_char() == ',' && depth == 0 => break,
+                TokenTree::Ident(i)
+                    if depth == 0
+                        && !type_.is_empty()
+                        && INDEX_MODIFIERS.contains(&i.to_string().as_str()) =>
+                {
+                    break;
+                }
+                TokenTree::Punct(p) if p.as_char() == '<' => depth += 1,
+                TokenTree::Punct(p) if p.as_char() == '>' => depth -= 1,
+                _ => {}
+            }
+            type_.extend([self.input_iter.next().expect("was just peeked")]);
+        }
+        if type_.is_empty() {
+            return Err(syn::Error::new(
+                as_ident.span(),
+                "Expected computed index key type after `as`",
+            ));
+        }
+        syn::parse2::<syn::Type>(type_.clone())?;
+
+        Ok((Some(expr), Some(type_)))
+    }
+
+    /// Parses `spatial(<x column>, <y column>)` of the spatial index.
+    fn parse_spatial_columns(&mut self, spatial_ident: &Ident) -> syn::Result<(Ident, Ident)> {
+        let Some(TokenTree::Group(group)) = self.input_iter.next() else {
+            return Err(syn::Error::new(
+                spatial_ident.span(),
+                "Expected two columns in parentheses after `spatial`",
+            ));
+        };
+        if group.delimiter() != Delimiter::Parenthesis {
+            return Err(syn::Error::new(group.span(), "Expected parentheses"));
+        }
+        let columns: Vec<_> = group
+            .stream()
+            .into_iter()
+            .filter(|tt| !matches!(tt, TokenTree::Punct(p) if p.as_char() == ','))
+            .collect();
+        match columns.as_slice() {
+            [TokenTree::Ident(x), TokenTree::Ident(y)] => Ok((x.clone(), y.clone())),
+            _ => Err(syn::Error::new(
+                group.span(),
+                "Expected two columns in `spatial` index, like `spatial(lat, lon)`",
+            )),
+        }
+    }
+
+    /// Parses `where(<predicate>)` of the partial index if it's present.
+    fn parse_index_predicate(&mut self) -> syn::Result<Option<TokenStream>> {
+        match self.input_iter.peek() {
+            Some(TokenTree::Ident(ident)) if ident == "where" => {}
+            _ => return Ok(None),
+        }
+        let where_ident = self.input_iter.next().expect("was just peeked");
+        let Some(TokenTree::Group(group)) = self.input_iter.next() else {
+            return Err(syn::Error::new(
+                where_ident.span(),
+                "Expected predicate in parentheses after `where`",
+            ));
+        };
+        if group.delimiter() != Delimiter::Parenthesis {
+            return Err(syn::Error::new(group.span(), "Expected parentheses"));
+        }
+        syn::parse2::<syn::Expr>(group.stream())?;
+
+        Ok(Some(group.stream()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quote::quote;
+
+    use crate::worktable::Parser;
+
+    #[test]
+    fn test_indexes_parse() {
+        let tokens = quote! {indexes: {
+            test_idx: test unique,
+            another_idx: another,
+        }};
+        let mut parser = Parser::new(tokens);
+        let indexes = parser.parse_indexes().unwrap();
+
+        let test = indexes.values().find(|i| i.name == "test_idx").unwrap();
+        assert_eq!(test.field.to_string(), "test");
+        assert!(test.is_unique);
+        assert!(!test.is_array);
+        let another = indexes.values().find(|i| i.name == "another_idx").unwrap();
+        assert!(!another.is_unique);
+    }
+
+    #[test]
+    fn test_array_index_parse() {
+        let tokens = quote! {indexes: {
+            tags_idx: tags array,
+        }};
+        let mut parser = Parser::new(tokens);
+        let indexes = parser.parse_indexes().unwrap();
+
+        let tags = indexes.values().next().unwrap();
+        assert_eq!(tags.field.to_string(), "tags");
+        assert!(tags.is_array);
+        assert!(!tags.is_unique);
+    }
+
+    #[test]
+    fn test_unique_array_index_parse() {
+        let tokens = quote! {indexes: {
+            tags_idx: tags unique array,
+        }};
+        let mut parser = Parser::new(tokens);
+
+        assert!(parser.parse_indexes().is_err());
+    }
+
+    #[test]
+    fn test_string_index_modifiers_parse() {
+        let tokens = quote! {indexes: {
+            symbol_idx: symbol unique prefix ci,
+            name_idx: name ci,
+        }};
+        let mut parser = Parser::new(tokens);
+        let indexes = parser.parse_indexes().unwrap();
+
+        let symbol = indexes.values().find(|i| i.name == "symbol_idx").unwrap();
+        assert!(symbol.is_unique);
+        assert!(symbol.is_prefix);
+        assert!(symbol.is_ci);
+        let name = indexes.values().find(|i| i.name == "name_idx").unwrap();
+        assert!(!name.is_prefix);
+        assert!(name.is_ci);
+    }
+
+    #[test]
+    fn test_hash_index_parse() {
+        let tokens = quote! {indexes: {
+            order_idx: order_id unique hash,
+            user_idx: user_id hash,
+        }};
+        let mut parser = Parser::new(tokens);
+        let indexes = parser.parse_indexes().unwrap();
+
+        let order = indexes.values().find(|i| i.name == "order_idx").unwrap();
+        assert!(order.is_unique);
+        assert!(order.is_hash);
+        let user = indexes.values().find(|i| i.name == "user_idx").unwrap();
+        assert!(!user.is_unique);
+        assert!(user.is_hash);
+    }
+
+    #[test]
+    fn test_hash_prefix_index_parse() {
+        let tokens = quote! {indexes: {
+            symbol_idx: symbol hash prefix,
+        }};
+        let mut parser = Parser::new(tokens);
+
+        assert!(parser.parse_indexes().is_err());
+    }
+
+    #[test]
+    fn test_bitmap_index_parse() {
+        let tokens = quote! {indexes: {
+            status_idx: status bitmap,
+            side_idx: side,
+        }};
+        let mut parser = Parser::new(tokens);
+        let indexes = parser.parse_indexes().unwrap();
+
+        let status = indexes.values().find(|i| i.name == "status_idx").unwrap();
+        assert!(status.is_bitmap);
+        let side = indexes.values().find(|i| i.name == "side_idx").unwrap();
+        assert!(!side.is_bitmap);
+
+        let tokens = quote! {indexes: {
+            status_idx: status unique bitmap,
+        }};
+        let mut parser = Parser::new(tokens);
+        assert!(parser.parse_indexes().is_err());
+    }
+
+    #[test]
+    fn test_partial_index_parse() {
+        let tokens = quote! {indexes: {
+            open_coid_idx: client_order_id unique where(status == Open),
+            qty_idx: qty where(qty > 0 && !closed),
+        }};
+        let mut parser = Parser::new(tokens);
+        let indexes = parser.parse_indexes().unwrap();
+
+        let coid = indexes
+            .values()
+            .find(|i| i.name == "open_coid_idx")
+            .unwrap();
+        assert!(coid.is_unique);
+        assert_eq!(
+            coid.predicate.as_ref().unwrap().to_string(),
+            quote! { status == Open }.to_string()
+        );
+        let qty = indexes.values().find(|i| i.name == "qty_idx").unwrap();
+        assert!(qty.is_partial());
+
+        let tokens = quote! {indexes: {
+            status_idx: status bitmap where(side == Buy),
+        }};
+        let mut parser = Parser::new(tokens);
+        assert!(parser.parse_indexes().is_err());
+    }
+
+    #[test]
+    fn test_computed_index_parse() {
+        let tokens = quote! {indexes: {
+            notional_idx: expr(price * qty) as f64,
+            lower_email_idx: expr(email.to_lowercase()) unique,
+            pair_idx: expr(pair.clone()) as Option<String> hash,
+        }};
+        let mut parser = Parser::new(tokens);
+        let indexes = parser.parse_indexes().unwrap();
+
+        let notional = indexes.values().find(|i| i.name == "notional_idx").unwrap();
+        assert_eq!(notional.field.to_string(), "notional_idx");
+        assert_eq!(
+            notional.expr.as_ref().unwrap().to_string(),
+            quote! { price * qty }.to_string()
+        );
+        assert_eq!(notional.expr_type.as_ref().unwrap().to_string(), "f64");
+        let email = indexes
+            .values()
+            .find(|i| i.name == "lower_email_idx")
+            .unwrap();
+        assert!(email.is_unique);
+        assert!(email.is_computed());
+        assert!(email.expr_type.is_none());
+        let pair = indexes.values().find(|i| i.name == "pair_idx").unwrap();
+        assert!(pair.is_hash);
+        assert_eq!(
+            pair.expr_type.as_ref().unwrap().to_string(),
+            quote! { Option<String> }.to_string()
+        );
+    }
+
+    #[test]
+    fn test_spatial_index_parse() {
+        let tokens = quote! {indexes: {
+            location_idx: spatial(lat, lon),
+        }};
+        let mut parser = Parser::new(tokens);
+        let indexes = parser.parse_indexes().unwrap();
+
+        let location = indexes.values().next().unwrap();
+        assert!(location.is_spatial);
+        assert!(location.is_rebuilt_on_load());
+        assert_eq!(location.field.to_string(), "location_idx");
+        assert_eq!(
+            location.expr.as_ref().unwrap().to_string(),
+            quote! { (lat, lon) }.to_string()
+        );
+
+        let tokens = quote! {indexes: {
+            location_idx: spatial(lat, lon) unique,
+        }};
+        let mut parser = Parser::new(tokens);
+        assert!(parser.parse_indexes().is_err());
+    }
 }
@@ -68,6 +68,6 @@ mod tests {
         assert_eq!(op.columns.len(), 2);
         assert_eq!(op.columns[0], "id");
         assert_eq!(op.columns[1], "test");
-        assert_eq!(op.by, "name");
+        assert_eq!(op.by_column().unwrap(), "name");
     }
 }
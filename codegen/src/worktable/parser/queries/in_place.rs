@@ -66,6 +66,6 @@ mod tests {
         assert_eq!(op.name, "TestQuery");
         assert_eq!(op.columns.len(), 1);
         assert_eq!(op.columns[0], "id");
-        assert_eq!(op.by, "name");
+        assert_eq!(op.by_column().unwrap(), "name");
     }
 }
@@ -78,11 +78,25 @@ impl Parser {
             ));
         };
 
-        Ok(Operation {
-            name,
-            columns,
-            by: by_name,
-        })
+        // Columns after `by` are separated by commas, like operations, so
+        // identifier is `by` column only if it's not followed by operation
+        // columns group.
+        let mut by = vec![by_name];
+        loop {
+            let mut lookahead = self.input_iter.clone();
+            match (lookahead.next(), lookahead.next(), lookahead.next()) {
+                (Some(TokenTree::Punct(p)), Some(TokenTree::Ident(column)), next)
+                    if p.as_char() == ',' && !matches!(next, Some(TokenTree::Group(_))) =>
+                {
+                    self.input_iter.next();
+                    self.input_iter.next();
+                    by.push(column);
+                }
+                _ => break,
+            }
+        }
+
+        Ok(Operation { name, columns, by })
     }
 
     pub fn parse_column_ident(&mut self) -> syn::Result<Ident> {
@@ -119,6 +133,25 @@ mod tests {
         assert_eq!(op.columns.len(), 2);
         assert_eq!(op.columns[0], "id".to_string());
         assert_eq!(op.columns[1], "test".to_string());
-        assert_eq!(op.by.to_string(), "name".to_string());
+        assert_eq!(op.by_column().unwrap().to_string(), "name".to_string());
+    }
+
+    #[test]
+    fn test_operation_by_several_columns() {
+        let tokens = quote! {
+            TestQuery(val) by id, test,
+            Test1Query(val) by name
+        };
+
+        let mut parser = Parser::new(tokens);
+        let ops = parser.parse_operations().unwrap();
+        assert_eq!(ops.len(), 2);
+        let op = ops.values().find(|op| op.name == "TestQuery").unwrap();
+        assert_eq!(op.by.len(), 2);
+        assert_eq!(op.by[0], "id");
+        assert_eq!(op.by[1], "test");
+        assert!(op.by_column().is_none());
+        let op = ops.values().find(|op| op.name == "Test1Query").unwrap();
+        assert_eq!(op.by_column().unwrap(), "name");
     }
 }
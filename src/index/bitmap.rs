@@ -0,0 +1,409 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt::{self, Debug, Formatter};
+use std::sync::Arc;
+
+use data_bucket::Link;
+use parking_lot::RwLock;
+
+/// Count of the bits in one word of [`RowBitmap`].
+const WORD_BITS: u64 = u64::BITS as u64;
+
+/// Compressed set of row ids. Only non-zero words are stored, so sparse
+/// sets take as much memory as their set bits need.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RowBitmap {
+    words: BTreeMap<u64, u64>,
+}
+
+impl RowBitmap {
+    pub fn insert(&mut self, id: u64) -> bool {
+        let word = self.words.entry(id / WORD_BITS).or_default();
+        let bit = 1 << (id % WORD_BITS);
+        let inserted = *word & bit == 0;
+        *word |= bit;
+        inserted
+    }
+
+    pub fn remove(&mut self, id: u64) -> bool {
+        let key = id / WORD_BITS;
+        let Some(word) = self.words.get_mut(&key) else {
+            return false;
+        };
+        let bit = 1 << (id % WORD_BITS);
+        let removed = *word & bit != 0;
+        *word &= !bit;
+        if *word == 0 {
+            self.words.remove(&key);
+        }
+        removed
+    }
+
+    pub fn contains(&self, id: u64) -> bool {
+        self.words
+            .get(&(id / WORD_BITS))
+            .is_some_and(|word| word & (1 << (id % WORD_BITS)) != 0)
+    }
+
+    /// Returns count of the set bits.
+    pub fn len(&self) -> usize {
+        self.words.values().map(|w| w.count_ones() as usize).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    /// Returns set of the ids that are present in both bitmaps.
+    pub fn intersection(&self, other: &RowBitmap) -> RowBitmap {
+        let (small, big) = if self.words.len() <= other.words.len() {
+            (self, other)
+        } else {
+            (other, self)
+        };
+        let words = small
+            .words
+            .iter()
+            .filter_map(|(key, word)| {
+                let word = word & big.words.get(key)?;
+                (word != 0).then_some((*key, word))
+            })
+            .collect();
+        RowBitmap { words }
+    }
+
+    /// Returns set ids in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = u64> + '_ {
+        self.words.iter().flat_map(|(key, word)| {
+            let mut word = *word;
+            std::iter::from_fn(move || {
+                if word == 0 {
+                    return None;
+                }
+                let bit = word.trailing_zeros() as u64;
+                word &= word - 1;
+                Some(key * WORD_BITS + bit)
+            })
+        })
+    }
+
+    /// Returns set of the ids that are present in any of bitmaps.
+    pub fn union(&self, other: &RowBitmap) -> RowBitmap {
+        let mut words = self.words.clone();
+        for (key, word) in &other.words {
+            *words.entry(*key).or_default() |= word;
+        }
+        RowBitmap { words }
+    }
+
+    /// Returns count of the stored words.
+    pub fn word_count(&self) -> usize {
+        self.words.len()
+    }
+}
+
+#[derive(Debug, Default)]
+struct RowIdsInner {
+    ids: HashMap<Link, (u64, usize)>,
+    links: Vec<Option<Link>>,
+    free: BTreeSet<u64>,
+}
+
+/// Dense ids of the rows which are used as bit positions in [`RowBitmap`].
+/// Link's offset is a byte offset, so it can't be used as a bit position
+/// without making bitmaps sparse. Ids are shared by all bitmap indexes of one
+/// table, so their bitmaps can be intersected. Id is released when no bitmap
+/// holds it anymore and the lowest free id is reused first.
+#[derive(Debug, Default)]
+pub struct RowIds {
+    inner: RwLock<RowIdsInner>,
+}
+
+impl RowIds {
+    /// Returns id of the link and increments count of its holders.
+    fn acquire(&self, link: Link) -> u64 {
+        let mut inner = self.inner.write();
+        if let Some((id, holders)) = inner.ids.get_mut(&link) {
+            *holders += 1;
+            return *id;
+        }
+        let id = if let Some(id) = inner.free.pop_first() {
+            inner.links[id as usize] = Some(link);
+            id
+        } else {
+            inner.links.push(Some(link));
+            inner.links.len() as u64 - 1
+        };
+        inner.ids.insert(link, (id, 1));
+        id
+    }
+
+    /// Decrements count of the link's holders. Id is freed when it has no
+    /// holders left.
+    fn release(&self, link: &Link) {
+        let mut inner = self.inner.write();
+        let Some((id, holders)) = inner.ids.get_mut(link) else {
+            return;
+        };
+        *holders -= 1;
+        if *holders != 0 {
+            return;
+        }
+        let id = *id;
+        inner.ids.remove(link);
+        inner.links[id as usize] = None;
+        inner.free.insert(id);
+    }
+
+    fn get(&self, link: &Link) -> Option<u64> {
+        self.inner.read().ids.get(link).map(|(id, _)| *id)
+    }
+
+    /// Returns links of the ids in the bitmap.
+    pub fn links(&self, bitmap: &RowBitmap) -> Vec<Link> {
+        let inner = self.inner.read();
+        bitmap
+            .iter()
+            .filter_map(|id| inner.links.get(id as usize).copied().flatten())
+            .collect()
+    }
+
+    /// Returns count of the rows with id.
+    pub fn len(&self) -> usize {
+        self.inner.read().ids.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.inner.read().ids.is_empty()
+    }
+}
+
+/// Index which keeps [`RowBitmap`] of the rows for every value. It's used for
+/// columns with low cardinality, so values are stored in a `Vec` and only
+/// `PartialEq` is needed for keys. Bitmaps of the indexes which share
+/// [`RowIds`] can be intersected before any row is read.
+pub struct IndexBitmap<K> {
+    values: RwLock<Vec<(K, RowBitmap)>>,
+    row_ids: Arc<RowIds>,
+}
+
+impl<K> Default for IndexBitmap<K> {
+    fn default() -> Self {
+        Self::with_row_ids(Arc::default())
+    }
+}
+
+impl<K: Debug> Debug for IndexBitmap<K> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let values = self.values.read();
+        f.debug_map()
+            .entries(values.iter().map(|(k, b)| (k, b.len())))
+            .finish()
+    }
+}
+
+impl<K> IndexBitmap<K> {
+    pub fn with_row_ids(row_ids: Arc<RowIds>) -> Self {
+        Self {
+            values: RwLock::new(vec![]),
+            row_ids,
+        }
+    }
+}
+
+impl<K> IndexBitmap<K>
+where
+    K: PartialEq,
+{
+    /// Inserts link for the value. Returns link back if it's already present.
+    pub fn insert(&self, key: K, link: Link) -> Option<Link> {
+        let mut values = self.values.write();
+        let pos = values.iter().position(|(k, _)| *k == key);
+        if let Some(pos) = pos
+            && self
+                .row_ids
+                .get(&link)
+                .is_some_and(|id| values[pos].1.contains(id))
+        {
+            return Some(link);
+        }
+        let id = self.row_ids.acquire(link);
+        if let Some(pos) = pos {
+            values[pos].1.insert(id);
+        } else {
+            let mut bitmap = RowBitmap::default();
+            bitmap.insert(id);
+            values.push((key, bitmap));
+        }
+        None
+    }
+
+    pub fn remove(&self, key: &K, link: &Link) -> Option<Link> {
+        let mut values = self.values.write();
+        let id = self.row_ids.get(link)?;
+        let pos = values.iter().position(|(k, _)| k == key)?;
+        if !values[pos].1.remove(id) {
+            return None;
+        }
+        if values[pos].1.is_empty() {
+            values.swap_remove(pos);
+        }
+        self.row_ids.release(link);
+        Some(*link)
+    }
+
+    /// Returns bitmap of the rows with the value.
+    pub fn get(&self, key: &K) -> RowBitmap {
+        self.values
+            .read()
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, b)| b.clone())
+            .unwrap_or_default()
+    }
+
+    /// Returns count of the rows with the value.
+    pub fn count(&self, key: &K) -> usize {
+        self.values
+            .read()
+            .iter()
+            .find(|(k, _)| k == key)
+            .map_or(0, |(_, b)| b.len())
+    }
+
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.values.read().iter().any(|(k, _)| k == key)
+    }
+
+    /// Returns links of the rows in the bitmap.
+    pub fn links(&self, bitmap: &RowBitmap) -> Vec<Link> {
+        self.row_ids.links(bitmap)
+    }
+
+    /// Returns count of the indexed rows.
+    pub fn len(&self) -> usize {
+        self.values
+            .read()
+            .iter()
+            .fold(RowBitmap::default(), |rows, (_, b)| rows.union(b))
+            .len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.read().is_empty()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.values.read().capacity()
+    }
+
+    /// Returns count of the distinct values.
+    pub fn value_count(&self) -> usize {
+        self.values.read().len()
+    }
+
+    pub(crate) fn with_values<R>(&self, f: impl FnOnce(&[(K, RowBitmap)]) -> R) -> R {
+        f(&self.values.read())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use data_bucket::Link;
+
+    use super::{IndexBitmap, RowBitmap, RowIds};
+
+    fn link(page: u32, offset: u32) -> Link {
+        Link {
+            page_id: page.into(),
+            offset,
+            length: 8,
+        }
+    }
+
+    #[test]
+    fn bitmap_insert_remove() {
+        let mut bitmap = RowBitmap::default();
+        assert!(bitmap.insert(3));
+        assert!(bitmap.insert(200));
+        assert!(!bitmap.insert(3));
+        assert_eq!(bitmap.len(), 2);
+        assert_eq!(bitmap.iter().collect::<Vec<_>>(), vec![3, 200]);
+
+        assert!(bitmap.remove(200));
+        assert!(!bitmap.remove(200));
+        assert_eq!(bitmap.word_count(), 1);
+        assert!(bitmap.remove(3));
+        assert!(bitmap.is_empty());
+    }
+
+    #[test]
+    fn bitmap_intersection() {
+        let mut a = RowBitmap::default();
+        let mut b = RowBitmap::default();
+        for i in 0..100 {
+            a.insert(i * 2);
+            b.insert(i * 3);
+        }
+        let both = a.intersection(&b);
+        assert_eq!(both.len(), 34);
+        assert!(both.iter().all(|s| s % 6 == 0));
+    }
+
+    #[test]
+    fn index_links() {
+        let index = IndexBitmap::default();
+        assert!(index.insert("a", link(1, 0)).is_none());
+        assert!(index.insert("a", link(1, 8)).is_none());
+        assert!(index.insert("b", link(2, 0)).is_none());
+        assert!(index.insert("a", link(1, 0)).is_some());
+
+        assert_eq!(index.count(&"a"), 2);
+        assert_eq!(index.links(&index.get(&"b")), vec![link(2, 0)]);
+        assert_eq!(index.remove(&"b", &link(2, 0)), Some(link(2, 0)));
+        assert!(!index.contains_key(&"b"));
+        assert_eq!(index.len(), 2);
+    }
+
+    #[test]
+    fn index_same_link_value_change() {
+        let index = IndexBitmap::default();
+        index.insert("a", link(1, 0));
+        index.insert("b", link(1, 0));
+        assert_eq!(index.remove(&"a", &link(1, 0)), Some(link(1, 0)));
+
+        assert_eq!(index.links(&index.get(&"b")), vec![link(1, 0)]);
+        assert_eq!(index.len(), 1);
+    }
+
+    #[test]
+    fn index_dense_row_ids() {
+        let row_ids = Arc::new(RowIds::default());
+        let status = IndexBitmap::with_row_ids(row_ids.clone());
+        let kind = IndexBitmap::with_row_ids(row_ids.clone());
+        for i in 0..128 {
+            status.insert(i % 2, link(i / 16, (i % 16) * 120));
+            kind.insert(i % 3, link(i / 16, (i % 16) * 120));
+        }
+        assert_eq!(row_ids.len(), 128);
+        assert_eq!(status.get(&0).iter().max(), Some(126));
+        assert_eq!(status.get(&0).word_count(), 2);
+
+        let both = status.get(&0).intersection(&kind.get(&0));
+        assert_eq!(both.len(), 22);
+        assert!(status.links(&both).iter().all(|l| {
+            let i = u32::from(l.page_id) * 16 + l.offset / 120;
+            i % 6 == 0
+        }));
+
+        status.remove(&1, &link(0, 120));
+        assert_eq!(row_ids.len(), 128);
+        kind.remove(&1, &link(0, 120));
+        assert_eq!(row_ids.len(), 127);
+        // Freed id is reused, so ids stay dense.
+        status.insert(0, link(100, 0));
+        assert!(status.get(&0).contains(1));
+        assert_eq!(row_ids.len(), 128);
+    }
+}
@@ -0,0 +1,165 @@
+use std::collections::VecDeque;
+use std::fmt::Debug;
+use std::ops::Bound;
+
+use indexset::core::node::NodeLike;
+use indexset::core::pair::Pair;
+
+use crate::IndexMap;
+
+/// Count of the entries that are read from the index at once.
+pub const INDEX_CHUNK_SIZE: usize = 128;
+
+/// Iterator over [`IndexMap`] entries that reads index in chunks of
+/// [`INDEX_CHUNK_SIZE`]. Index iterator holds node locks while alive, so
+/// every chunk is read with a fresh range (re-seeked from the last read key)
+/// and no index locks are held between `next` calls. This makes it safe to
+/// keep this iterator across `.await` points and modify the table meanwhile.
+pub struct ChunkedIter<'a, K, V, Node>
+where
+    K: Debug + Send + Ord + Clone + 'static,
+    V: Debug + Send + Clone + 'static,
+    Node: NodeLike<Pair<K, V>> + Send + 'static,
+{
+    map: &'a IndexMap<K, V, Node>,
+    front: Bound<K>,
+    back: Bound<K>,
+    front_chunk: VecDeque<(K, V)>,
+    back_chunk: VecDeque<(K, V)>,
+    /// Is set when all entries between `front` and `back` are read into
+    /// chunks.
+    is_exhausted: bool,
+}
+
+impl<'a, K, V, Node> ChunkedIter<'a, K, V, Node>
+where
+    K: Debug + Send + Ord + Clone + 'static,
+    V: Debug + Send + Clone + 'static,
+    Node: NodeLike<Pair<K, V>> + Send + 'static,
+{
+    pub fn new(map: &'a IndexMap<K, V, Node>) -> Self {
+        Self {
+            map,
+            front: Bound::Unbounded,
+            back: Bound::Unbounded,
+            front_chunk: VecDeque::new(),
+            back_chunk: VecDeque::new(),
+            is_exhausted: false,
+        }
+    }
+
+    fn read_chunk(&mut self, rev: bool) -> Vec<(K, V)> {
+        let range = self
+            .map
+            .range::<K, _>((self.front.as_ref(), self.back.as_ref()));
+        let entries = |(k, v): (&K, &V)| (k.clone(), v.clone());
+        let chunk: Vec<_> = if rev {
+            range.rev().take(INDEX_CHUNK_SIZE).map(entries).collect()
+        } else {
+            range.take(INDEX_CHUNK_SIZE).map(entries).collect()
+        };
+        if chunk.len() < INDEX_CHUNK_SIZE {
+            self.is_exhausted = true;
+        }
+        chunk
+    }
+}
+
+impl<K, V, Node> Iterator for ChunkedIter<'_, K, V, Node>
+where
+    K: Debug + Send + Ord + Clone + 'static,
+    V: Debug + Send + Clone + 'static,
+    Node: NodeLike<Pair<K, V>> + Send + 'static,
+{
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.front_chunk.is_empty() && !self.is_exhausted {
+            let chunk = self.read_chunk(false);
+            if let Some((k, _)) = chunk.last() {
+                self.front = Bound::Excluded(k.clone());
+            }
+            self.front_chunk.extend(chunk);
+        }
+        self.front_chunk
+            .pop_front()
+            .or_else(|| self.back_chunk.pop_front())
+    }
+}
+
+impl<K, V, Node> DoubleEndedIterator for ChunkedIter<'_, K, V, Node>
+where
+    K: Debug + Send + Ord + Clone + 'static,
+    V: Debug + Send + Clone + 'static,
+    Node: NodeLike<Pair<K, V>> + Send + 'static,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.back_chunk.is_empty() && !self.is_exhausted {
+            let chunk = self.read_chunk(true);
+            if let Some((k, _)) = chunk.last() {
+                self.back = Bound::Excluded(k.clone());
+            }
+            for entry in chunk {
+                self.back_chunk.push_front(entry);
+            }
+        }
+        self.back_chunk
+            .pop_back()
+            .or_else(|| self.front_chunk.pop_back())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ChunkedIter, INDEX_CHUNK_SIZE};
+    use crate::IndexMap;
+
+    #[test]
+    fn iterates_all_entries_in_order() {
+        let map = IndexMap::<u64, u64>::new();
+        let count = INDEX_CHUNK_SIZE as u64 * 3 + 5;
+        for i in 0..count {
+            map.insert(i, i * 2);
+        }
+
+        let keys: Vec<_> = ChunkedIter::new(&map).map(|(k, _)| k).collect();
+        assert_eq!(keys, (0..count).collect::<Vec<_>>());
+
+        let keys: Vec<_> = ChunkedIter::new(&map).rev().map(|(k, _)| k).collect();
+        assert_eq!(keys, (0..count).rev().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn iterates_from_both_ends() {
+        let map = IndexMap::<u64, u64>::new();
+        let count = INDEX_CHUNK_SIZE as u64 * 2 + 1;
+        for i in 0..count {
+            map.insert(i, i);
+        }
+
+        let mut iter = ChunkedIter::new(&map);
+        let mut keys = vec![];
+        loop {
+            match (iter.next(), iter.next_back()) {
+                (None, None) => break,
+                (front, back) => keys.extend(front.into_iter().chain(back).map(|(k, _)| k)),
+            }
+        }
+        keys.sort();
+        assert_eq!(keys, (0..count).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn does_not_lock_index_between_calls() {
+        let map = IndexMap::<u64, u64>::new();
+        for i in 0..10 {
+            map.insert(i, i);
+        }
+
+        let mut iter = ChunkedIter::new(&map);
+        assert_eq!(iter.next(), Some((0, 0)));
+        map.insert(100, 100);
+        // Whole map fits in the first chunk, so changes are not visible.
+        assert_eq!(iter.count(), 9);
+    }
+}
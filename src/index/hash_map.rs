@@ -0,0 +1,189 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt::{self, Debug, Formatter};
+use std::hash::{BuildHasher, Hash, RandomState};
+
+use parking_lot::RwLock;
+
+/// Count of the shards of hash index. Must be power of two.
+const SHARD_COUNT: usize = 64;
+
+/// Concurrent hash map which is split into shards guarded by separate locks,
+/// so operations on different keys rarely wait for each other. Used as unique
+/// index for equality-only lookups.
+pub struct IndexHashMap<K, V> {
+    shards: Box<[RwLock<HashMap<K, V>>]>,
+    hasher: RandomState,
+}
+
+/// Concurrent hash map which stores many values for same key. Values of the
+/// key are stored in a set, so insert and remove don't depend on the count of
+/// values. Used as non-unique index for equality-only lookups.
+pub struct IndexHashMultiMap<K, V> {
+    inner: IndexHashMap<K, HashSet<V>>,
+}
+
+impl<K, V> Default for IndexHashMap<K, V> {
+    fn default() -> Self {
+        Self {
+            shards: (0..SHARD_COUNT).map(|_| RwLock::default()).collect(),
+            hasher: RandomState::new(),
+        }
+    }
+}
+
+impl<K, V> Default for IndexHashMultiMap<K, V> {
+    fn default() -> Self {
+        Self {
+            inner: IndexHashMap::default(),
+        }
+    }
+}
+
+impl<K: Debug, V: Debug> Debug for IndexHashMap<K, V> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let mut map = f.debug_map();
+        for shard in self.shards.iter() {
+            map.entries(shard.read().iter());
+        }
+        map.finish()
+    }
+}
+
+impl<K: Debug, V: Debug> Debug for IndexHashMultiMap<K, V> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.inner.fmt(f)
+    }
+}
+
+impl<K, V> IndexHashMap<K, V>
+where
+    K: Eq + Hash,
+{
+    fn shard(&self, key: &K) -> &RwLock<HashMap<K, V>> {
+        let hash = self.hasher.hash_one(key) as usize;
+        &self.shards[hash & (SHARD_COUNT - 1)]
+    }
+
+    /// Inserts value for the key. Returns old value if key was present.
+    pub fn insert(&self, key: K, value: V) -> Option<V> {
+        self.shard(&key).write().insert(key, value)
+    }
+
+    /// Inserts value only if key is not present. Returns `None` if key is
+    /// already present.
+    pub fn checked_insert(&self, key: K, value: V) -> Option<()> {
+        let mut shard = self.shard(&key).write();
+        if shard.contains_key(&key) {
+            return None;
+        }
+        shard.insert(key, value);
+        Some(())
+    }
+
+    pub fn get(&self, key: &K) -> Option<V>
+    where
+        V: Clone,
+    {
+        self.shard(key).read().get(key).cloned()
+    }
+
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.shard(key).read().contains_key(key)
+    }
+
+    pub fn remove(&self, key: &K) -> Option<(K, V)> {
+        self.shard(key).write().remove_entry(key)
+    }
+
+    pub fn len(&self) -> usize {
+        self.shards.iter().map(|s| s.read().len()).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.shards.iter().all(|s| s.read().is_empty())
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.shards.iter().map(|s| s.read().capacity()).sum()
+    }
+
+    pub fn shard_count(&self) -> usize {
+        self.shards.len()
+    }
+
+    pub(crate) fn shards(&self) -> impl Iterator<Item = &RwLock<HashMap<K, V>>> {
+        self.shards.iter()
+    }
+}
+
+impl<K, V> IndexHashMultiMap<K, V>
+where
+    K: Eq + Hash,
+    V: Eq + Hash,
+{
+    /// Inserts value for the key. Returns value back if same key-value pair is
+    /// already present.
+    pub fn insert(&self, key: K, value: V) -> Option<V> {
+        let mut shard = self.inner.shard(&key).write();
+        let values = shard.entry(key).or_default();
+        if values.contains(&value) {
+            return Some(value);
+        }
+        values.insert(value);
+        None
+    }
+
+    /// Returns all values of the key.
+    pub fn get(&self, key: &K) -> Vec<V>
+    where
+        V: Clone,
+    {
+        self.inner
+            .shard(key)
+            .read()
+            .get(key)
+            .map(|values| values.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.inner.contains_key(key)
+    }
+
+    pub fn remove(&self, key: &K, value: &V) -> Option<(K, V)>
+    where
+        K: Clone,
+    {
+        let mut shard = self.inner.shard(key).write();
+        let values = shard.get_mut(key)?;
+        let value = values.take(value)?;
+        if values.is_empty() {
+            shard.remove(key);
+        }
+        Some((key.clone(), value))
+    }
+
+    /// Returns count of the stored key-value pairs.
+    pub fn len(&self) -> usize {
+        self.inner
+            .shards()
+            .map(|s| s.read().values().map(HashSet::len).sum::<usize>())
+            .sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.inner.capacity()
+    }
+
+    pub fn shard_count(&self) -> usize {
+        self.inner.shard_count()
+    }
+
+    pub(crate) fn shards(&self) -> impl Iterator<Item = &RwLock<HashMap<K, HashSet<V>>>> {
+        self.inner.shards()
+    }
+}
@@ -1,13 +1,21 @@
 mod available_index;
+mod bitmap;
+mod chunked_iter;
+mod hash_map;
 mod multipair;
+mod spatial;
 mod table_index;
 mod table_secondary_index;
 mod unsized_node;
 
 pub use available_index::AvailableIndex;
+pub use bitmap::{IndexBitmap, RowBitmap, RowIds};
+pub use chunked_iter::{ChunkedIter, INDEX_CHUNK_SIZE};
+pub use hash_map::{IndexHashMap, IndexHashMultiMap};
 pub use indexset::concurrent::map::BTreeMap as IndexMap;
 pub use indexset::concurrent::multimap::BTreeMultiMap as IndexMultiMap;
 pub use multipair::MultiPairRecreate;
+pub use spatial::IndexSpatial;
 pub use table_index::{TableIndex, TableIndexCdc};
 pub use table_secondary_index::{
     IndexError, TableSecondaryIndex, TableSecondaryIndexCdc, TableSecondaryIndexEventsOps,
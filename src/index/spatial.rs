@@ -0,0 +1,478 @@
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use std::fmt::{self, Debug, Formatter};
+
+use data_bucket::Link;
+use parking_lot::RwLock;
+
+/// Max count of the entries in one node of [`IndexSpatial`]. Node is split in
+/// halves when it has more.
+const MAX_ENTRIES: usize = 16;
+
+type Point = [f64; 2];
+
+/// Bounding box of the node entries.
+#[derive(Clone, Copy, Debug)]
+struct Rect {
+    min: Point,
+    max: Point,
+}
+
+impl Rect {
+    fn of_point(point: Point) -> Self {
+        Self {
+            min: point,
+            max: point,
+        }
+    }
+
+    fn union(self, other: Rect) -> Rect {
+        Rect {
+            min: [self.min[0].min(other.min[0]), self.min[1].min(other.min[1])],
+            max: [self.max[0].max(other.max[0]), self.max[1].max(other.max[1])],
+        }
+    }
+
+    fn area(&self) -> f64 {
+        (self.max[0] - self.min[0]) * (self.max[1] - self.min[1])
+    }
+
+    fn enlargement(&self, other: &Rect) -> f64 {
+        self.union(*other).area() - self.area()
+    }
+
+    fn center(&self, axis: usize) -> f64 {
+        (self.min[axis] + self.max[axis]) / 2.0
+    }
+
+    fn contains(&self, point: Point) -> bool {
+        (0..2).all(|axis| self.min[axis] <= point[axis] && point[axis] <= self.max[axis])
+    }
+
+    fn intersects(&self, other: &Rect) -> bool {
+        (0..2).all(|axis| self.min[axis] <= other.max[axis] && other.min[axis] <= self.max[axis])
+    }
+
+    /// Returns squared distance from point to the nearest point of the box.
+    fn distance2(&self, point: Point) -> f64 {
+        (0..2)
+            .map(|axis| {
+                let d = (self.min[axis] - point[axis])
+                    .max(point[axis] - self.max[axis])
+                    .max(0.0);
+                d * d
+            })
+            .sum()
+    }
+}
+
+enum Node {
+    Leaf(Vec<(Point, Link)>),
+    Inner(Vec<(Rect, Node)>),
+}
+
+impl Node {
+    fn is_empty(&self) -> bool {
+        match self {
+            Node::Leaf(entries) => entries.is_empty(),
+            Node::Inner(children) => children.is_empty(),
+        }
+    }
+
+    /// Returns bounding box of the node. Node must be not empty.
+    fn rect(&self) -> Rect {
+        match self {
+            Node::Leaf(entries) => entries
+                .iter()
+                .map(|(p, _)| Rect::of_point(*p))
+                .reduce(Rect::union),
+            Node::Inner(children) => children.iter().map(|(r, _)| *r).reduce(Rect::union),
+        }
+        .expect("node is not empty")
+    }
+
+    /// Inserts entry into the node. Returns new sibling node if node was
+    /// split.
+    fn insert(&mut self, point: Point, link: Link) -> Option<Node> {
+        match self {
+            Node::Leaf(entries) => {
+                entries.push((point, link));
+                (entries.len() > MAX_ENTRIES)
+                    .then(|| Node::Leaf(split_half(entries, |(p, _)| Rect::of_point(*p))))
+            }
+            Node::Inner(children) => {
+                let target = Rect::of_point(point);
+                let pos = (0..children.len())
+                    .min_by(|&a, &b| {
+                        let (a, b) = (&children[a].0, &children[b].0);
+                        a.enlargement(&target)
+                            .total_cmp(&b.enlargement(&target))
+                            .then(a.area().total_cmp(&b.area()))
+                    })
+                    .expect("inner node is not empty");
+                children[pos].0 = children[pos].0.union(target);
+                if let Some(sibling) = children[pos].1.insert(point, link) {
+                    children[pos].0 = children[pos].1.rect();
+                    children.push((sibling.rect(), sibling));
+                }
+                (children.len() > MAX_ENTRIES)
+                    .then(|| Node::Inner(split_half(children, |(r, _)| *r)))
+            }
+        }
+    }
+
+    /// Removes entry from the node. Empty child nodes are dropped and boxes
+    /// on the path are shrunk.
+    fn remove(&mut self, point: Point, link: &Link) -> bool {
+        match self {
+            Node::Leaf(entries) => {
+                let Some(pos) = entries.iter().position(|(p, l)| *p == point && l == link) else {
+                    return false;
+                };
+                entries.swap_remove(pos);
+                true
+            }
+            Node::Inner(children) => {
+                for pos in 0..children.len() {
+                    if !children[pos].0.contains(point) || !children[pos].1.remove(point, link) {
+                        continue;
+                    }
+                    if children[pos].1.is_empty() {
+                        children.swap_remove(pos);
+                    } else {
+                        children[pos].0 = children[pos].1.rect();
+                    }
+                    return true;
+                }
+                false
+            }
+        }
+    }
+
+    fn contains(&self, point: Point, link: &Link) -> bool {
+        match self {
+            Node::Leaf(entries) => entries.iter().any(|(p, l)| *p == point && l == link),
+            Node::Inner(children) => children
+                .iter()
+                .any(|(r, n)| r.contains(point) && n.contains(point, link)),
+        }
+    }
+
+    fn within(&self, rect: &Rect, links: &mut Vec<Link>) {
+        match self {
+            Node::Leaf(entries) => links.extend(
+                entries
+                    .iter()
+                    .filter(|(p, _)| rect.contains(*p))
+                    .map(|(_, l)| *l),
+            ),
+            Node::Inner(children) => {
+                for (r, n) in children {
+                    if r.intersects(rect) {
+                        n.within(rect, links)
+                    }
+                }
+            }
+        }
+    }
+
+    fn node_count(&self) -> usize {
+        match self {
+            Node::Leaf(_) => 1,
+            Node::Inner(children) => {
+                1 + children.iter().map(|(_, n)| n.node_count()).sum::<usize>()
+            }
+        }
+    }
+
+    fn heap_size(&self) -> usize {
+        match self {
+            Node::Leaf(entries) => entries.capacity() * size_of::<(Point, Link)>(),
+            Node::Inner(children) => {
+                children.capacity() * size_of::<(Rect, Node)>()
+                    + children.iter().map(|(_, n)| n.heap_size()).sum::<usize>()
+            }
+        }
+    }
+
+    fn used_size(&self) -> usize {
+        match self {
+            Node::Leaf(entries) => entries.len() * size_of::<(Point, Link)>(),
+            Node::Inner(children) => {
+                children.len() * size_of::<(Rect, Node)>()
+                    + children.iter().map(|(_, n)| n.used_size()).sum::<usize>()
+            }
+        }
+    }
+}
+
+/// Sorts entries along the axis where their centers spread most and splits
+/// off the second half.
+fn split_half<T>(entries: &mut Vec<T>, rect_of: impl Fn(&T) -> Rect) -> Vec<T> {
+    let bounds = entries
+        .iter()
+        .map(&rect_of)
+        .reduce(Rect::union)
+        .expect("split node is not empty");
+    let axis = if bounds.max[0] - bounds.min[0] >= bounds.max[1] - bounds.min[1] {
+        0
+    } else {
+        1
+    };
+    entries.sort_by(|a, b| rect_of(a).center(axis).total_cmp(&rect_of(b).center(axis)));
+    entries.split_off(entries.len() / 2)
+}
+
+/// Node or row which is waiting to be visited by nearest search.
+struct Candidate<'a> {
+    distance2: f64,
+    entry: CandidateEntry<'a>,
+}
+
+enum CandidateEntry<'a> {
+    Node(&'a Node),
+    Row(Link),
+}
+
+impl PartialEq for Candidate<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Candidate<'_> {}
+
+impl PartialOrd for Candidate<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Reversed, so [`BinaryHeap`] pops the nearest candidate first.
+impl Ord for Candidate<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.distance2.total_cmp(&self.distance2)
+    }
+}
+
+struct SpatialInner {
+    root: Node,
+    len: usize,
+}
+
+/// R-tree over 2D points. It's used as index on pair of float columns for
+/// bounding box and nearest rows lookups. Distance is euclidean over column
+/// values. Points with `NaN` coordinate can't be found, so they are not
+/// indexed.
+pub struct IndexSpatial {
+    inner: RwLock<SpatialInner>,
+}
+
+impl Default for IndexSpatial {
+    fn default() -> Self {
+        Self {
+            inner: RwLock::new(SpatialInner {
+                root: Node::Leaf(vec![]),
+                len: 0,
+            }),
+        }
+    }
+}
+
+impl Debug for IndexSpatial {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let inner = self.inner.read();
+        f.debug_struct("IndexSpatial")
+            .field("len", &inner.len)
+            .field("node_count", &inner.root.node_count())
+            .finish()
+    }
+}
+
+impl IndexSpatial {
+    /// Inserts link for the point. Returns link back if it's already present.
+    pub fn insert(&self, point: (f64, f64), link: Link) -> Option<Link> {
+        let point = [point.0, point.1];
+        if point.iter().any(|c| c.is_nan()) {
+            return None;
+        }
+        let mut inner = self.inner.write();
+        if inner.root.contains(point, &link) {
+            return Some(link);
+        }
+        if let Some(sibling) = inner.root.insert(point, link) {
+            let root = std::mem::replace(&mut inner.root, Node::Leaf(vec![]));
+            inner.root = Node::Inner(vec![(root.rect(), root), (sibling.rect(), sibling)]);
+        }
+        inner.len += 1;
+        None
+    }
+
+    pub fn remove(&self, point: &(f64, f64), link: &Link) -> Option<Link> {
+        let mut inner = self.inner.write();
+        if !inner.root.remove([point.0, point.1], link) {
+            return None;
+        }
+        inner.len -= 1;
+        // Root with single child is replaced by it, so tree doesn't stay
+        // higher than needed after removals.
+        loop {
+            match &mut inner.root {
+                Node::Inner(children) if children.len() <= 1 => {
+                    inner.root = children.pop().map(|(_, n)| n).unwrap_or(Node::Leaf(vec![]));
+                }
+                _ => break,
+            }
+        }
+        Some(*link)
+    }
+
+    /// Returns links of the rows which points are inside the box, bounds
+    /// included.
+    pub fn within(&self, min: (f64, f64), max: (f64, f64)) -> Vec<Link> {
+        let rect = Rect {
+            min: [min.0, min.1],
+            max: [max.0, max.1],
+        };
+        let mut links = vec![];
+        let inner = self.inner.read();
+        if !inner.root.is_empty() {
+            inner.root.within(&rect, &mut links);
+        }
+        links
+    }
+
+    /// Returns links of the `k` rows which points are nearest to `point`,
+    /// ordered by distance.
+    pub fn nearest(&self, point: (f64, f64), k: usize) -> Vec<Link> {
+        let point = [point.0, point.1];
+        let inner = self.inner.read();
+        let mut heap = BinaryHeap::new();
+        heap.push(Candidate {
+            distance2: 0.0,
+            entry: CandidateEntry::Node(&inner.root),
+        });
+        let mut links = vec![];
+        while links.len() < k {
+            let Some(candidate) = heap.pop() else {
+                break;
+            };
+            match candidate.entry {
+                CandidateEntry::Row(link) => links.push(link),
+                CandidateEntry::Node(Node::Leaf(entries)) => {
+                    heap.extend(entries.iter().map(|(p, l)| Candidate {
+                        distance2: Rect::of_point(*p).distance2(point),
+                        entry: CandidateEntry::Row(*l),
+                    }))
+                }
+                CandidateEntry::Node(Node::Inner(children)) => {
+                    heap.extend(children.iter().map(|(r, n)| Candidate {
+                        distance2: r.distance2(point),
+                        entry: CandidateEntry::Node(n),
+                    }))
+                }
+            }
+        }
+        links
+    }
+
+    /// Returns count of the indexed rows.
+    pub fn len(&self) -> usize {
+        self.inner.read().len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.len()
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.inner.read().root.node_count()
+    }
+
+    pub(crate) fn heap_size(&self) -> usize {
+        self.inner.read().root.heap_size()
+    }
+
+    pub(crate) fn used_size(&self) -> usize {
+        self.inner.read().root.used_size()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use data_bucket::Link;
+
+    use super::IndexSpatial;
+
+    fn link(offset: u32) -> Link {
+        Link {
+            page_id: 1.into(),
+            offset,
+            length: 8,
+        }
+    }
+
+    fn grid() -> IndexSpatial {
+        let index = IndexSpatial::default();
+        for x in 0..20 {
+            for y in 0..20 {
+                assert!(
+                    index
+                        .insert((x as f64, y as f64), link(x * 20 + y))
+                        .is_none()
+                );
+            }
+        }
+        index
+    }
+
+    #[test]
+    fn spatial_within() {
+        let index = grid();
+        assert_eq!(index.len(), 400);
+        assert!(index.node_count() > 1);
+
+        let mut links = index.within((2.0, 3.0), (4.0, 4.0));
+        links.sort();
+        let mut expected: Vec<_> = [2, 3, 4]
+            .iter()
+            .flat_map(|x| [3, 4].map(|y| link(x * 20 + y)))
+            .collect();
+        expected.sort();
+        assert_eq!(links, expected);
+        assert!(index.within((20.5, 0.0), (30.0, 30.0)).is_empty());
+    }
+
+    #[test]
+    fn spatial_nearest() {
+        let index = grid();
+        let links = index.nearest((7.1, 7.2), 3);
+        assert_eq!(links[0], link(7 * 20 + 7));
+        let mut rest = links[1..].to_vec();
+        rest.sort();
+        assert_eq!(rest, vec![link(7 * 20 + 8), link(8 * 20 + 7)]);
+        assert_eq!(index.nearest((0.0, 0.0), 1000).len(), 400);
+    }
+
+    #[test]
+    fn spatial_remove() {
+        let index = grid();
+        assert!(index.insert((1.0, 1.0), link(21)).is_some());
+        for x in 0..20 {
+            for y in 0..20 {
+                if x != 5 || y != 5 {
+                    let l = link(x * 20 + y);
+                    assert_eq!(index.remove(&(x as f64, y as f64), &l), Some(l));
+                }
+            }
+        }
+        assert!(index.remove(&(1.0, 1.0), &link(21)).is_none());
+        assert_eq!(index.len(), 1);
+        assert_eq!(index.node_count(), 1);
+        assert_eq!(index.nearest((0.0, 0.0), 2), vec![link(105)]);
+    }
+}
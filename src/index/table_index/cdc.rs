@@ -7,7 +7,9 @@ use indexset::core::multipair::MultiPair;
 use indexset::core::node::NodeLike;
 use indexset::core::pair::Pair;
 
-use crate::{IndexMap, IndexMultiMap};
+use crate::{
+    IndexBitmap, IndexHashMap, IndexHashMultiMap, IndexMap, IndexMultiMap, IndexSpatial, TableIndex,
+};
 
 pub trait TableIndexCdc<T> {
     fn insert_cdc(&self, value: T, link: Link) -> (Option<Link>, Vec<ChangeEvent<Pair<T, Link>>>);
@@ -71,3 +73,99 @@ where
         self.remove_cdc(&value)
     }
 }
+
+/// Hash indexes are not persisted, but rebuilt from rows on load, so they
+/// produce no change events.
+impl<T> TableIndexCdc<T> for IndexHashMultiMap<T, Link>
+where
+    T: Eq + Hash + Clone,
+{
+    fn insert_cdc(&self, value: T, link: Link) -> (Option<Link>, Vec<ChangeEvent<Pair<T, Link>>>) {
+        (TableIndex::insert(self, value, link), vec![])
+    }
+
+    fn insert_checked_cdc(&self, value: T, link: Link) -> Option<Vec<ChangeEvent<Pair<T, Link>>>> {
+        TableIndex::insert_checked(self, value, link).map(|_| vec![])
+    }
+
+    fn remove_cdc(
+        &self,
+        value: T,
+        link: Link,
+    ) -> (Option<(T, Link)>, Vec<ChangeEvent<Pair<T, Link>>>) {
+        (TableIndex::remove(self, value, link), vec![])
+    }
+}
+
+impl<T> TableIndexCdc<T> for IndexHashMap<T, Link>
+where
+    T: Eq + Hash,
+{
+    fn insert_cdc(&self, value: T, link: Link) -> (Option<Link>, Vec<ChangeEvent<Pair<T, Link>>>) {
+        (TableIndex::insert(self, value, link), vec![])
+    }
+
+    fn insert_checked_cdc(&self, value: T, link: Link) -> Option<Vec<ChangeEvent<Pair<T, Link>>>> {
+        TableIndex::insert_checked(self, value, link).map(|_| vec![])
+    }
+
+    fn remove_cdc(
+        &self,
+        value: T,
+        link: Link,
+    ) -> (Option<(T, Link)>, Vec<ChangeEvent<Pair<T, Link>>>) {
+        (TableIndex::remove(self, value, link), vec![])
+    }
+}
+
+/// Bitmap index is rebuilt from rows on load too.
+impl<T> TableIndexCdc<T> for IndexBitmap<T>
+where
+    T: PartialEq,
+{
+    fn insert_cdc(&self, value: T, link: Link) -> (Option<Link>, Vec<ChangeEvent<Pair<T, Link>>>) {
+        (TableIndex::insert(self, value, link), vec![])
+    }
+
+    fn insert_checked_cdc(&self, value: T, link: Link) -> Option<Vec<ChangeEvent<Pair<T, Link>>>> {
+        TableIndex::insert_checked(self, value, link).map(|_| vec![])
+    }
+
+    fn remove_cdc(
+        &self,
+        value: T,
+        link: Link,
+    ) -> (Option<(T, Link)>, Vec<ChangeEvent<Pair<T, Link>>>) {
+        (TableIndex::remove(self, value, link), vec![])
+    }
+}
+
+/// Spatial index is rebuilt from rows on load too.
+impl TableIndexCdc<(f64, f64)> for IndexSpatial {
+    fn insert_cdc(
+        &self,
+        value: (f64, f64),
+        link: Link,
+    ) -> (Option<Link>, Vec<ChangeEvent<Pair<(f64, f64), Link>>>) {
+        (TableIndex::insert(self, value, link), vec![])
+    }
+
+    fn insert_checked_cdc(
+        &self,
+        value: (f64, f64),
+        link: Link,
+    ) -> Option<Vec<ChangeEvent<Pair<(f64, f64), Link>>>> {
+        TableIndex::insert_checked(self, value, link).map(|_| vec![])
+    }
+
+    fn remove_cdc(
+        &self,
+        value: (f64, f64),
+        link: Link,
+    ) -> (
+        Option<((f64, f64), Link)>,
+        Vec<ChangeEvent<Pair<(f64, f64), Link>>>,
+    ) {
+        (TableIndex::remove(self, value, link), vec![])
+    }
+}
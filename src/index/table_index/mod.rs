@@ -6,7 +6,7 @@ use indexset::core::multipair::MultiPair;
 use indexset::core::node::NodeLike;
 use indexset::core::pair::Pair;
 
-use crate::{IndexMap, IndexMultiMap};
+use crate::{IndexBitmap, IndexHashMap, IndexHashMultiMap, IndexMap, IndexMultiMap, IndexSpatial};
 
 mod cdc;
 
@@ -57,3 +57,80 @@ where
         self.remove(&value)
     }
 }
+
+impl<T> TableIndex<T> for IndexHashMultiMap<T, Link>
+where
+    T: Eq + Hash + Clone,
+{
+    fn insert(&self, value: T, link: Link) -> Option<Link> {
+        self.insert(value, link)
+    }
+
+    fn insert_checked(&self, value: T, link: Link) -> Option<()> {
+        if self.insert(value, link).is_some() {
+            None
+        } else {
+            Some(())
+        }
+    }
+
+    fn remove(&self, value: T, link: Link) -> Option<(T, Link)> {
+        self.remove(&value, &link)
+    }
+}
+
+impl<T> TableIndex<T> for IndexHashMap<T, Link>
+where
+    T: Eq + Hash,
+{
+    fn insert(&self, value: T, link: Link) -> Option<Link> {
+        self.insert(value, link)
+    }
+
+    fn insert_checked(&self, value: T, link: Link) -> Option<()> {
+        self.checked_insert(value, link)
+    }
+
+    fn remove(&self, value: T, _: Link) -> Option<(T, Link)> {
+        self.remove(&value)
+    }
+}
+
+impl<T> TableIndex<T> for IndexBitmap<T>
+where
+    T: PartialEq,
+{
+    fn insert(&self, value: T, link: Link) -> Option<Link> {
+        self.insert(value, link)
+    }
+
+    fn insert_checked(&self, value: T, link: Link) -> Option<()> {
+        if self.insert(value, link).is_some() {
+            None
+        } else {
+            Some(())
+        }
+    }
+
+    fn remove(&self, value: T, link: Link) -> Option<(T, Link)> {
+        self.remove(&value, &link).map(|link| (value, link))
+    }
+}
+
+impl TableIndex<(f64, f64)> for IndexSpatial {
+    fn insert(&self, value: (f64, f64), link: Link) -> Option<Link> {
+        self.insert(value, link)
+    }
+
+    fn insert_checked(&self, value: (f64, f64), link: Link) -> Option<()> {
+        if self.insert(value, link).is_some() {
+            None
+        } else {
+            Some(())
+        }
+    }
+
+    fn remove(&self, value: (f64, f64), link: Link) -> Option<((f64, f64), Link)> {
+        self.remove(&value, &link).map(|link| (value, link))
+    }
+}
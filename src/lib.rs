@@ -33,12 +33,19 @@ pub mod prelude {
         map_unsized_index_pages_to_toc_and_general, validate_events,
     };
     pub use crate::primary_key::{PrimaryKeyGenerator, PrimaryKeyGeneratorState, TablePrimaryKey};
-    pub use crate::table::select::{Order, QueryParams, SelectQueryBuilder, SelectQueryExecutor};
+    pub use crate::table::select::{
+        AccessPath, ArchivedCheck, ArchivedFilter, ArchivedRows, CmpOp, HashJoin, JoinRight, Order,
+        PageCursor, QueryParams, QueryPlan, SelectAggregateExecutor, SelectGroupBuilder,
+        SelectGroupExecutor, SelectJoinExecutor, SelectPage, SelectPageExecutor,
+        SelectQueryBuilder, SelectQueryExecutor, SelectStreamExecutor, SortStrategy, TableJoin,
+        TextQuery, TextQueryColumn, TextQueryResult, TopK, TotalCmp, TotalOrd,
+    };
     pub use crate::table::system_info::{IndexInfo, IndexKind, SystemInfo};
-    pub use crate::util::{OrderedF32Def, OrderedF64Def};
+    pub use crate::util::{ArchivedSize, OrderedF32Def, OrderedF64Def};
     pub use crate::{
-        AvailableIndex, Difference, IndexError, IndexMap, IndexMultiMap, MultiPairRecreate,
-        TableIndex, TableIndexCdc, TableRow, TableSecondaryIndex, TableSecondaryIndexCdc,
+        AvailableIndex, ChunkedIter, Difference, IndexBitmap, IndexError, IndexHashMap,
+        IndexHashMultiMap, IndexMap, IndexMultiMap, IndexSpatial, MultiPairRecreate, RowBitmap,
+        RowIds, TableIndex, TableIndexCdc, TableRow, TableSecondaryIndex, TableSecondaryIndexCdc,
         TableSecondaryIndexEventsOps, TableSecondaryIndexInfo, UnsizedNode, WorkTable,
         WorkTableError,
     };
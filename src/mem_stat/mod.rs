@@ -1,6 +1,6 @@
 mod primitives;
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fmt::Debug;
 use std::rc::Rc;
 use std::sync::Arc;
@@ -16,7 +16,10 @@ use uuid::Uuid;
 use crate::IndexMultiMap;
 use crate::persistence::OperationType;
 use crate::prelude::OperationId;
-use crate::{IndexMap, impl_memstat_zero};
+use crate::{
+    IndexBitmap, IndexHashMap, IndexHashMultiMap, IndexMap, IndexSpatial, RowBitmap,
+    impl_memstat_zero,
+};
 
 pub trait MemStat {
     fn heap_size(&self) -> usize;
@@ -42,6 +45,15 @@ impl<T: MemStat> MemStat for Vec<T> {
     }
 }
 
+impl<T: MemStat, const N: usize> MemStat for [T; N] {
+    fn heap_size(&self) -> usize {
+        self.iter().map(|v| v.heap_size()).sum()
+    }
+    fn used_size(&self) -> usize {
+        self.iter().map(|v| v.used_size()).sum()
+    }
+}
+
 impl MemStat for String {
     fn heap_size(&self) -> usize {
         self.capacity()
@@ -113,6 +125,77 @@ where
     }
 }
 
+impl<K, V> MemStat for IndexHashMap<K, V>
+where
+    K: MemStat + Eq + std::hash::Hash,
+    V: MemStat,
+{
+    fn heap_size(&self) -> usize {
+        self.shards().map(|s| s.read().heap_size()).sum()
+    }
+
+    fn used_size(&self) -> usize {
+        self.shards().map(|s| s.read().used_size()).sum()
+    }
+}
+
+impl<K, V> MemStat for IndexHashMultiMap<K, V>
+where
+    K: MemStat + Eq + std::hash::Hash,
+    V: MemStat + Eq + std::hash::Hash,
+{
+    fn heap_size(&self) -> usize {
+        self.shards().map(|s| s.read().heap_size()).sum()
+    }
+
+    fn used_size(&self) -> usize {
+        self.shards().map(|s| s.read().used_size()).sum()
+    }
+}
+
+impl MemStat for RowBitmap {
+    fn heap_size(&self) -> usize {
+        self.word_count() * 2 * std::mem::size_of::<u64>()
+    }
+
+    fn used_size(&self) -> usize {
+        self.heap_size()
+    }
+}
+
+impl MemStat for IndexSpatial {
+    fn heap_size(&self) -> usize {
+        self.heap_size()
+    }
+
+    fn used_size(&self) -> usize {
+        self.used_size()
+    }
+}
+
+impl<K> MemStat for IndexBitmap<K>
+where
+    K: MemStat + PartialEq,
+{
+    fn heap_size(&self) -> usize {
+        self.with_values(|values| {
+            values
+                .iter()
+                .map(|(k, b)| std::mem::size_of::<(K, RowBitmap)>() + k.heap_size() + b.heap_size())
+                .sum::<usize>()
+        })
+    }
+
+    fn used_size(&self) -> usize {
+        self.with_values(|values| {
+            values
+                .iter()
+                .map(|(k, b)| std::mem::size_of::<(K, RowBitmap)>() + k.used_size() + b.used_size())
+                .sum::<usize>()
+        })
+    }
+}
+
 impl<T: MemStat> MemStat for Box<T> {
     fn heap_size(&self) -> usize {
         std::mem::size_of::<T>() + (**self).heap_size()
@@ -140,6 +223,15 @@ impl<T: MemStat> MemStat for Rc<T> {
     }
 }
 
+impl<T: MemStat + Eq + std::hash::Hash> MemStat for HashSet<T> {
+    fn heap_size(&self) -> usize {
+        self.capacity() * size_of::<T>() + self.iter().map(|v| v.heap_size()).sum::<usize>()
+    }
+    fn used_size(&self) -> usize {
+        self.len() * size_of::<T>() + self.iter().map(|v| v.used_size()).sum::<usize>()
+    }
+}
+
 impl<K: MemStat + Eq + std::hash::Hash, V: MemStat> MemStat for HashMap<K, V> {
     fn heap_size(&self) -> usize {
         let bucket_size = size_of::<(K, V)>();
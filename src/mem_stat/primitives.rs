@@ -43,7 +43,13 @@ impl_memstat_zero!(
     std::num::NonZeroIsize,
     std::time::Duration,
     std::time::SystemTime,
-    std::time::Instant
+    std::time::Instant,
+    std::net::Ipv4Addr,
+    std::net::Ipv6Addr,
+    std::net::IpAddr,
+    std::net::SocketAddrV4,
+    std::net::SocketAddrV6,
+    std::net::SocketAddr
 );
 
 impl_memstat_zero!(
@@ -100,8 +100,17 @@ where
     pub fn push(
         &mut self,
         value: Operation<PrimaryKeyGenState, PrimaryKey, SecondaryKeys>,
-    ) -> eyre::Result<()> {
+    ) -> eyre::Result<()>
+    where
+        SecondaryKeys: TableSecondaryIndexEventsOps<AvailableIndexes>,
+    {
         let link = value.link();
+        if let Operation::Update(update) = &value
+            && update.secondary_keys_events.is_empty()
+            && self.coalesce_update(update)?
+        {
+            return Ok(());
+        }
         let mut row = QueueInnerRow {
             id: self.queue_inner_wt.get_next_pk().into(),
             operation_id: value.operation_id(),
@@ -115,10 +124,41 @@ where
         Ok(())
     }
 
+    /// Merges `Update` operation without index changes into the last queued
+    /// `Update` of the same [`Link`], as row bytes of newer operation replace
+    /// older ones. So frequent updates of one row (like in place counters)
+    /// don't flood the queue. Returns `false` if operation must be queued.
+    fn coalesce_update(&mut self, update: &UpdateOperation<SecondaryKeys>) -> eyre::Result<bool> {
+        let Some(last) = self
+            .queue_inner_wt
+            .select_by_link(update.link)
+            .execute()?
+            .into_iter()
+            .max_by_key(|r| r.operation_id)
+        else {
+            return Ok(false);
+        };
+        // Operations that failed batch validation are pushed back, so older
+        // operation must never override newer bytes.
+        if last.operation_id >= update.id {
+            return Ok(false);
+        }
+        match self.operations.get_mut(last.pos) {
+            Some(Operation::Update(queued)) => {
+                queued.bytes.clone_from(&update.bytes);
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+
     pub fn extend_from_iter(
         &mut self,
         i: impl Iterator<Item = Operation<PrimaryKeyGenState, PrimaryKey, SecondaryKeys>>,
-    ) -> eyre::Result<()> {
+    ) -> eyre::Result<()>
+    where
+        SecondaryKeys: TableSecondaryIndexEventsOps<AvailableIndexes>,
+    {
         for op in i {
             self.push(op)?
         }
@@ -324,13 +324,43 @@ where
         SecondaryIndexes: TableSecondaryIndex<Row, AvailableTypes, AvailableIndexes>,
         LockType: 'static,
     {
-        let pk = row_new.get_primary_key().clone();
-        if pk != row_old.get_primary_key() {
+        if row_new.get_primary_key() != row_old.get_primary_key() {
             return Err(WorkTableError::PrimaryUpdateTry);
         }
+        self.reinsert_with_primary_key(row_old, row_new)
+    }
+
+    /// Reinserts row same as [`WorkTable::reinsert`], but `PrimaryKey` of the
+    /// new row can differ from the old one. New key is added before indexes
+    /// are updated and old key is removed after, so row is available by one
+    /// of the keys all the time. Both keys must be locked by caller.
+    pub fn reinsert_with_primary_key(
+        &self,
+        row_old: Row,
+        row_new: Row,
+    ) -> Result<PrimaryKey, WorkTableError>
+    where
+        Row: Archive
+            + Clone
+            + for<'a> Serialize<
+                Strategy<Serializer<AlignedVec, ArenaHandle<'a>, Share>, rkyv::rancor::Error>,
+            >,
+        <Row as StorableRow>::WrappedRow: Archive
+            + for<'a> Serialize<
+                Strategy<Serializer<AlignedVec, ArenaHandle<'a>, Share>, rkyv::rancor::Error>,
+            >,
+        <<Row as StorableRow>::WrappedRow as Archive>::Archived: GhostWrapper,
+        PrimaryKey: Clone,
+        AvailableTypes: 'static,
+        AvailableIndexes: Debug + AvailableIndex,
+        SecondaryIndexes: TableSecondaryIndex<Row, AvailableTypes, AvailableIndexes>,
+        LockType: 'static,
+    {
+        let pk = row_new.get_primary_key().clone();
+        let old_pk = row_old.get_primary_key();
         let old_link = self
             .pk_map
-            .get(&pk)
+            .get(&old_pk)
             .map(|v| v.get().value)
             .ok_or(WorkTableError::NotFound)?;
         let new_link = self
@@ -342,7 +372,15 @@ where
                 .with_mut_ref(new_link, |r| r.unghost())
                 .map_err(WorkTableError::PagesError)?
         }
-        self.pk_map.insert(pk.clone(), new_link);
+        let is_pk_moved = pk != old_pk;
+        if !is_pk_moved {
+            self.pk_map.insert(pk.clone(), new_link);
+        } else if self.pk_map.checked_insert(pk.clone(), new_link).is_none() {
+            self.data
+                .delete(new_link)
+                .map_err(WorkTableError::PagesError)?;
+            return Err(WorkTableError::AlreadyExists("Primary".to_string()));
+        }
 
         let indexes_res = self
             .indexes
@@ -353,7 +391,11 @@ where
                     at,
                     inserted_already,
                 } => {
-                    self.pk_map.insert(pk.clone(), old_link);
+                    if is_pk_moved {
+                        self.pk_map.remove(&pk);
+                    } else {
+                        self.pk_map.insert(pk.clone(), old_link);
+                    }
                     self.indexes
                         .delete_from_indexes(row_new, new_link, inserted_already)?;
                     self.data
@@ -365,6 +407,9 @@ where
                 IndexError::NotFound => Err(WorkTableError::NotFound),
             };
         }
+        if is_pk_moved {
+            self.pk_map.remove(&old_pk);
+        }
         self.data
             .delete(old_link)
             .map_err(WorkTableError::PagesError)?;
@@ -400,13 +445,47 @@ where
         PkGen: PrimaryKeyGeneratorState,
         AvailableIndexes: Debug + AvailableIndex,
     {
-        let pk = row_new.get_primary_key().clone();
-        if pk != row_old.get_primary_key() {
+        if row_new.get_primary_key() != row_old.get_primary_key() {
             return Err(WorkTableError::PrimaryUpdateTry);
         }
+        self.reinsert_with_primary_key_cdc(row_old, row_new)
+    }
+
+    /// CDC version of [`WorkTable::reinsert_with_primary_key`].
+    #[allow(clippy::type_complexity)]
+    pub fn reinsert_with_primary_key_cdc<SecondaryEvents>(
+        &self,
+        row_old: Row,
+        row_new: Row,
+    ) -> Result<
+        (
+            PrimaryKey,
+            Operation<<PkGen as PrimaryKeyGeneratorState>::State, PrimaryKey, SecondaryEvents>,
+        ),
+        WorkTableError,
+    >
+    where
+        Row: Archive
+            + Clone
+            + for<'a> Serialize<
+                Strategy<Serializer<AlignedVec, ArenaHandle<'a>, Share>, rkyv::rancor::Error>,
+            >,
+        <Row as StorableRow>::WrappedRow: Archive
+            + for<'a> Serialize<
+                Strategy<Serializer<AlignedVec, ArenaHandle<'a>, Share>, rkyv::rancor::Error>,
+            >,
+        <<Row as StorableRow>::WrappedRow as Archive>::Archived: GhostWrapper,
+        PrimaryKey: Clone,
+        SecondaryIndexes: TableSecondaryIndex<Row, AvailableTypes, AvailableIndexes>
+            + TableSecondaryIndexCdc<Row, AvailableTypes, SecondaryEvents, AvailableIndexes>,
+        PkGen: PrimaryKeyGeneratorState,
+        AvailableIndexes: Debug + AvailableIndex,
+    {
+        let pk = row_new.get_primary_key().clone();
+        let old_pk = row_old.get_primary_key();
         let old_link = self
             .pk_map
-            .get(&pk)
+            .get(&old_pk)
             .map(|v| v.get().value)
             .ok_or(WorkTableError::NotFound)?;
         let (new_link, _) = self
@@ -418,7 +497,17 @@ where
                 .with_mut_ref(new_link, |r| r.unghost())
                 .map_err(WorkTableError::PagesError)?
         }
-        let (_, primary_key_events) = self.pk_map.insert_cdc(pk.clone(), new_link);
+        let is_pk_moved = pk != old_pk;
+        let mut primary_key_events = if !is_pk_moved {
+            self.pk_map.insert_cdc(pk.clone(), new_link).1
+        } else if let Some(events) = self.pk_map.checked_insert_cdc(pk.clone(), new_link) {
+            events
+        } else {
+            self.data
+                .delete(new_link)
+                .map_err(WorkTableError::PagesError)?;
+            return Err(WorkTableError::AlreadyExists("Primary".to_string()));
+        };
         let indexes_res =
             self.indexes
                 .reinsert_row_cdc(row_old, old_link, row_new.clone(), new_link);
@@ -428,7 +517,11 @@ where
                     at,
                     inserted_already,
                 } => {
-                    self.pk_map.insert(pk.clone(), old_link);
+                    if is_pk_moved {
+                        self.pk_map.remove(&pk);
+                    } else {
+                        self.pk_map.insert(pk.clone(), old_link);
+                    }
                     self.indexes
                         .delete_from_indexes(row_new, new_link, inserted_already)?;
                     self.data
@@ -440,6 +533,12 @@ where
                 IndexError::NotFound => Err(WorkTableError::NotFound),
             };
         }
+        // Moved row is persisted as insert of the new key with removal of the
+        // old one, so operation describes the whole move.
+        if is_pk_moved {
+            let (_, events) = self.pk_map.remove_cdc(&old_pk);
+            primary_key_events.extend(events);
+        }
 
         self.data
             .delete(old_link)
@@ -470,5 +569,20 @@ pub enum WorkTableError {
     SerializeError,
     SecondaryIndexError,
     PrimaryUpdateTry,
+    #[display("Column `{}` is not numeric", _0)]
+    #[from(skip)]
+    NotNumericColumn(#[error(not(source))] String),
+    #[display("Sum of column `{}` overflows its type", _0)]
+    #[from(skip)]
+    SumOverflow(#[error(not(source))] String),
+    #[display(
+        "Cursor is ordered by not indexed column `{}`, query's `after` must be used",
+        _0
+    )]
+    #[from(skip)]
+    NotIndexedCursor(#[error(not(source))] String),
+    #[display("Query parse error: {}", _0)]
+    #[from(skip)]
+    QueryParseError(#[error(not(source))] String),
     PagesError(in_memory::PagesExecutionError),
 }
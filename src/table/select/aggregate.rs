@@ -0,0 +1,50 @@
+use crate::WorkTableError;
+
+/// Builder returned by [`SelectAggregateExecutor::group_by`]. Aggregations
+/// called on it are computed separately for each distinct value of `column`.
+pub struct SelectGroupBuilder<Query, RowFields> {
+    pub query: Query,
+    pub column: RowFields,
+}
+
+/// Terminal aggregate operations for the [`SelectQueryBuilder`]. All query
+/// params (`range_on`, `order_on`, `offset`, `limit`) are applied before
+/// aggregation.
+///
+/// [`SelectQueryBuilder`]: crate::select::SelectQueryBuilder
+pub trait SelectAggregateExecutor<RowFields>
+where
+    Self: Sized,
+{
+    /// Column value type which is used for `sum`, `min` and `max` results.
+    type Value;
+
+    fn count(self) -> Result<usize, WorkTableError>;
+    fn sum(self, column: RowFields) -> Result<Self::Value, WorkTableError>;
+    fn min(self, column: RowFields) -> Result<Option<Self::Value>, WorkTableError>;
+    fn max(self, column: RowFields) -> Result<Option<Self::Value>, WorkTableError>;
+    fn avg(self, column: RowFields) -> Result<Option<f64>, WorkTableError>;
+
+    fn group_by(self, column: RowFields) -> SelectGroupBuilder<Self, RowFields> {
+        SelectGroupBuilder {
+            query: self,
+            column,
+        }
+    }
+}
+
+/// Same aggregate operations as [`SelectAggregateExecutor`] but computed per
+/// group. Groups are returned ordered by their key.
+#[allow(clippy::type_complexity)]
+pub trait SelectGroupExecutor<RowFields>
+where
+    Self: Sized,
+{
+    type Value;
+
+    fn count(self) -> Result<Vec<(Self::Value, usize)>, WorkTableError>;
+    fn sum(self, column: RowFields) -> Result<Vec<(Self::Value, Self::Value)>, WorkTableError>;
+    fn min(self, column: RowFields) -> Result<Vec<(Self::Value, Self::Value)>, WorkTableError>;
+    fn max(self, column: RowFields) -> Result<Vec<(Self::Value, Self::Value)>, WorkTableError>;
+    fn avg(self, column: RowFields) -> Result<Vec<(Self::Value, f64)>, WorkTableError>;
+}
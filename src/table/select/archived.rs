@@ -0,0 +1,86 @@
+use data_bucket::Link;
+
+/// Filter check passed to the table's row reader. It is `None` if no
+/// `where_archived` filters were added, so rows are read without archived
+/// check.
+pub type ArchivedCheck<'b, Archived> = Option<&'b dyn Fn(&Archived) -> bool>;
+
+/// Rows iterator of the table's select queries. Rows are read by `links`
+/// lazily with `read`, so `where_archived` filters are checked against
+/// archived rows and only rows which pass them are deserialized.
+#[allow(clippy::type_complexity)]
+pub struct ArchivedRows<'a, Links, Read, Archived> {
+    links: Links,
+    read: Read,
+    filters: Vec<Box<dyn Fn(&Archived) -> bool + 'a>>,
+}
+
+impl<Links, Read, Archived> ArchivedRows<'_, Links, Read, Archived> {
+    pub fn new<Row>(links: Links, read: Read) -> Self
+    where
+        Read: Fn(Link, ArchivedCheck<'_, Archived>) -> Option<Row>,
+    {
+        Self {
+            links,
+            read,
+            filters: vec![],
+        }
+    }
+
+    fn read_link<Row>(&self, link: Link) -> Option<Row>
+    where
+        Read: Fn(Link, ArchivedCheck<'_, Archived>) -> Option<Row>,
+    {
+        if self.filters.is_empty() {
+            (self.read)(link, None)
+        } else {
+            let check = |archived: &Archived| self.filters.iter().all(|f| f(archived));
+            (self.read)(link, Some(&check))
+        }
+    }
+}
+
+impl<Links, Read, Archived, Row> Iterator for ArchivedRows<'_, Links, Read, Archived>
+where
+    Links: Iterator<Item = Link>,
+    Read: Fn(Link, ArchivedCheck<'_, Archived>) -> Option<Row>,
+{
+    type Item = Row;
+
+    fn next(&mut self) -> Option<Row> {
+        while let Some(link) = self.links.next() {
+            if let Some(row) = self.read_link(link) {
+                return Some(row);
+            }
+        }
+        None
+    }
+}
+
+impl<Links, Read, Archived, Row> DoubleEndedIterator for ArchivedRows<'_, Links, Read, Archived>
+where
+    Links: DoubleEndedIterator<Item = Link>,
+    Read: Fn(Link, ArchivedCheck<'_, Archived>) -> Option<Row>,
+{
+    fn next_back(&mut self) -> Option<Row> {
+        while let Some(link) = self.links.next_back() {
+            if let Some(row) = self.read_link(link) {
+                return Some(row);
+            }
+        }
+        None
+    }
+}
+
+/// Rows iterator which supports filters on archived rows.
+pub trait ArchivedFilter<'a, Archived> {
+    fn push_archived_filter(&mut self, predicate: Box<dyn Fn(&Archived) -> bool + 'a>);
+}
+
+impl<'a, Links, Read, Archived> ArchivedFilter<'a, Archived>
+    for ArchivedRows<'a, Links, Read, Archived>
+{
+    fn push_archived_filter(&mut self, predicate: Box<dyn Fn(&Archived) -> bool + 'a>) {
+        self.filters.push(predicate);
+    }
+}
@@ -0,0 +1,82 @@
+use std::fmt;
+
+/// How query gets rows from the table.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub enum AccessPath {
+    /// Single row lookup in `pk_map`.
+    PrimaryKeyLookup,
+    /// Range scan of `pk_map`.
+    PrimaryKeyRange,
+    /// Exact lookup in secondary index with provided name.
+    IndexExact(&'static str),
+    /// Range scan of secondary index with provided name.
+    IndexRange(&'static str),
+    /// Intersection of bitmaps of the bitmap indexes with provided names.
+    BitmapIntersection(Vec<&'static str>),
+    /// Box or nearest lookup in spatial index with provided name.
+    Spatial(&'static str),
+    /// Scan of all rows in `pk_map` order.
+    #[default]
+    FullScan,
+    /// Brute-force scan of all rows using `iter_with_async`.
+    AsyncFullScan,
+}
+
+impl fmt::Display for AccessPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AccessPath::PrimaryKeyLookup => write!(f, "pk_map lookup"),
+            AccessPath::PrimaryKeyRange => write!(f, "pk_map range"),
+            AccessPath::IndexExact(index) => write!(f, "index `{index}` exact"),
+            AccessPath::IndexRange(index) => write!(f, "index `{index}` range"),
+            AccessPath::BitmapIntersection(indexes) => {
+                write!(f, "bitmap indexes ")?;
+                for (i, index) in indexes.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, " & ")?;
+                    }
+                    write!(f, "`{index}`")?;
+                }
+                Ok(())
+            }
+            AccessPath::Spatial(index) => write!(f, "spatial index `{index}`"),
+            AccessPath::FullScan => write!(f, "full scan"),
+            AccessPath::AsyncFullScan => write!(f, "full scan (iter_with_async)"),
+        }
+    }
+}
+
+/// How rows are sorted if `order_on` is set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortStrategy {
+    /// All rows are sorted.
+    Full,
+    /// Only `k` first rows are kept in bounded heap.
+    TopK(usize),
+}
+
+/// Result of the `explain` call. `estimated_rows` is count of rows that
+/// access path will read (it's `None` if unknown) and `actual_rows` is count
+/// of rows that query returns (or modifies for update/delete queries).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueryPlan {
+    pub access_path: AccessPath,
+    pub sort: Option<SortStrategy>,
+    pub estimated_rows: Option<usize>,
+    pub actual_rows: usize,
+}
+
+impl fmt::Display for QueryPlan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.access_path)?;
+        match self.sort {
+            Some(SortStrategy::Full) => write!(f, ", full sort")?,
+            Some(SortStrategy::TopK(k)) => write!(f, ", top-{k} heap")?,
+            None => {}
+        }
+        if let Some(estimated) = self.estimated_rows {
+            write!(f, ", estimated rows: {estimated}")?;
+        }
+        write!(f, ", actual rows: {}", self.actual_rows)
+    }
+}
@@ -0,0 +1,208 @@
+use std::cell::OnceCell;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use crate::WorkTableError;
+use crate::select::{SelectQueryBuilder, SelectQueryExecutor};
+
+/// Right side of the join. Returns right rows which match left row's key.
+pub trait JoinRight<Key> {
+    type Row;
+
+    fn matches(&self, key: &Key) -> Result<Vec<Self::Row>, WorkTableError>;
+}
+
+/// Right side of the join returned by table's generated `join_on_<column>`
+/// fns. Right rows are got by `lookup`, which is index (or primary key)
+/// lookup for indexed columns, so index nested-loop join is used, and hash
+/// map built once from all table rows for not indexed columns, so hash join
+/// is used.
+#[allow(clippy::type_complexity)]
+pub struct TableJoin<'a, Key, Row> {
+    lookup: Box<dyn Fn(&Key) -> Result<Vec<Row>, WorkTableError> + 'a>,
+    filters: Vec<Box<dyn Fn(&Row) -> bool + 'a>>,
+    limit: Option<usize>,
+}
+
+impl<'a, Key, Row> TableJoin<'a, Key, Row> {
+    pub fn new<F>(lookup: F) -> Self
+    where
+        F: Fn(&Key) -> Result<Vec<Row>, WorkTableError> + 'a,
+    {
+        Self {
+            lookup: Box::new(lookup),
+            filters: vec![],
+            limit: None,
+        }
+    }
+
+    /// Creates hash join side. `rows` are selected and grouped by `row_key`
+    /// on the first lookup only, then left keys are converted by `key` and
+    /// looked up in the built map.
+    pub fn hash<Hashed, R, RK, K>(rows: R, row_key: RK, key: K) -> Self
+    where
+        Hashed: Hash + Eq + 'a,
+        Row: Clone + 'a,
+        R: Fn() -> Result<Vec<Row>, WorkTableError> + 'a,
+        RK: Fn(&Row) -> Hashed + 'a,
+        K: Fn(&Key) -> Hashed + 'a,
+    {
+        let map: OnceCell<HashMap<Hashed, Vec<Row>>> = OnceCell::new();
+        Self::new(move |k| {
+            let map = match map.get() {
+                Some(map) => map,
+                None => {
+                    let mut grouped: HashMap<Hashed, Vec<Row>> = HashMap::new();
+                    for row in rows()? {
+                        grouped.entry(row_key(&row)).or_default().push(row);
+                    }
+                    map.get_or_init(|| grouped)
+                }
+            };
+            Ok(map.get(&key(k)).cloned().unwrap_or_default())
+        })
+    }
+
+    /// Filters right rows. Predicates of the several calls are combined, so
+    /// right row must match all of them.
+    pub fn where_by<F>(mut self, predicate: F) -> Self
+    where
+        F: Fn(&Row) -> bool + 'a,
+    {
+        self.filters.push(Box::new(predicate));
+        self
+    }
+
+    /// Limits count of the right rows joined to each left row.
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+}
+
+impl<Key, Row> JoinRight<Key> for TableJoin<'_, Key, Row> {
+    type Row = Row;
+
+    fn matches(&self, key: &Key) -> Result<Vec<Row>, WorkTableError> {
+        Ok((self.lookup)(key)?
+            .into_iter()
+            .filter(|r| self.filters.iter().all(|f| f(r)))
+            .take(self.limit.unwrap_or(usize::MAX))
+            .collect())
+    }
+}
+
+/// Hash join side. It is built from the right [`SelectQueryBuilder`] which is
+/// executed once, so all its params are applied. It can be used instead of
+/// [`TableJoin`] if right rows must be got by custom query.
+pub struct HashJoin<Key, Row> {
+    rows: HashMap<Key, Vec<Row>>,
+}
+
+impl<Key, Row> HashJoin<Key, Row>
+where
+    Key: Hash + Eq,
+{
+    pub fn from_query<I, ColumnRange, RowFields, F>(
+        query: SelectQueryBuilder<Row, I, ColumnRange, RowFields>,
+        key: F,
+    ) -> Result<Self, WorkTableError>
+    where
+        I: DoubleEndedIterator<Item = Row> + Sized,
+        SelectQueryBuilder<Row, I, ColumnRange, RowFields>:
+            SelectQueryExecutor<Row, I, ColumnRange, RowFields>,
+        F: Fn(&Row) -> Key,
+    {
+        let mut rows: HashMap<Key, Vec<Row>> = HashMap::new();
+        for row in query.execute()? {
+            rows.entry(key(&row)).or_default().push(row);
+        }
+        Ok(Self { rows })
+    }
+}
+
+impl<Key, Row> JoinRight<Key> for HashJoin<Key, Row>
+where
+    Key: Hash + Eq,
+    Row: Clone,
+{
+    type Row = Row;
+
+    fn matches(&self, key: &Key) -> Result<Vec<Row>, WorkTableError> {
+        Ok(self.rows.get(key).cloned().unwrap_or_default())
+    }
+}
+
+/// Joins for the [`SelectQueryBuilder`]. Left query is executed with all its
+/// params and then rows are joined with `right` by the `key` got from each
+/// left row. Right side errors are returned from the join itself.
+pub trait SelectJoinExecutor<Row>
+where
+    Self: Sized,
+{
+    fn join<Key, R, F>(
+        self,
+        right: R,
+        key: F,
+    ) -> Result<impl Iterator<Item = (Row, R::Row)>, WorkTableError>
+    where
+        R: JoinRight<Key>,
+        F: Fn(&Row) -> Key;
+
+    /// Same as `join`, but left rows without matches are returned with `None`.
+    fn left_join<Key, R, F>(
+        self,
+        right: R,
+        key: F,
+    ) -> Result<impl Iterator<Item = (Row, Option<R::Row>)>, WorkTableError>
+    where
+        R: JoinRight<Key>,
+        F: Fn(&Row) -> Key;
+}
+
+impl<Row, I, ColumnRange, RowFields> SelectJoinExecutor<Row>
+    for SelectQueryBuilder<Row, I, ColumnRange, RowFields>
+where
+    Row: Clone,
+    I: DoubleEndedIterator<Item = Row> + Sized,
+    Self: SelectQueryExecutor<Row, I, ColumnRange, RowFields>,
+{
+    fn join<Key, R, F>(
+        self,
+        right: R,
+        key: F,
+    ) -> Result<impl Iterator<Item = (Row, R::Row)>, WorkTableError>
+    where
+        R: JoinRight<Key>,
+        F: Fn(&Row) -> Key,
+    {
+        let mut joined = vec![];
+        for l in self.execute()? {
+            for r in right.matches(&key(&l))? {
+                joined.push((l.clone(), r));
+            }
+        }
+        Ok(joined.into_iter())
+    }
+
+    fn left_join<Key, R, F>(
+        self,
+        right: R,
+        key: F,
+    ) -> Result<impl Iterator<Item = (Row, Option<R::Row>)>, WorkTableError>
+    where
+        R: JoinRight<Key>,
+        F: Fn(&Row) -> Key,
+    {
+        let mut joined = vec![];
+        for l in self.execute()? {
+            let matches = right.matches(&key(&l))?;
+            if matches.is_empty() {
+                joined.push((l, None));
+            } else {
+                joined.extend(matches.into_iter().map(|r| (l.clone(), Some(r))));
+            }
+        }
+        Ok(joined.into_iter())
+    }
+}
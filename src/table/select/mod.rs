@@ -1,8 +1,26 @@
 use std::collections::VecDeque;
 
+mod aggregate;
+mod archived;
+mod explain;
+mod join;
+mod page;
 mod query;
+mod stream;
+mod text_query;
+mod top_k;
+mod total_ord;
 
+pub use aggregate::{SelectAggregateExecutor, SelectGroupBuilder, SelectGroupExecutor};
+pub use archived::{ArchivedCheck, ArchivedFilter, ArchivedRows};
+pub use explain::{AccessPath, QueryPlan, SortStrategy};
+pub use join::{HashJoin, JoinRight, SelectJoinExecutor, TableJoin};
+pub use page::{PageCursor, SelectPage, SelectPageExecutor};
 pub use query::{SelectQueryBuilder, SelectQueryExecutor};
+pub use stream::SelectStreamExecutor;
+pub use text_query::{CmpOp, TextQuery, TextQueryColumn, TextQueryResult};
+pub use top_k::TopK;
+pub use total_ord::{TotalCmp, TotalOrd};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Order {
@@ -16,4 +34,7 @@ pub struct QueryParams<ColumnRange, RowFields> {
     pub offset: Option<usize>,
     pub order: VecDeque<(Order, RowFields)>,
     pub range: VecDeque<(ColumnRange, RowFields)>,
+    pub distinct: Option<RowFields>,
+    pub access_path: AccessPath,
+    pub estimated_rows: Option<usize>,
 }
@@ -0,0 +1,58 @@
+use crate::WorkTableError;
+use crate::select::{Order, SelectQueryBuilder};
+
+/// Result of the [`SelectPageExecutor::execute_page`]. `cursor` is `None` if
+/// there are no more rows after this page.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SelectPage<Row, Cursor> {
+    pub rows: Vec<Row>,
+    pub cursor: Option<Cursor>,
+}
+
+/// Position of the last row of the page. It is implemented by table's
+/// generated cursor type.
+pub trait PageCursor<Row, RowFields> {
+    /// Order of the query which returned the page.
+    fn order(&self) -> Vec<(Order, RowFields)>;
+    /// Checks if `row` goes after cursor's position.
+    fn is_passed_by(&self, row: &Row) -> bool;
+}
+
+impl<Row, I, ColumnRange, RowFields> SelectQueryBuilder<Row, I, ColumnRange, RowFields>
+where
+    I: DoubleEndedIterator<Item = Row> + Sized,
+{
+    /// Continues query after `cursor` got from `execute_page`. Query must be
+    /// built same as for the previous page (same select fn and filters), so
+    /// pages have same source and filters. Order is restored from cursor. All
+    /// query rows are read and rows before cursor are skipped, so for
+    /// unfiltered queries ordered by index table's `after` is faster.
+    pub fn after<C>(
+        self,
+        cursor: &C,
+    ) -> SelectQueryBuilder<Row, impl DoubleEndedIterator<Item = Row> + Sized, ColumnRange, RowFields>
+    where
+        C: PageCursor<Row, RowFields> + Clone,
+    {
+        let mut params = self.params;
+        params.order = cursor.order().into();
+        let cursor = cursor.clone();
+        SelectQueryBuilder {
+            params,
+            iter: self.iter.filter(move |row| cursor.is_passed_by(row)),
+        }
+    }
+}
+
+/// Keyset pagination for the [`SelectQueryBuilder`]. Returned cursor encodes
+/// sort key and primary key of the last row of the page, and can be passed to
+/// query's `after` or table's `after` to get next page.
+///
+/// If no `order_on` is set, rows are expected to be in primary key order (as
+/// for `select_all`).
+pub trait SelectPageExecutor<Row, Cursor>
+where
+    Self: Sized,
+{
+    fn execute_page(self, page_size: usize) -> Result<SelectPage<Row, Cursor>, WorkTableError>;
+}
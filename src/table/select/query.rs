@@ -1,7 +1,7 @@
 use std::collections::VecDeque;
 
 use crate::WorkTableError;
-use crate::select::{Order, QueryParams};
+use crate::select::{AccessPath, ArchivedFilter, Order, QueryParams, QueryPlan, SortStrategy};
 
 pub struct SelectQueryBuilder<Row, I, ColumnRange, RowFields>
 where
@@ -22,11 +22,22 @@ where
                 offset: None,
                 order: VecDeque::new(),
                 range: VecDeque::new(),
+                distinct: None,
+                access_path: AccessPath::default(),
+                estimated_rows: None,
             },
             iter,
         }
     }
 
+    /// Sets access path which is used to get builder's rows. It is reported
+    /// by `explain`.
+    pub fn with_access_path(mut self, path: AccessPath, estimated_rows: usize) -> Self {
+        self.params.access_path = path;
+        self.params.estimated_rows = Some(estimated_rows);
+        self
+    }
+
     pub fn limit(mut self, limit: usize) -> Self {
         self.params.limit = Some(limit);
         self
@@ -42,6 +53,13 @@ where
         self
     }
 
+    /// Keeps only first row for each distinct value of `column`. It is
+    /// applied after `order_on`, so first row in the current order is kept.
+    pub fn distinct_on(mut self, column: RowFields) -> Self {
+        self.params.distinct = Some(column);
+        self
+    }
+
     pub fn range_on<R>(mut self, column: RowFields, range: R) -> Self
     where
         R: Into<ColumnRange>,
@@ -57,10 +75,58 @@ where
     I: DoubleEndedIterator<Item = Row> + Sized,
 {
     fn execute(self) -> Result<Vec<Row>, WorkTableError>;
+    /// Returns iterator of the query rows. Rows of the query without
+    /// `order_on` are read lazily, ordered query rows are sorted first.
+    fn rows(self) -> impl Iterator<Item = Row>;
+    /// Applies `range_on` and `distinct_on` to the builder's rows lazily, so
+    /// rows are filtered as they are read. Returned builder has no ranges and
+    /// distinct column set. It doesn't sort rows, so `distinct_on` is
+    /// applied in the builder's rows order.
+    fn filtered(
+        self,
+    ) -> SelectQueryBuilder<Row, impl DoubleEndedIterator<Item = Row> + Sized, ColumnRange, RowFields>;
     fn where_by<F>(
         self,
         predicate: F,
     ) -> SelectQueryBuilder<Row, impl DoubleEndedIterator<Item = Row> + Sized, ColumnRange, RowFields>
     where
         F: FnMut(&Row) -> bool;
+    /// Filters rows by their archived representation, so rows which don't
+    /// match `predicate` are not deserialized. It is supported by table's
+    /// select fns iterators, so it must be called before `where_by`.
+    fn where_archived<'a, F>(self, predicate: F) -> Self
+    where
+        Row: rkyv::Archive,
+        F: Fn(&<Row as rkyv::Archive>::Archived) -> bool + 'a,
+        I: ArchivedFilter<'a, <Row as rkyv::Archive>::Archived>;
+}
+
+impl<Row, I, ColumnRange, RowFields> SelectQueryBuilder<Row, I, ColumnRange, RowFields>
+where
+    I: DoubleEndedIterator<Item = Row> + Sized,
+    Self: SelectQueryExecutor<Row, I, ColumnRange, RowFields>,
+{
+    /// Executes query and returns its [`QueryPlan`] with access path, sort
+    /// strategy and estimated/actual rows count.
+    pub fn explain(self) -> Result<QueryPlan, WorkTableError> {
+        let sort = if self.params.order.is_empty() {
+            None
+        } else if let (Some(limit), None) = (self.params.limit, &self.params.distinct) {
+            Some(SortStrategy::TopK(
+                self.params.offset.unwrap_or(0).saturating_add(limit),
+            ))
+        } else {
+            Some(SortStrategy::Full)
+        };
+        let access_path = self.params.access_path.clone();
+        let estimated_rows = self.params.estimated_rows;
+        let actual_rows = self.execute()?.len();
+
+        Ok(QueryPlan {
+            access_path,
+            sort,
+            estimated_rows,
+            actual_rows,
+        })
+    }
 }
@@ -0,0 +1,34 @@
+use futures::{Stream, stream};
+
+use crate::WorkTableError;
+use crate::select::{SelectQueryBuilder, SelectQueryExecutor};
+
+/// Streaming execution for the [`SelectQueryBuilder`].
+///
+/// If no `order_on` is set, rows are read lazily from the builder's iterator
+/// as stream is polled, `range_on` and `distinct_on` are checked for each row
+/// as it is read. Table's iterators read index in chunks and don't hold index
+/// or page locks between rows, so stream can be held across `.await` points
+/// while table is modified. Backpressure is provided by the polling itself
+/// and dropping the stream cancels the query. To read only rows of the index
+/// range, query must be built with table's `select_by_<column>_range` fn.
+///
+/// Ordered queries need all rows to be read first, so they are executed with
+/// `execute` and returned rows are streamed.
+pub trait SelectStreamExecutor<Row>
+where
+    Self: Sized,
+{
+    fn execute_stream(self) -> impl Stream<Item = Result<Row, WorkTableError>>;
+}
+
+impl<Row, I, ColumnRange, RowFields> SelectStreamExecutor<Row>
+    for SelectQueryBuilder<Row, I, ColumnRange, RowFields>
+where
+    I: DoubleEndedIterator<Item = Row> + Sized,
+    Self: SelectQueryExecutor<Row, I, ColumnRange, RowFields>,
+{
+    fn execute_stream(self) -> impl Stream<Item = Result<Row, WorkTableError>> {
+        stream::iter(self.rows().map(Ok))
+    }
+}
@@ -0,0 +1,386 @@
+use std::fmt::{self, Display, Formatter};
+use std::iter::Peekable;
+use std::vec::IntoIter;
+
+use prettytable::format::consts::FORMAT_NO_BORDER_LINE_SEPARATOR;
+use prettytable::{Cell, Row as TableRow, Table};
+
+use crate::WorkTableError;
+use crate::select::{Order, SelectQueryBuilder, SelectQueryExecutor};
+
+/// Table columns that can be used in [`TextQuery`]. Is implemented for
+/// generated `<Name>RowFields`.
+pub trait TextQueryColumn: Sized + Clone {
+    type Row;
+    type Value: PartialOrd;
+
+    /// All columns in declaration order.
+    fn columns() -> Vec<Self>;
+    fn name(&self) -> &'static str;
+    fn parse_value(&self, value: &str) -> Result<Self::Value, WorkTableError>;
+    fn get_value(&self, row: &Self::Row) -> Self::Value;
+    fn format_value(value: &Self::Value) -> String;
+
+    fn from_name(name: &str) -> Result<Self, WorkTableError> {
+        Self::columns()
+            .into_iter()
+            .find(|c| c.name() == name)
+            .ok_or_else(|| WorkTableError::QueryParseError(format!("Unknown column `{name}`")))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CmpOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+impl CmpOp {
+    fn matches<T: PartialOrd>(&self, left: &T, right: &T) -> bool {
+        match self {
+            CmpOp::Eq => left == right,
+            CmpOp::Ne => left != right,
+            CmpOp::Lt => left < right,
+            CmpOp::Le => left <= right,
+            CmpOp::Gt => left > right,
+            CmpOp::Ge => left >= right,
+        }
+    }
+}
+
+type BoxedIter<'a, Row> = Box<dyn DoubleEndedIterator<Item = Row> + 'a>;
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Word(String),
+    Str(String),
+    Op(CmpOp),
+    Comma,
+    Star,
+}
+
+/// Text query which is parsed at runtime, so tables can be queried without
+/// recompiling (for example from debug console). Syntax is:
+///
+/// ```text
+/// [select <column>, ... | *] [where <column> <op> <value> [and ...]]
+/// [order by <column> [asc|desc], ...] [limit <n>] [offset <n>]
+/// ```
+///
+/// where `<op>` is one of `=`, `!=`, `<`, `<=`, `>`, `>=`, and `<value>` is a
+/// word, number or quoted string. Values are parsed using column types when
+/// query is executed.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct TextQuery {
+    pub projection: Option<Vec<String>>,
+    pub filters: Vec<(String, CmpOp, String)>,
+    pub order: Vec<(String, Order)>,
+    pub limit: Option<usize>,
+    pub offset: Option<usize>,
+}
+
+impl TextQuery {
+    pub fn parse(query: &str) -> Result<Self, WorkTableError> {
+        let mut tokens = lex(query)?.into_iter().peekable();
+        let mut parsed = TextQuery::default();
+
+        if next_keyword(&mut tokens, "select") && tokens.next_if_eq(&Token::Star).is_none() {
+            let mut columns = vec![expect_word(&mut tokens)?];
+            while tokens.next_if_eq(&Token::Comma).is_some() {
+                columns.push(expect_word(&mut tokens)?);
+            }
+            parsed.projection = Some(columns);
+        }
+        if next_keyword(&mut tokens, "where") {
+            loop {
+                let column = expect_word(&mut tokens)?;
+                let op = match tokens.next() {
+                    Some(Token::Op(op)) => op,
+                    t => return Err(unexpected(t, "comparison operator")),
+                };
+                let value = match tokens.next() {
+                    Some(Token::Word(v)) | Some(Token::Str(v)) => v,
+                    t => return Err(unexpected(t, "value")),
+                };
+                parsed.filters.push((column, op, value));
+                if !next_keyword(&mut tokens, "and") {
+                    break;
+                }
+            }
+        }
+        if next_keyword(&mut tokens, "order") {
+            if !next_keyword(&mut tokens, "by") {
+                return Err(unexpected(tokens.next(), "`by`"));
+            }
+            loop {
+                let column = expect_word(&mut tokens)?;
+                let order = if next_keyword(&mut tokens, "desc") {
+                    Order::Desc
+                } else {
+                    next_keyword(&mut tokens, "asc");
+                    Order::Asc
+                };
+                parsed.order.push((column, order));
+                if tokens.next_if_eq(&Token::Comma).is_none() {
+                    break;
+                }
+            }
+        }
+        loop {
+            if next_keyword(&mut tokens, "limit") {
+                parsed.limit = Some(expect_number(&mut tokens)?);
+            } else if next_keyword(&mut tokens, "offset") {
+                parsed.offset = Some(expect_number(&mut tokens)?);
+            } else {
+                break;
+            }
+        }
+        if let Some(t) = tokens.next() {
+            return Err(unexpected(Some(t), "end of query"));
+        }
+
+        Ok(parsed)
+    }
+
+    /// Applies query to the provided [`SelectQueryBuilder`] and executes it.
+    /// Filters are applied to the builder's rows, so it can be already
+    /// filtered.
+    pub fn execute<'a, Row, I, ColumnRange, RowFields>(
+        &self,
+        query: SelectQueryBuilder<Row, I, ColumnRange, RowFields>,
+    ) -> Result<TextQueryResult, WorkTableError>
+    where
+        Row: 'a,
+        I: DoubleEndedIterator<Item = Row> + Sized + 'a,
+        RowFields: TextQueryColumn<Row = Row> + 'a,
+        SelectQueryBuilder<Row, BoxedIter<'a, Row>, ColumnRange, RowFields>:
+            SelectQueryExecutor<Row, BoxedIter<'a, Row>, ColumnRange, RowFields>,
+    {
+        let filters = self
+            .filters
+            .iter()
+            .map(|(column, op, value)| {
+                let column = RowFields::from_name(column)?;
+                let value = column.parse_value(value)?;
+                Ok((column, *op, value))
+            })
+            .collect::<Result<Vec<_>, WorkTableError>>()?;
+        let projection = match &self.projection {
+            Some(columns) => columns
+                .iter()
+                .map(|c| RowFields::from_name(c))
+                .collect::<Result<Vec<_>, _>>()?,
+            None => RowFields::columns(),
+        };
+
+        let iter: BoxedIter<'a, Row> = Box::new(query.iter.filter(move |row| {
+            filters
+                .iter()
+                .all(|(column, op, value)| op.matches(&column.get_value(row), value))
+        }));
+        let mut builder = SelectQueryBuilder {
+            params: query.params,
+            iter,
+        };
+        for (column, order) in &self.order {
+            builder = builder.order_on(RowFields::from_name(column)?, *order);
+        }
+        if let Some(limit) = self.limit {
+            builder = builder.limit(limit);
+        }
+        if let Some(offset) = self.offset {
+            builder = builder.offset(offset);
+        }
+
+        let rows = builder
+            .execute()?
+            .into_iter()
+            .map(|row| {
+                projection
+                    .iter()
+                    .map(|c| RowFields::format_value(&c.get_value(&row)))
+                    .collect()
+            })
+            .collect();
+
+        Ok(TextQueryResult {
+            columns: projection.iter().map(|c| c.name()).collect(),
+            rows,
+        })
+    }
+}
+
+/// Result of the [`TextQuery`]. Is rendered as table on `Display`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextQueryResult {
+    pub columns: Vec<&'static str>,
+    pub rows: Vec<Vec<String>>,
+}
+
+impl Display for TextQueryResult {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let mut table = Table::new();
+        table.set_format(*FORMAT_NO_BORDER_LINE_SEPARATOR);
+        table.set_titles(TableRow::new(
+            self.columns.iter().map(|c| Cell::new(c)).collect(),
+        ));
+        for row in &self.rows {
+            table.add_row(TableRow::new(row.iter().map(|v| Cell::new(v)).collect()));
+        }
+
+        let mut buffer = Vec::new();
+        table.print(&mut buffer).map_err(|_| fmt::Error)?;
+        let table_str = String::from_utf8(buffer).map_err(|_| fmt::Error)?;
+        writeln!(f, "{}", table_str.trim_end())
+    }
+}
+
+fn lex(query: &str) -> Result<Vec<Token>, WorkTableError> {
+    let mut tokens = vec![];
+    let mut chars = query.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        match c {
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            ',' => {
+                chars.next();
+                tokens.push(Token::Comma);
+            }
+            '*' => {
+                chars.next();
+                tokens.push(Token::Star);
+            }
+            '\'' | '"' => {
+                chars.next();
+                let mut value = String::new();
+                loop {
+                    match chars.next() {
+                        Some(ch) if ch == c => break,
+                        Some(ch) => value.push(ch),
+                        None => {
+                            return Err(WorkTableError::QueryParseError(
+                                "Unterminated string".to_string(),
+                            ));
+                        }
+                    }
+                }
+                tokens.push(Token::Str(value));
+            }
+            '=' | '!' | '<' | '>' => {
+                chars.next();
+                let with_eq = chars.next_if_eq(&'=').is_some();
+                let op = match (c, with_eq) {
+                    ('=', _) => CmpOp::Eq,
+                    ('!', true) => CmpOp::Ne,
+                    ('<', false) => CmpOp::Lt,
+                    ('<', true) => CmpOp::Le,
+                    ('>', false) => CmpOp::Gt,
+                    ('>', true) => CmpOp::Ge,
+                    _ => {
+                        return Err(WorkTableError::QueryParseError(
+                            "Unknown operator `!`".to_string(),
+                        ));
+                    }
+                };
+                tokens.push(Token::Op(op));
+            }
+            _ => {
+                let mut word = String::new();
+                while let Some(ch) =
+                    chars.next_if(|ch| ch.is_alphanumeric() || matches!(ch, '_' | '.' | '-' | ':'))
+                {
+                    word.push(ch);
+                }
+                if word.is_empty() {
+                    return Err(WorkTableError::QueryParseError(format!(
+                        "Unexpected character `{c}`"
+                    )));
+                }
+                tokens.push(Token::Word(word));
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+fn next_keyword(tokens: &mut Peekable<IntoIter<Token>>, keyword: &str) -> bool {
+    tokens
+        .next_if(|t| matches!(t, Token::Word(w) if w.eq_ignore_ascii_case(keyword)))
+        .is_some()
+}
+
+fn expect_word(tokens: &mut Peekable<IntoIter<Token>>) -> Result<String, WorkTableError> {
+    match tokens.next() {
+        Some(Token::Word(w)) => Ok(w),
+        t => Err(unexpected(t, "column name")),
+    }
+}
+
+fn expect_number(tokens: &mut Peekable<IntoIter<Token>>) -> Result<usize, WorkTableError> {
+    let word = expect_word(tokens)?;
+    word.parse()
+        .map_err(|_| WorkTableError::QueryParseError(format!("Expected number, got `{word}`")))
+}
+
+fn unexpected(token: Option<Token>, expected: &str) -> WorkTableError {
+    let got = match token {
+        Some(Token::Word(w)) => format!("`{w}`"),
+        Some(Token::Str(s)) => format!("'{s}'"),
+        Some(Token::Op(op)) => format!("{op:?}"),
+        Some(Token::Comma) => "`,`".to_string(),
+        Some(Token::Star) => "`*`".to_string(),
+        None => "end of query".to_string(),
+    };
+    WorkTableError::QueryParseError(format!("Expected {expected}, got {got}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CmpOp, TextQuery};
+    use crate::select::Order;
+
+    #[test]
+    fn parses_full_query() {
+        let query = TextQuery::parse(
+            "SELECT id, name where qty >= 10 and name = 'a b' order by qty desc, id limit 5 offset 2",
+        )
+        .unwrap();
+        assert_eq!(
+            query,
+            TextQuery {
+                projection: Some(vec!["id".to_string(), "name".to_string()]),
+                filters: vec![
+                    ("qty".to_string(), CmpOp::Ge, "10".to_string()),
+                    ("name".to_string(), CmpOp::Eq, "a b".to_string()),
+                ],
+                order: vec![
+                    ("qty".to_string(), Order::Desc),
+                    ("id".to_string(), Order::Asc)
+                ],
+                limit: Some(5),
+                offset: Some(2),
+            }
+        );
+    }
+
+    #[test]
+    fn parses_empty_query() {
+        assert_eq!(TextQuery::parse("").unwrap(), TextQuery::default());
+        assert_eq!(TextQuery::parse("select *").unwrap(), TextQuery::default());
+    }
+
+    #[test]
+    fn fails_on_bad_query() {
+        assert!(TextQuery::parse("where qty").is_err());
+        assert!(TextQuery::parse("where qty ! 1").is_err());
+        assert!(TextQuery::parse("order qty").is_err());
+        assert!(TextQuery::parse("limit ten").is_err());
+        assert!(TextQuery::parse("select id foo").is_err());
+        assert!(TextQuery::parse("where name = 'abc").is_err());
+    }
+}
@@ -0,0 +1,127 @@
+use std::cmp::Ordering;
+
+/// Bounded heap which keeps `k` smallest items according to `cmp`. It is used
+/// for `order_on` queries with `limit`, so only `offset + limit` rows are
+/// kept in memory instead of sorting whole result set.
+///
+/// Items with equal keys keep insertion order, so result is the same as
+/// stable sort followed by truncation.
+pub struct TopK<T, F>
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    k: usize,
+    /// Max-heap by `(cmp, seq)`, so root is the worst kept item.
+    heap: Vec<(usize, T)>,
+    seq: usize,
+    cmp: F,
+}
+
+impl<T, F> TopK<T, F>
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    pub fn new(k: usize, cmp: F) -> Self {
+        Self {
+            k,
+            heap: Vec::with_capacity(k.min(1024)),
+            seq: 0,
+            cmp,
+        }
+    }
+
+    pub fn push(&mut self, item: T) {
+        let seq = self.seq;
+        self.seq += 1;
+        if self.heap.len() < self.k {
+            self.heap.push((seq, item));
+            self.sift_up(self.heap.len() - 1);
+        } else if let Some((_, root)) = self.heap.first() {
+            // New item has the biggest `seq`, so it replaces root only if
+            // it's strictly less.
+            if (self.cmp)(&item, root) == Ordering::Less {
+                self.heap[0] = (seq, item);
+                self.sift_down(0);
+            }
+        }
+    }
+
+    pub fn into_sorted_vec(mut self) -> Vec<T> {
+        let mut items = std::mem::take(&mut self.heap);
+        let cmp = &mut self.cmp;
+        items.sort_by(|(a_seq, a), (b_seq, b)| cmp(a, b).then(a_seq.cmp(b_seq)));
+        items.into_iter().map(|(_, item)| item).collect()
+    }
+
+    fn greater(&mut self, a: usize, b: usize) -> bool {
+        let (a_seq, a) = &self.heap[a];
+        let (b_seq, b) = &self.heap[b];
+        (self.cmp)(a, b).then(a_seq.cmp(b_seq)) == Ordering::Greater
+    }
+
+    fn sift_up(&mut self, mut pos: usize) {
+        while pos > 0 {
+            let parent = (pos - 1) / 2;
+            if !self.greater(pos, parent) {
+                break;
+            }
+            self.heap.swap(pos, parent);
+            pos = parent;
+        }
+    }
+
+    fn sift_down(&mut self, mut pos: usize) {
+        loop {
+            let left = 2 * pos + 1;
+            let right = left + 1;
+            let mut largest = pos;
+            if left < self.heap.len() && self.greater(left, largest) {
+                largest = left;
+            }
+            if right < self.heap.len() && self.greater(right, largest) {
+                largest = right;
+            }
+            if largest == pos {
+                break;
+            }
+            self.heap.swap(pos, largest);
+            pos = largest;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TopK;
+
+    #[test]
+    fn keeps_k_smallest_sorted() {
+        let items: Vec<u64> = (0..1000).map(|i| (i * 7919) % 1000).collect();
+        let mut top = TopK::new(10, |a: &u64, b: &u64| a.cmp(b));
+        for i in &items {
+            top.push(*i);
+        }
+        assert_eq!(top.into_sorted_vec(), (0..10).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn is_stable() {
+        let items: Vec<(u64, usize)> = (0..100).map(|i| (i % 3, i as usize)).collect();
+        let mut top = TopK::new(20, |a: &(u64, usize), b: &(u64, usize)| a.0.cmp(&b.0));
+        for i in &items {
+            top.push(*i);
+        }
+
+        let mut expected = items.clone();
+        expected.sort_by_key(|a| a.0);
+        expected.truncate(20);
+        assert_eq!(top.into_sorted_vec(), expected);
+    }
+
+    #[test]
+    fn zero_k() {
+        let mut top = TopK::new(0, |a: &u64, b: &u64| a.cmp(b));
+        top.push(1);
+        assert!(top.into_sorted_vec().is_empty());
+    }
+}
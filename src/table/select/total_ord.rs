@@ -0,0 +1,33 @@
+use std::cmp::Ordering;
+
+/// Total order of the column values. Float values are compared with
+/// `total_cmp`, so `NaN` values are equal to each other and are not equal to
+/// other values.
+pub trait TotalCmp {
+    fn total_cmp(&self, other: &Self) -> Ordering;
+}
+
+/// Wrapper which orders value by its [`TotalCmp`], so column values can be
+/// used as keys of `BTreeSet` or `BTreeMap`.
+#[derive(Debug, Clone)]
+pub struct TotalOrd<T>(pub T);
+
+impl<T: TotalCmp> PartialEq for TotalOrd<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<T: TotalCmp> Eq for TotalOrd<T> {}
+
+impl<T: TotalCmp> PartialOrd for TotalOrd<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: TotalCmp> Ord for TotalOrd<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
@@ -34,6 +34,10 @@ pub struct IndexInfo {
 pub enum IndexKind {
     Unique,
     NonUnique,
+    UniqueHash,
+    NonUniqueHash,
+    Bitmap,
+    Spatial,
 }
 
 impl Display for IndexKind {
@@ -41,6 +45,10 @@ impl Display for IndexKind {
         match self {
             Self::Unique => write!(f, "unique"),
             Self::NonUnique => write!(f, "non unique"),
+            Self::UniqueHash => write!(f, "unique hash"),
+            Self::NonUniqueHash => write!(f, "non unique hash"),
+            Self::Bitmap => write!(f, "bitmap"),
+            Self::Spatial => write!(f, "spatial"),
         }
     }
 }
@@ -0,0 +1,122 @@
+use std::mem::size_of;
+
+use data_bucket::VariableSizeMeasurable;
+use rkyv::boxed::ArchivedBox;
+use rkyv::collections::btree_map::ArchivedBTreeMap;
+use rkyv::collections::btree_set::ArchivedBTreeSet;
+use rkyv::collections::swiss_table::{ArchivedHashMap, ArchivedHashSet};
+use rkyv::option::ArchivedOption;
+use rkyv::rend::{
+    char_le, f32_le, f64_le, i16_le, i32_le, i64_le, i128_le, u16_le, u32_le, u64_le, u128_le,
+};
+use rkyv::string::ArchivedString;
+use rkyv::vec::ArchivedVec;
+
+/// Size of the archived value including its out-of-line data. It is used to
+/// check whether updated value of the variable size column fits its old place
+/// without value deserialization. Custom `unsized` column types must
+/// implement it for their archived type.
+pub trait ArchivedSize {
+    fn archived_size(&self) -> usize;
+}
+
+macro_rules! archived_size_for_sized {
+    ($($t:ty),+) => {
+        $(
+            impl ArchivedSize for $t {
+                fn archived_size(&self) -> usize {
+                    size_of::<Self>()
+                }
+            }
+        )+
+    };
+}
+
+archived_size_for_sized!(
+    u8,
+    i8,
+    bool,
+    u16_le,
+    u32_le,
+    u64_le,
+    u128_le,
+    i16_le,
+    i32_le,
+    i64_le,
+    i128_le,
+    f32_le,
+    f64_le,
+    char_le,
+    uuid::Uuid
+);
+
+impl ArchivedSize for ArchivedString {
+    fn archived_size(&self) -> usize {
+        String::aligned_size(self.len())
+    }
+}
+
+impl<T: ArchivedSize> ArchivedSize for [T] {
+    fn archived_size(&self) -> usize {
+        self.iter().map(ArchivedSize::archived_size).sum()
+    }
+}
+
+impl<T: ArchivedSize, const N: usize> ArchivedSize for [T; N] {
+    fn archived_size(&self) -> usize {
+        self.as_slice().archived_size()
+    }
+}
+
+impl<T: ArchivedSize> ArchivedSize for ArchivedVec<T> {
+    fn archived_size(&self) -> usize {
+        size_of::<Self>() + self.as_slice().archived_size()
+    }
+}
+
+impl<T: ArchivedSize + ?Sized + rkyv::traits::ArchivePointee> ArchivedSize for ArchivedBox<T> {
+    fn archived_size(&self) -> usize {
+        size_of::<Self>() + self.get().archived_size()
+    }
+}
+
+impl<T: ArchivedSize> ArchivedSize for ArchivedOption<T> {
+    fn archived_size(&self) -> usize {
+        match self.as_ref() {
+            Some(value) => size_of::<Self>() - size_of::<T>() + value.archived_size(),
+            None => size_of::<Self>(),
+        }
+    }
+}
+
+impl<K: ArchivedSize, V: ArchivedSize, H> ArchivedSize for ArchivedHashMap<K, V, H> {
+    fn archived_size(&self) -> usize {
+        size_of::<Self>()
+            + self
+                .iter()
+                .map(|(k, v)| k.archived_size() + v.archived_size())
+                .sum::<usize>()
+    }
+}
+
+impl<K: ArchivedSize, H> ArchivedSize for ArchivedHashSet<K, H> {
+    fn archived_size(&self) -> usize {
+        size_of::<Self>() + self.iter().map(ArchivedSize::archived_size).sum::<usize>()
+    }
+}
+
+impl<K: ArchivedSize, V: ArchivedSize, const E: usize> ArchivedSize for ArchivedBTreeMap<K, V, E> {
+    fn archived_size(&self) -> usize {
+        size_of::<Self>()
+            + self
+                .iter()
+                .map(|(k, v)| k.archived_size() + v.archived_size())
+                .sum::<usize>()
+    }
+}
+
+impl<K: ArchivedSize, const E: usize> ArchivedSize for ArchivedBTreeSet<K, E> {
+    fn archived_size(&self) -> usize {
+        size_of::<Self>() + self.iter().map(ArchivedSize::archived_size).sum::<usize>()
+    }
+}
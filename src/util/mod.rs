@@ -1,5 +1,7 @@
+mod archived_size;
 mod optimized_vec;
 mod ordered_float;
 
+pub use archived_size::ArchivedSize;
 pub use optimized_vec::OptimizedVec;
 pub use ordered_float::{OrderedF32Def, OrderedF64Def};
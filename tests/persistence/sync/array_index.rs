@@ -0,0 +1,155 @@
+use crate::remove_dir_if_exists;
+
+use worktable::prelude::*;
+use worktable::worktable;
+
+worktable!(
+    name: ArrayIndexReRead,
+    persist: true,
+    columns: {
+        id: u64 primary_key autoincrement,
+        tags: Vec<String>,
+        codes: Vec<u64>,
+    },
+    indexes: {
+        tags_idx: tags array,
+        codes_idx: codes array,
+    },
+    queries: {
+        update: {
+            CodesById(codes) by id,
+        },
+    }
+);
+
+#[test]
+fn test_array_index_re_read() {
+    let config = PersistenceConfig::new(
+        "tests/data/array_index/reread",
+        "tests/data/array_index/reread",
+    );
+
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .worker_threads(2)
+        .enable_io()
+        .enable_time()
+        .build()
+        .unwrap();
+
+    runtime.block_on(async {
+        remove_dir_if_exists("tests/data/array_index/reread".to_string()).await;
+
+        {
+            let table = ArrayIndexReReadWorkTable::load_from_file(config.clone())
+                .await
+                .unwrap();
+            for i in 0..100 {
+                table
+                    .insert(ArrayIndexReReadRow {
+                        id: table.get_next_pk().into(),
+                        tags: vec![format!("tag_{i}"), "all".to_string()],
+                        codes: vec![i % 10, 100 + i],
+                    })
+                    .unwrap();
+            }
+            table
+                .update_codes_by_id(
+                    CodesByIdQuery { codes: vec![1000] },
+                    ArrayIndexReReadPrimaryKey::from(42),
+                )
+                .await
+                .unwrap();
+            table.delete(7.into()).await.unwrap();
+            table.wait_for_ops().await
+        }
+        {
+            let table = ArrayIndexReReadWorkTable::load_from_file(config.clone())
+                .await
+                .unwrap();
+            assert_eq!(table.select_all().execute().unwrap().len(), 99);
+
+            let rows = table
+                .select_by_tags_contains("tag_42".to_string())
+                .execute()
+                .unwrap();
+            assert_eq!(rows.len(), 1);
+            assert_eq!(rows[0].codes, vec![1000]);
+            assert!(!table.exists_by_tags_contains("tag_7".to_string()));
+
+            assert_eq!(table.count_by_codes_contains(1000), 1);
+            assert_eq!(table.count_by_codes_contains(142), 0);
+            assert_eq!(table.count_by_codes_contains(2), 9);
+            assert_eq!(table.count_by_codes_contains(7), 9);
+        }
+    })
+}
+
+worktable!(
+    name: SizedArrayIndexReRead,
+    persist: true,
+    columns: {
+        id: u64 primary_key autoincrement,
+        codes: [u16; 3],
+    },
+    indexes: {
+        codes_idx: codes array,
+    },
+    queries: {
+        update: {
+            SizedCodesById(codes) by id,
+        },
+    }
+);
+
+#[test]
+fn test_sized_array_index_re_read() {
+    let config = PersistenceConfig::new(
+        "tests/data/array_index/sized_reread",
+        "tests/data/array_index/sized_reread",
+    );
+
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .worker_threads(2)
+        .enable_io()
+        .enable_time()
+        .build()
+        .unwrap();
+
+    runtime.block_on(async {
+        remove_dir_if_exists("tests/data/array_index/sized_reread".to_string()).await;
+
+        {
+            let table = SizedArrayIndexReReadWorkTable::load_from_file(config.clone())
+                .await
+                .unwrap();
+            for i in 0..100 {
+                table
+                    .insert(SizedArrayIndexReReadRow {
+                        id: table.get_next_pk().into(),
+                        codes: [i % 10, i % 10, 100 + i],
+                    })
+                    .unwrap();
+            }
+            table
+                .update_sized_codes_by_id(
+                    SizedCodesByIdQuery {
+                        codes: [2, 1000, 1000],
+                    },
+                    SizedArrayIndexReReadPrimaryKey::from(42),
+                )
+                .await
+                .unwrap();
+            table.wait_for_ops().await
+        }
+        {
+            let table = SizedArrayIndexReReadWorkTable::load_from_file(config.clone())
+                .await
+                .unwrap();
+            assert_eq!(table.count_by_codes_contains(1000), 1);
+            assert_eq!(table.count_by_codes_contains(142), 0);
+            assert_eq!(table.count_by_codes_contains(2), 10);
+            let rows = table.select_by_codes_contains(1000).execute().unwrap();
+            assert_eq!(rows[0].id, 42);
+        }
+    })
+}
@@ -0,0 +1,88 @@
+use crate::remove_dir_if_exists;
+
+use worktable::prelude::*;
+use worktable::worktable;
+
+worktable!(
+    name: BitmapIndexReRead,
+    persist: true,
+    columns: {
+        id: u64 primary_key autoincrement,
+        kind: u8,
+        venue: u32,
+        another: u64,
+    },
+    indexes: {
+        kind_idx: kind bitmap,
+        venue_idx: venue bitmap,
+        another_idx: another unique,
+    },
+    queries: {
+        update: {
+            KindById(kind) by id,
+        },
+    }
+);
+
+#[test]
+fn test_bitmap_index_re_read() {
+    let config = PersistenceConfig::new(
+        "tests/data/bitmap_index/reread",
+        "tests/data/bitmap_index/reread",
+    );
+
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .worker_threads(2)
+        .enable_io()
+        .enable_time()
+        .build()
+        .unwrap();
+
+    runtime.block_on(async {
+        remove_dir_if_exists("tests/data/bitmap_index/reread".to_string()).await;
+
+        {
+            let table = BitmapIndexReReadWorkTable::load_from_file(config.clone())
+                .await
+                .unwrap();
+            for i in 0..100 {
+                table
+                    .insert(BitmapIndexReReadRow {
+                        id: table.get_next_pk().into(),
+                        kind: (i % 4) as u8,
+                        venue: (i % 5) as u32,
+                        another: i,
+                    })
+                    .unwrap();
+            }
+            table
+                .update_kind_by_id(
+                    KindByIdQuery { kind: 7 },
+                    BitmapIndexReReadPrimaryKey::from(1),
+                )
+                .await
+                .unwrap();
+            table.delete(2.into()).await.unwrap();
+            table.wait_for_ops().await
+        }
+        {
+            let table = BitmapIndexReReadWorkTable::load_from_file(config.clone())
+                .await
+                .unwrap();
+            assert_eq!(table.select_all().execute().unwrap().len(), 99);
+
+            assert_eq!(table.count_by_kind(0), 25);
+            assert_eq!(table.count_by_kind(1), 24);
+            assert_eq!(table.count_by_kind(2), 24);
+            assert_eq!(table.count_by_kind(7), 1);
+            let filter = BitmapIndexReReadBitmapFilter {
+                kind: Some(0),
+                venue: Some(0),
+            };
+            assert_eq!(table.count_by_bitmaps(filter.clone()), 5);
+            let rows = table.select_by_bitmaps(filter).execute().unwrap();
+            assert!(rows.iter().all(|r| r.id % 20 == 0));
+            assert_eq!(table.select_by_another(50).unwrap().id, 50);
+        }
+    })
+}
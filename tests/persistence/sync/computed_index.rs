@@ -0,0 +1,95 @@
+use crate::remove_dir_if_exists;
+
+use worktable::prelude::*;
+use worktable::worktable;
+
+worktable!(
+    name: ComputedIndexReRead,
+    persist: true,
+    columns: {
+        id: u64 primary_key autoincrement,
+        email: String,
+        price: u64,
+        qty: u64,
+    },
+    indexes: {
+        notional_idx: expr(price * qty) as u64,
+        lower_email_idx: expr(email.to_lowercase()) unique,
+    },
+    queries: {
+        update: {
+            QtyById(qty) by id,
+        },
+    }
+);
+
+#[test]
+fn test_computed_index_re_read() {
+    let config = PersistenceConfig::new(
+        "tests/data/computed_index/reread",
+        "tests/data/computed_index/reread",
+    );
+
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .worker_threads(2)
+        .enable_io()
+        .enable_time()
+        .build()
+        .unwrap();
+
+    runtime.block_on(async {
+        remove_dir_if_exists("tests/data/computed_index/reread".to_string()).await;
+
+        {
+            let table = ComputedIndexReReadWorkTable::load_from_file(config.clone())
+                .await
+                .unwrap();
+            for i in 0..100 {
+                table
+                    .insert(ComputedIndexReReadRow {
+                        id: table.get_next_pk().into(),
+                        email: format!("User{i}@Mail.com"),
+                        price: i % 10,
+                        qty: 3,
+                    })
+                    .unwrap();
+            }
+            table
+                .update_qty_by_id(
+                    QtyByIdQuery { qty: 100 },
+                    ComputedIndexReReadPrimaryKey::from(7),
+                )
+                .await
+                .unwrap();
+            table.wait_for_ops().await
+        }
+        {
+            let table = ComputedIndexReReadWorkTable::load_from_file(config.clone())
+                .await
+                .unwrap();
+            assert_eq!(table.select_all().execute().unwrap().len(), 100);
+
+            assert_eq!(table.count_by_notional_idx(6), 10);
+            assert_eq!(table.count_by_notional_idx(21), 9);
+            assert_eq!(
+                table.select_by_notional_idx(700).execute().unwrap()[0].id,
+                7
+            );
+            assert_eq!(
+                table
+                    .select_by_lower_email_idx("user42@mail.com".to_string())
+                    .unwrap()
+                    .id,
+                42
+            );
+
+            let res = table.insert(ComputedIndexReReadRow {
+                id: table.get_next_pk().into(),
+                email: "USER42@mail.com".to_string(),
+                price: 0,
+                qty: 0,
+            });
+            assert!(res.is_err());
+        }
+    })
+}
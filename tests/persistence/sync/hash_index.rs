@@ -0,0 +1,102 @@
+use crate::remove_dir_if_exists;
+
+use worktable::prelude::*;
+use worktable::worktable;
+
+worktable!(
+    name: HashIndexReRead,
+    persist: true,
+    columns: {
+        id: u64 primary_key autoincrement,
+        client_order_id: String,
+        account: u64,
+        another: u64,
+    },
+    indexes: {
+        coid_idx: client_order_id unique hash,
+        account_idx: account hash,
+        another_idx: another unique,
+    },
+    queries: {
+        update: {
+            CoidById(client_order_id) by id,
+        },
+    }
+);
+
+#[test]
+fn test_hash_index_re_read() {
+    let config = PersistenceConfig::new(
+        "tests/data/hash_index/reread",
+        "tests/data/hash_index/reread",
+    );
+
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .worker_threads(2)
+        .enable_io()
+        .enable_time()
+        .build()
+        .unwrap();
+
+    runtime.block_on(async {
+        remove_dir_if_exists("tests/data/hash_index/reread".to_string()).await;
+
+        {
+            let table = HashIndexReReadWorkTable::load_from_file(config.clone())
+                .await
+                .unwrap();
+            for i in 0..100 {
+                table
+                    .insert(HashIndexReReadRow {
+                        id: table.get_next_pk().into(),
+                        client_order_id: format!("coid-{i}"),
+                        account: i % 10,
+                        another: i,
+                    })
+                    .unwrap();
+            }
+            table
+                .update_coid_by_id(
+                    CoidByIdQuery {
+                        client_order_id: "renamed-coid-1".to_string(),
+                    },
+                    HashIndexReReadPrimaryKey::from(1),
+                )
+                .await
+                .unwrap();
+            table.delete(2.into()).await.unwrap();
+            table.wait_for_ops().await
+        }
+        {
+            let table = HashIndexReReadWorkTable::load_from_file(config.clone())
+                .await
+                .unwrap();
+            assert_eq!(table.select_all().execute().unwrap().len(), 99);
+
+            let row = table
+                .select_by_client_order_id("coid-42".to_string())
+                .unwrap();
+            assert_eq!(row.id, 42);
+            assert!(!table.exists_by_client_order_id("coid-1".to_string()));
+            assert!(!table.exists_by_client_order_id("coid-2".to_string()));
+            assert_eq!(
+                table
+                    .select_by_client_order_id("renamed-coid-1".to_string())
+                    .unwrap()
+                    .id,
+                1
+            );
+            assert_eq!(table.count_by_account(2), 9);
+            assert_eq!(table.count_by_account(3), 10);
+            assert_eq!(table.select_by_another(50).unwrap().id, 50);
+
+            let res = table.insert(HashIndexReReadRow {
+                id: table.get_next_pk().into(),
+                client_order_id: "coid-50".to_string(),
+                account: 0,
+                another: 1000,
+            });
+            assert!(res.is_err());
+        }
+    })
+}
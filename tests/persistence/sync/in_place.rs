@@ -0,0 +1,87 @@
+use crate::remove_dir_if_exists;
+
+use worktable::prelude::*;
+use worktable::worktable;
+
+worktable!(
+    name: InPlaceReRead,
+    persist: true,
+    columns: {
+        id: u64 primary_key autoincrement,
+        account: u32,
+        counter: u64,
+        notional: f64,
+    },
+    indexes: {
+        account_idx: account,
+    },
+    queries: {
+        in_place: {
+            CounterById(counter) by id,
+            NotionalByAccount(notional) by account,
+        }
+    }
+);
+
+#[test]
+fn test_in_place_re_read() {
+    let config = PersistenceConfig::new("tests/data/in_place/reread", "tests/data/in_place/reread");
+
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .worker_threads(2)
+        .enable_io()
+        .enable_time()
+        .build()
+        .unwrap();
+
+    runtime.block_on(async {
+        remove_dir_if_exists("tests/data/in_place/reread".to_string()).await;
+
+        {
+            let table = InPlaceReReadWorkTable::load_from_file(config.clone())
+                .await
+                .unwrap();
+            for i in 0..30 {
+                table
+                    .insert(InPlaceReReadRow {
+                        id: table.get_next_pk().into(),
+                        account: i % 3,
+                        counter: 0,
+                        notional: 0.0,
+                    })
+                    .unwrap();
+            }
+            // Many updates of the same row are coalesced in the queue.
+            for _ in 0..5_000 {
+                table
+                    .update_counter_by_id_in_place(|counter| *counter += 1, 7)
+                    .await
+                    .unwrap();
+            }
+            table
+                .update_notional_by_account_in_place(|notional| *notional = 2.5.into(), 1)
+                .await
+                .unwrap();
+            table.wait_for_ops().await
+        }
+        {
+            let table = InPlaceReReadWorkTable::load_from_file(config.clone())
+                .await
+                .unwrap();
+            assert_eq!(table.select_all().execute().unwrap().len(), 30);
+            assert_eq!(table.select(7).unwrap().counter, 5_000);
+            assert_eq!(table.select(8).unwrap().counter, 0);
+            let rows = table.select_by_account(1).execute().unwrap();
+            assert_eq!(rows.len(), 10);
+            assert!(rows.iter().all(|r| r.notional == 2.5));
+            assert!(
+                table
+                    .select_by_account(2)
+                    .execute()
+                    .unwrap()
+                    .iter()
+                    .all(|r| r.notional == 0.0)
+            );
+        }
+    })
+}
@@ -4,11 +4,23 @@ use std::time::Duration;
 use worktable::prelude::*;
 use worktable::worktable;
 
+mod array_index;
+mod bitmap_index;
+mod computed_index;
+mod hash_index;
+mod in_place;
 mod many_strings;
+mod option_index;
+mod partial_index;
+mod primary_key_update;
+mod spatial_index;
+mod string_index;
 mod string_primary_index;
 mod string_re_read;
 mod string_secondary_index;
+mod type_path;
 mod uuid_;
+mod variable_size;
 
 worktable! (
     name: TestSync,
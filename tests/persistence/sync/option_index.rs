@@ -0,0 +1,86 @@
+use crate::remove_dir_if_exists;
+
+use worktable::prelude::*;
+use worktable::worktable;
+
+worktable!(
+    name: OptionIndexReRead,
+    persist: true,
+    columns: {
+        id: u64 primary_key autoincrement,
+        email: String optional,
+        score: u64 optional,
+    },
+    indexes: {
+        email_idx: email unique,
+        score_idx: score,
+    },
+    queries: {
+        update: {
+            ScoreById(score) by id,
+        },
+    }
+);
+
+#[test]
+fn test_option_index_re_read() {
+    let config = PersistenceConfig::new(
+        "tests/data/option_index/reread",
+        "tests/data/option_index/reread",
+    );
+
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .worker_threads(2)
+        .enable_io()
+        .enable_time()
+        .build()
+        .unwrap();
+
+    runtime.block_on(async {
+        remove_dir_if_exists("tests/data/option_index/reread".to_string()).await;
+
+        {
+            let table = OptionIndexReReadWorkTable::load_from_file(config.clone())
+                .await
+                .unwrap();
+            for i in 0..100 {
+                table
+                    .insert(OptionIndexReReadRow {
+                        id: table.get_next_pk().into(),
+                        email: (i % 2 == 0).then(|| format!("user_{i}@mail.com")),
+                        score: (i % 3 != 0).then_some(i % 10),
+                    })
+                    .unwrap();
+            }
+            table
+                .update_score_by_id(
+                    ScoreByIdQuery { score: None },
+                    OptionIndexReReadPrimaryKey::from(1),
+                )
+                .await
+                .unwrap();
+            table.wait_for_ops().await
+        }
+        {
+            let table = OptionIndexReReadWorkTable::load_from_file(config.clone())
+                .await
+                .unwrap();
+            assert_eq!(table.select_all().execute().unwrap().len(), 100);
+
+            let row = table
+                .select_by_email("user_42@mail.com".to_string())
+                .unwrap();
+            assert_eq!(row.id, 42);
+            assert!(
+                table
+                    .select_by_email("user_43@mail.com".to_string())
+                    .is_none()
+            );
+            assert_eq!(table.select_by_email_is_none().execute().unwrap().len(), 50);
+
+            assert_eq!(table.count_by_score(1), 6);
+            assert_eq!(table.select_by_score_is_none().execute().unwrap().len(), 35);
+            assert_eq!(table.count_range_by_score(..), 65);
+        }
+    })
+}
@@ -0,0 +1,123 @@
+use crate::remove_dir_if_exists;
+
+use worktable::prelude::*;
+use worktable::worktable;
+
+worktable!(
+    name: PartialIndexReRead,
+    persist: true,
+    columns: {
+        id: u64 primary_key autoincrement,
+        client_order_id: String,
+        is_open: bool,
+        account: u64,
+    },
+    indexes: {
+        open_coid_idx: client_order_id unique where(is_open),
+        account_idx: account where(account % 2 == 0),
+    },
+    queries: {
+        update: {
+            IsOpenById(is_open) by id,
+        },
+    }
+);
+
+#[test]
+fn test_partial_index_re_read() {
+    let config = PersistenceConfig::new(
+        "tests/data/partial_index/reread",
+        "tests/data/partial_index/reread",
+    );
+
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .worker_threads(2)
+        .enable_io()
+        .enable_time()
+        .build()
+        .unwrap();
+
+    runtime.block_on(async {
+        remove_dir_if_exists("tests/data/partial_index/reread".to_string()).await;
+
+        {
+            let table = PartialIndexReReadWorkTable::load_from_file(config.clone())
+                .await
+                .unwrap();
+            for i in 0..100 {
+                table
+                    .insert(PartialIndexReReadRow {
+                        id: table.get_next_pk().into(),
+                        client_order_id: format!("coid-{i}"),
+                        is_open: i % 4 == 0,
+                        account: i % 10,
+                    })
+                    .unwrap();
+            }
+            table
+                .update_is_open_by_id(
+                    IsOpenByIdQuery { is_open: false },
+                    PartialIndexReReadPrimaryKey::from(4),
+                )
+                .await
+                .unwrap();
+            table
+                .update_is_open_by_id(
+                    IsOpenByIdQuery { is_open: true },
+                    PartialIndexReReadPrimaryKey::from(5),
+                )
+                .await
+                .unwrap();
+            table.wait_for_ops().await
+        }
+        {
+            let table = PartialIndexReReadWorkTable::load_from_file(config.clone())
+                .await
+                .unwrap();
+            assert_eq!(table.select_all().execute().unwrap().len(), 100);
+
+            assert_eq!(
+                table
+                    .select_by_client_order_id("coid-8".to_string())
+                    .unwrap()
+                    .id,
+                8
+            );
+            assert_eq!(
+                table
+                    .select_by_client_order_id("coid-5".to_string())
+                    .unwrap()
+                    .id,
+                5
+            );
+            assert!(
+                table
+                    .select_by_client_order_id("coid-4".to_string())
+                    .is_none()
+            );
+            assert!(
+                table
+                    .select_by_client_order_id("coid-3".to_string())
+                    .is_none()
+            );
+            assert_eq!(table.count_by_account(2), 10);
+            assert_eq!(table.count_by_account(3), 0);
+
+            table
+                .insert(PartialIndexReReadRow {
+                    id: table.get_next_pk().into(),
+                    client_order_id: "coid-4".to_string(),
+                    is_open: true,
+                    account: 0,
+                })
+                .unwrap();
+            let res = table.insert(PartialIndexReReadRow {
+                id: table.get_next_pk().into(),
+                client_order_id: "coid-8".to_string(),
+                is_open: true,
+                account: 0,
+            });
+            assert!(res.is_err());
+        }
+    })
+}
@@ -0,0 +1,87 @@
+use crate::remove_dir_if_exists;
+
+use worktable::prelude::*;
+use worktable::worktable;
+
+worktable!(
+    name: PrimaryKeyMove,
+    persist: true,
+    columns: {
+        id: u64 primary_key,
+        email: String,
+        group: u32,
+    },
+    indexes: {
+        email_idx: email unique,
+        group_idx: group,
+    },
+);
+
+#[test]
+fn test_primary_key_update_re_read() {
+    let config = PersistenceConfig::new(
+        "tests/data/primary_key_update/reread",
+        "tests/data/primary_key_update/reread",
+    );
+
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .worker_threads(2)
+        .enable_io()
+        .enable_time()
+        .build()
+        .unwrap();
+
+    runtime.block_on(async {
+        remove_dir_if_exists("tests/data/primary_key_update/reread".to_string()).await;
+
+        {
+            let table = PrimaryKeyMoveWorkTable::load_from_file(config.clone())
+                .await
+                .unwrap();
+            for id in 0..20 {
+                table
+                    .insert(PrimaryKeyMoveRow {
+                        id,
+                        email: format!("user{id}@mail.com"),
+                        group: id as u32 / 2,
+                    })
+                    .unwrap();
+            }
+            table.update_primary_key(3, 300).await.unwrap();
+            table.update_primary_key(300, 301).await.unwrap();
+            table.update_primary_key(7, 3).await.unwrap();
+            assert!(table.update_primary_key(8, 9).await.is_err());
+            table.wait_for_ops().await
+        }
+        {
+            let table = PrimaryKeyMoveWorkTable::load_from_file(config.clone())
+                .await
+                .unwrap();
+            assert_eq!(table.select_all().execute().unwrap().len(), 20);
+
+            assert!(table.select(300).is_none());
+            assert!(table.select(7).is_none());
+            assert_eq!(table.select(301).unwrap().email, "user3@mail.com");
+            assert_eq!(table.select(3).unwrap().email, "user7@mail.com");
+            assert_eq!(table.select(8).unwrap().email, "user8@mail.com");
+            assert_eq!(
+                table
+                    .select_by_email("user3@mail.com".to_string())
+                    .unwrap()
+                    .id,
+                301
+            );
+            let group = table.select_by_group(1).execute().unwrap();
+            assert_eq!(group.len(), 2);
+            assert!(group.iter().any(|r| r.id == 301));
+            assert!(group.iter().any(|r| r.id == 2));
+            let group = table.select_by_group(3).execute().unwrap();
+            assert_eq!(group.len(), 2);
+            assert!(
+                group
+                    .iter()
+                    .any(|r| r.id == 3 && r.email == "user7@mail.com")
+            );
+        }
+    })
+}
@@ -0,0 +1,80 @@
+use crate::remove_dir_if_exists;
+
+use worktable::prelude::*;
+use worktable::worktable;
+
+worktable!(
+    name: SpatialIndexReRead,
+    persist: true,
+    columns: {
+        id: u64 primary_key autoincrement,
+        lat: f64,
+        lon: f64,
+    },
+    indexes: {
+        location_idx: spatial(lat, lon),
+    },
+    queries: {
+        update: {
+            LonById(lon) by id,
+        },
+    }
+);
+
+#[test]
+fn test_spatial_index_re_read() {
+    let config = PersistenceConfig::new(
+        "tests/data/spatial_index/reread",
+        "tests/data/spatial_index/reread",
+    );
+
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .worker_threads(2)
+        .enable_io()
+        .enable_time()
+        .build()
+        .unwrap();
+
+    runtime.block_on(async {
+        remove_dir_if_exists("tests/data/spatial_index/reread".to_string()).await;
+
+        {
+            let table = SpatialIndexReReadWorkTable::load_from_file(config.clone())
+                .await
+                .unwrap();
+            for i in 0..100 {
+                table
+                    .insert(SpatialIndexReReadRow {
+                        id: table.get_next_pk().into(),
+                        lat: (i / 10) as f64,
+                        lon: (i % 10) as f64,
+                    })
+                    .unwrap();
+            }
+            table
+                .update_lon_by_id(
+                    LonByIdQuery { lon: 100.0 },
+                    SpatialIndexReReadPrimaryKey::from(55),
+                )
+                .await
+                .unwrap();
+            table.wait_for_ops().await
+        }
+        {
+            let table = SpatialIndexReReadWorkTable::load_from_file(config.clone())
+                .await
+                .unwrap();
+            assert_eq!(table.select_all().execute().unwrap().len(), 100);
+
+            let rows = table
+                .select_within_box((5.0, 4.0), (5.0, 6.0))
+                .execute()
+                .unwrap();
+            let mut ids: Vec<_> = rows.into_iter().map(|r| r.id).collect();
+            ids.sort();
+            assert_eq!(ids, vec![54, 56]);
+            let rows = table.select_nearest((5.0, 90.0), 1).execute().unwrap();
+            assert_eq!(rows[0].id, 55);
+        }
+    })
+}
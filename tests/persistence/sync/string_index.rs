@@ -0,0 +1,87 @@
+use crate::remove_dir_if_exists;
+
+use worktable::prelude::*;
+use worktable::worktable;
+
+worktable!(
+    name: StringIndexReRead,
+    persist: true,
+    columns: {
+        id: u64 primary_key autoincrement,
+        symbol: String,
+    },
+    indexes: {
+        symbol_idx: symbol unique prefix ci,
+    },
+    queries: {
+        update: {
+            SymbolById(symbol) by id,
+        },
+    }
+);
+
+#[test]
+fn test_string_index_re_read() {
+    let config = PersistenceConfig::new(
+        "tests/data/string_index/reread",
+        "tests/data/string_index/reread",
+    );
+
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .worker_threads(2)
+        .enable_io()
+        .enable_time()
+        .build()
+        .unwrap();
+
+    runtime.block_on(async {
+        remove_dir_if_exists("tests/data/string_index/reread".to_string()).await;
+
+        {
+            let table = StringIndexReReadWorkTable::load_from_file(config.clone())
+                .await
+                .unwrap();
+            for i in 0..100 {
+                let symbol = if i % 2 == 0 {
+                    format!("BTC{i}")
+                } else {
+                    format!("eth{i}")
+                };
+                table
+                    .insert(StringIndexReReadRow {
+                        id: table.get_next_pk().into(),
+                        symbol,
+                    })
+                    .unwrap();
+            }
+            table
+                .update_symbol_by_id(
+                    SymbolByIdQuery {
+                        symbol: "btc1".to_string(),
+                    },
+                    StringIndexReReadPrimaryKey::from(1),
+                )
+                .await
+                .unwrap();
+            table.delete(2.into()).await.unwrap();
+            table.wait_for_ops().await
+        }
+        {
+            let table = StringIndexReReadWorkTable::load_from_file(config.clone())
+                .await
+                .unwrap();
+            assert_eq!(table.select_all().execute().unwrap().len(), 99);
+
+            let row = table.select_by_symbol("Btc42".to_string()).unwrap();
+            assert_eq!(row.symbol, "BTC42");
+            assert!(table.select_by_symbol("btc2".to_string()).is_none());
+            assert_eq!(table.select_by_symbol("BTC1".to_string()).unwrap().id, 1);
+            assert!(!table.exists_by_symbol("eth1".to_string()));
+
+            let rows = table.select_by_symbol_prefix("btc").execute().unwrap();
+            assert_eq!(rows.len(), 50);
+            let rows = table.select_by_symbol_prefix("ETH9").execute().unwrap();
+            assert_eq!(rows.len(), 6);
+        }
+    })
+}
@@ -0,0 +1,82 @@
+use std::net::Ipv4Addr;
+
+use crate::remove_dir_if_exists;
+
+use worktable::prelude::*;
+use worktable::worktable;
+
+worktable!(
+    name: TypePathReRead,
+    persist: true,
+    columns: {
+        id: u64 primary_key autoincrement,
+        hash: [u8; 32],
+        addr: std::net::Ipv4Addr,
+        payload: Vec<u8>,
+        comment: Option<String>,
+    },
+    indexes: {
+        hash_idx: hash unique,
+    },
+    queries: {
+        update: {
+            CommentByHash(comment) by hash,
+        },
+    }
+);
+
+#[test]
+fn test_type_path_re_read() {
+    let config =
+        PersistenceConfig::new("tests/data/type_path/reread", "tests/data/type_path/reread");
+
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .worker_threads(2)
+        .enable_io()
+        .enable_time()
+        .build()
+        .unwrap();
+
+    runtime.block_on(async {
+        remove_dir_if_exists("tests/data/type_path/reread".to_string()).await;
+
+        {
+            let table = TypePathReReadWorkTable::load_from_file(config.clone())
+                .await
+                .unwrap();
+            for i in 0..100u8 {
+                table
+                    .insert(TypePathReReadRow {
+                        id: table.get_next_pk().into(),
+                        hash: [i; 32],
+                        addr: Ipv4Addr::new(10, 0, 0, i),
+                        payload: vec![i; i as usize],
+                        comment: None,
+                    })
+                    .unwrap();
+            }
+            table
+                .update_comment_by_hash(
+                    CommentByHashQuery {
+                        comment: Some("updated".to_string()),
+                    },
+                    [42; 32],
+                )
+                .await
+                .unwrap();
+            table.wait_for_ops().await
+        }
+        {
+            let table = TypePathReReadWorkTable::load_from_file(config.clone())
+                .await
+                .unwrap();
+            assert_eq!(table.select_all().execute().unwrap().len(), 100);
+
+            let row = table.select_by_hash([42; 32]).unwrap();
+            assert_eq!(row.addr, Ipv4Addr::new(10, 0, 0, 42));
+            assert_eq!(row.payload, vec![42; 42]);
+            assert_eq!(row.comment, Some("updated".to_string()));
+            assert_eq!(table.select_by_hash([7; 32]).unwrap().comment, None);
+        }
+    })
+}
@@ -0,0 +1,129 @@
+use rkyv::{Archive, Deserialize, Serialize};
+
+use crate::remove_dir_if_exists;
+
+use worktable::prelude::*;
+use worktable::worktable;
+
+#[derive(
+    Archive,
+    Clone,
+    Debug,
+    Default,
+    Deserialize,
+    Eq,
+    Hash,
+    MemStat,
+    Ord,
+    PartialEq,
+    PartialOrd,
+    Serialize,
+    VariableSizeMeasure,
+)]
+#[rkyv(compare(PartialEq), derive(Debug, PartialEq, Eq, PartialOrd, Ord))]
+pub struct Tag {
+    group: String,
+    value: String,
+}
+
+// Unsized index pages need exact archived size of the key.
+impl SizeMeasurable for Tag {
+    fn aligned_size(&self) -> usize {
+        rkyv::to_bytes::<rkyv::rancor::Error>(self).unwrap().len()
+    }
+}
+
+impl ArchivedSize for ArchivedTag {
+    fn archived_size(&self) -> usize {
+        self.group.archived_size() + self.value.archived_size()
+    }
+}
+
+worktable!(
+    name: VariableSizeReRead,
+    persist: true,
+    columns: {
+        id: u64 primary_key autoincrement,
+        tag: Tag unsized,
+        payload: Vec<u8>,
+    },
+    indexes: {
+        tag_idx: tag unique,
+    },
+    queries: {
+        update: {
+            PayloadById(payload) by id,
+            TagById(tag) by id,
+        },
+    }
+);
+
+fn tag(group: u64, value: u64) -> Tag {
+    Tag {
+        group: format!("group_{group}"),
+        value: format!("some long enough value {value}"),
+    }
+}
+
+#[test]
+fn test_variable_size_re_read() {
+    let config = PersistenceConfig::new(
+        "tests/data/variable_size/reread",
+        "tests/data/variable_size/reread",
+    );
+
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .worker_threads(2)
+        .enable_io()
+        .enable_time()
+        .build()
+        .unwrap();
+
+    runtime.block_on(async {
+        remove_dir_if_exists("tests/data/variable_size/reread".to_string()).await;
+
+        {
+            let table = VariableSizeReReadWorkTable::load_from_file(config.clone())
+                .await
+                .unwrap();
+            for i in 0..500 {
+                table
+                    .insert(VariableSizeReReadRow {
+                        id: table.get_next_pk().into(),
+                        tag: tag(i, i),
+                        payload: vec![i as u8; (i % 16) as usize],
+                    })
+                    .unwrap();
+            }
+            table
+                .update_payload_by_id(
+                    PayloadByIdQuery {
+                        payload: vec![1; 100],
+                    },
+                    VariableSizeReReadPrimaryKey::from(10),
+                )
+                .await
+                .unwrap();
+            table
+                .update_tag_by_id(
+                    TagByIdQuery {
+                        tag: tag(1000, 1000),
+                    },
+                    VariableSizeReReadPrimaryKey::from(20),
+                )
+                .await
+                .unwrap();
+            table.wait_for_ops().await
+        }
+        {
+            let table = VariableSizeReReadWorkTable::load_from_file(config.clone())
+                .await
+                .unwrap();
+            assert_eq!(table.select_all().execute().unwrap().len(), 500);
+            assert_eq!(table.select(10).unwrap().payload, vec![1; 100]);
+            assert_eq!(table.select_by_tag(tag(1000, 1000)).unwrap().id, 20);
+            assert!(table.select_by_tag(tag(20, 20)).is_none());
+            assert_eq!(table.select_by_tag(tag(499, 499)).unwrap().id, 499);
+        }
+    })
+}
@@ -0,0 +1,280 @@
+use worktable::prelude::*;
+use worktable::worktable;
+
+worktable!(
+    name: Test,
+    columns: {
+        id: u64 primary_key autoincrement,
+        exchange: String,
+        qty: u64,
+        price: f64,
+        account: u32,
+    },
+    indexes: {
+        exchange_idx: exchange,
+        account_idx: account unique,
+    }
+);
+
+fn fill_table() -> TestWorkTable {
+    let table = TestWorkTable::default();
+    for (i, (exchange, qty, price)) in [
+        ("A", 10, 1.0),
+        ("B", 20, 2.0),
+        ("A", 30, 3.0),
+        ("C", 40, 4.0),
+        ("B", 50, 5.0),
+    ]
+    .into_iter()
+    .enumerate()
+    {
+        table
+            .insert(TestRow {
+                id: table.get_next_pk().into(),
+                exchange: exchange.to_string(),
+                qty,
+                price,
+                account: i as u32,
+            })
+            .unwrap();
+    }
+    table
+}
+
+#[test]
+fn aggregates() {
+    let table = fill_table();
+
+    assert_eq!(table.select_all().count().unwrap(), 5);
+    assert_eq!(
+        table.select_all().sum(TestRowFields::Qty).unwrap(),
+        TestColumnValue::Qty(150)
+    );
+    assert_eq!(
+        table.select_all().min(TestRowFields::Price).unwrap(),
+        Some(TestColumnValue::Price(1.0))
+    );
+    assert_eq!(
+        table.select_all().max(TestRowFields::Exchange).unwrap(),
+        Some(TestColumnValue::Exchange("C".to_string()))
+    );
+    assert_eq!(
+        table.select_all().avg(TestRowFields::Price).unwrap(),
+        Some(3.0)
+    );
+    assert_eq!(
+        table
+            .select_all()
+            .where_by(|r| r.exchange == "A")
+            .sum(TestRowFields::Price)
+            .unwrap(),
+        TestColumnValue::Price(4.0)
+    );
+    assert_eq!(
+        table.select_all().limit(2).avg(TestRowFields::Qty).unwrap(),
+        Some(15.0)
+    );
+}
+
+#[test]
+fn aggregates_empty() {
+    let table = TestWorkTable::default();
+
+    assert_eq!(table.select_all().count().unwrap(), 0);
+    assert_eq!(
+        table.select_all().sum(TestRowFields::Qty).unwrap(),
+        TestColumnValue::Qty(0)
+    );
+    assert_eq!(table.select_all().max(TestRowFields::Qty).unwrap(), None);
+    assert_eq!(table.select_all().avg(TestRowFields::Qty).unwrap(), None);
+}
+
+#[test]
+fn aggregates_not_numeric() {
+    let table = fill_table();
+
+    assert!(matches!(
+        table.select_all().sum(TestRowFields::Exchange),
+        Err(WorkTableError::NotNumericColumn(_))
+    ));
+    assert!(matches!(
+        table
+            .select_all()
+            .group_by(TestRowFields::Qty)
+            .avg(TestRowFields::Exchange),
+        Err(WorkTableError::NotNumericColumn(_))
+    ));
+}
+
+#[test]
+fn group_by_aggregates() {
+    let table = fill_table();
+
+    assert_eq!(
+        table
+            .select_all()
+            .group_by(TestRowFields::Exchange)
+            .count()
+            .unwrap(),
+        vec![
+            (TestColumnValue::Exchange("A".to_string()), 2),
+            (TestColumnValue::Exchange("B".to_string()), 2),
+            (TestColumnValue::Exchange("C".to_string()), 1),
+        ]
+    );
+    assert_eq!(
+        table
+            .select_all()
+            .group_by(TestRowFields::Exchange)
+            .sum(TestRowFields::Qty)
+            .unwrap(),
+        vec![
+            (
+                TestColumnValue::Exchange("A".to_string()),
+                TestColumnValue::Qty(40)
+            ),
+            (
+                TestColumnValue::Exchange("B".to_string()),
+                TestColumnValue::Qty(70)
+            ),
+            (
+                TestColumnValue::Exchange("C".to_string()),
+                TestColumnValue::Qty(40)
+            ),
+        ]
+    );
+    assert_eq!(
+        table
+            .select_all()
+            .group_by(TestRowFields::Exchange)
+            .max(TestRowFields::Price)
+            .unwrap(),
+        vec![
+            (
+                TestColumnValue::Exchange("A".to_string()),
+                TestColumnValue::Price(3.0)
+            ),
+            (
+                TestColumnValue::Exchange("B".to_string()),
+                TestColumnValue::Price(5.0)
+            ),
+            (
+                TestColumnValue::Exchange("C".to_string()),
+                TestColumnValue::Price(4.0)
+            ),
+        ]
+    );
+    assert_eq!(
+        table
+            .select_all()
+            .group_by(TestRowFields::Exchange)
+            .avg(TestRowFields::Qty)
+            .unwrap(),
+        vec![
+            (TestColumnValue::Exchange("A".to_string()), 20.0),
+            (TestColumnValue::Exchange("B".to_string()), 35.0),
+            (TestColumnValue::Exchange("C".to_string()), 40.0),
+        ]
+    );
+}
+
+#[test]
+fn group_by_nan() {
+    let table = TestWorkTable::default();
+    for (i, price) in [f64::NAN, 1.0, f64::NAN, 1.0, 2.0, f64::NAN]
+        .into_iter()
+        .enumerate()
+    {
+        table
+            .insert(TestRow {
+                id: table.get_next_pk().into(),
+                exchange: "A".to_string(),
+                qty: i as u64,
+                price,
+                account: i as u32,
+            })
+            .unwrap();
+    }
+
+    let groups = table
+        .select_all()
+        .group_by(TestRowFields::Price)
+        .sum(TestRowFields::Qty)
+        .unwrap();
+    assert_eq!(groups.len(), 3);
+    assert_eq!(
+        groups[..2],
+        [
+            (TestColumnValue::Price(1.0), TestColumnValue::Qty(4)),
+            (TestColumnValue::Price(2.0), TestColumnValue::Qty(4)),
+        ]
+    );
+    assert!(matches!(
+        groups[2],
+        (TestColumnValue::Price(p), TestColumnValue::Qty(7)) if p.is_nan()
+    ));
+}
+
+#[test]
+fn count_grouped_by() {
+    let table = fill_table();
+
+    let by_index = table.count_grouped_by(TestRowFields::Exchange).unwrap();
+    let by_rows = table
+        .select_all()
+        .group_by(TestRowFields::Exchange)
+        .count()
+        .unwrap();
+    assert_eq!(by_index, by_rows);
+
+    let by_unique = table.count_grouped_by(TestRowFields::Account).unwrap();
+    assert_eq!(by_unique.len(), 5);
+    assert!(by_unique.iter().all(|(_, count)| *count == 1));
+
+    let not_indexed = table.count_grouped_by(TestRowFields::Price).unwrap();
+    assert_eq!(not_indexed.len(), 5);
+}
+
+worktable!(
+    name: Small,
+    columns: {
+        id: u64 primary_key autoincrement,
+        level: u8,
+        delta: i16,
+    }
+);
+
+#[test]
+fn sum_overflow() {
+    let table = SmallWorkTable::default();
+    for _ in 0..3 {
+        table
+            .insert(SmallRow {
+                id: table.get_next_pk().into(),
+                level: 100,
+                delta: -20000,
+            })
+            .unwrap();
+    }
+
+    assert!(matches!(
+        table.select_all().sum(SmallRowFields::Level),
+        Err(WorkTableError::SumOverflow(_))
+    ));
+    assert!(matches!(
+        table.select_all().sum(SmallRowFields::Delta),
+        Err(WorkTableError::SumOverflow(_))
+    ));
+    assert_eq!(
+        table
+            .select_all()
+            .limit(2)
+            .sum(SmallRowFields::Level)
+            .unwrap(),
+        SmallColumnValue::Level(200)
+    );
+    assert_eq!(
+        table.select_all().avg(SmallRowFields::Level).unwrap(),
+        Some(100.0)
+    );
+}
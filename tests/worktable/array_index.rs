@@ -0,0 +1,206 @@
+use worktable::prelude::*;
+use worktable::worktable;
+
+worktable!(
+    name: Tagged,
+    columns: {
+        id: u64 primary_key autoincrement,
+        value: u64,
+        tags: Vec<String>,
+    },
+    indexes: {
+        value_idx: value unique,
+        tags_idx: tags array,
+    },
+    queries: {
+        update: {
+            TagsById(tags) by id,
+        },
+        delete: {
+            ByValue() by value,
+        }
+    }
+);
+
+fn tags(tags: &[&str]) -> Vec<String> {
+    tags.iter().map(|t| t.to_string()).collect()
+}
+
+fn ids(rows: Vec<TaggedRow>) -> Vec<u64> {
+    let mut ids: Vec<_> = rows.into_iter().map(|r| r.id).collect();
+    ids.sort();
+    ids
+}
+
+#[test]
+fn select_by_element() {
+    let table = TaggedWorkTable::default();
+    for (value, t) in [&["a", "b"][..], &["b", "c"], &[], &["a", "a"]]
+        .into_iter()
+        .enumerate()
+    {
+        table
+            .insert(TaggedRow {
+                id: table.get_next_pk().into(),
+                value: value as u64,
+                tags: tags(t),
+            })
+            .unwrap();
+    }
+
+    let rows = table.select_by_tags_contains("a".to_string()).execute();
+    assert_eq!(ids(rows.unwrap()), vec![0, 3]);
+    let rows = table.select_by_tags_contains("b".to_string()).execute();
+    assert_eq!(ids(rows.unwrap()), vec![0, 1]);
+    let rows = table.select_by_tags_contains("d".to_string()).execute();
+    assert!(rows.unwrap().is_empty());
+
+    assert_eq!(table.count_by_tags_contains("c".to_string()), 1);
+    assert!(table.exists_by_tags_contains("a".to_string()));
+    assert!(!table.exists_by_tags_contains("d".to_string()));
+}
+
+#[tokio::test]
+async fn update_elements() {
+    let table = TaggedWorkTable::default();
+    let pk = table
+        .insert(TaggedRow {
+            id: table.get_next_pk().into(),
+            value: 1,
+            tags: tags(&["a", "b"]),
+        })
+        .unwrap();
+
+    table
+        .update_tags_by_id(
+            TagsByIdQuery {
+                tags: tags(&["b", "c", "c"]),
+            },
+            pk.clone(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(table.count_by_tags_contains("a".to_string()), 0);
+    assert_eq!(table.count_by_tags_contains("b".to_string()), 1);
+    assert_eq!(table.count_by_tags_contains("c".to_string()), 1);
+
+    table
+        .update(TaggedRow {
+            id: pk.into(),
+            value: 1,
+            tags: tags(&["d"]),
+        })
+        .await
+        .unwrap();
+
+    assert!(!table.exists_by_tags_contains("b".to_string()));
+    assert!(!table.exists_by_tags_contains("c".to_string()));
+    let rows = table.select_by_tags_contains("d".to_string()).execute();
+    assert_eq!(ids(rows.unwrap()), vec![0]);
+}
+
+#[tokio::test]
+async fn failed_update_keeps_elements() {
+    let table = TaggedWorkTable::default();
+    for value in 0..2 {
+        table
+            .insert(TaggedRow {
+                id: table.get_next_pk().into(),
+                value,
+                tags: tags(&["a"]),
+            })
+            .unwrap();
+    }
+
+    let res = table
+        .update(TaggedRow {
+            id: 0,
+            value: 1,
+            tags: tags(&["b"]),
+        })
+        .await;
+    assert!(res.is_err());
+
+    assert!(!table.exists_by_tags_contains("b".to_string()));
+    let rows = table.select_by_tags_contains("a".to_string()).execute();
+    assert_eq!(ids(rows.unwrap()), vec![0, 1]);
+}
+
+#[tokio::test]
+async fn delete_removes_elements() {
+    let table = TaggedWorkTable::default();
+    for value in 0..3 {
+        table
+            .insert(TaggedRow {
+                id: table.get_next_pk().into(),
+                value,
+                tags: tags(&["a", "b"]),
+            })
+            .unwrap();
+    }
+
+    table.delete(1.into()).await.unwrap();
+    table.delete_by_value(2).await.unwrap();
+
+    let rows = table.select_by_tags_contains("b".to_string()).execute();
+    assert_eq!(ids(rows.unwrap()), vec![0]);
+    assert_eq!(table.count_by_tags_contains("a".to_string()), 1);
+}
+
+worktable!(
+    name: Codes,
+    columns: {
+        id: u64 primary_key autoincrement,
+        codes: [u16; 3],
+    },
+    indexes: {
+        codes_idx: codes array,
+    },
+    queries: {
+        update: {
+            CodesById(codes) by id,
+        },
+    }
+);
+
+#[tokio::test]
+async fn update_sized_array_in_place() {
+    let table = CodesWorkTable::default();
+    let pk = table
+        .insert(CodesRow {
+            id: table.get_next_pk().into(),
+            codes: [1, 2, 2],
+        })
+        .unwrap();
+    table
+        .insert(CodesRow {
+            id: table.get_next_pk().into(),
+            codes: [2, 3, 4],
+        })
+        .unwrap();
+    let link = table.0.pk_map.get(&pk).unwrap().get().value;
+
+    table
+        .update_codes_by_id(CodesByIdQuery { codes: [2, 5, 5] }, pk.clone())
+        .await
+        .unwrap();
+
+    assert_eq!(table.0.pk_map.get(&pk).unwrap().get().value, link);
+    assert_eq!(table.count_by_codes_contains(1), 0);
+    assert_eq!(table.count_by_codes_contains(2), 2);
+    assert_eq!(table.count_by_codes_contains(5), 1);
+
+    table
+        .update(CodesRow {
+            id: pk.into(),
+            codes: [3, 3, 3],
+        })
+        .await
+        .unwrap();
+
+    assert_eq!(table.count_by_codes_contains(2), 1);
+    assert_eq!(table.count_by_codes_contains(5), 0);
+    let rows = table.select_by_codes_contains(3).execute().unwrap();
+    assert_eq!(rows.len(), 2);
+}
@@ -788,6 +788,58 @@ fn select_all_where_by_eq_string_number_test() {
     assert_eq!(equal.len(), 2);
 }
 
+#[test]
+fn select_where_archived_test() {
+    let table = TestWorkTable::default();
+
+    let row1 = TestRow {
+        id: table.get_next_pk().into(),
+        test: 3,
+        another: 1,
+        exchange: "M1".to_string(),
+    };
+    let row2 = TestRow {
+        id: table.get_next_pk().into(),
+        test: 1,
+        another: 2,
+        exchange: "N1".to_string(),
+    };
+    let row3 = TestRow {
+        id: table.get_next_pk().into(),
+        test: 2,
+        another: 1,
+        exchange: "P1".to_string(),
+    };
+
+    let _ = table.insert(row1.clone()).unwrap();
+    let _ = table.insert(row2.clone()).unwrap();
+    let _ = table.insert(row3.clone()).unwrap();
+
+    let equal = table
+        .select_all()
+        .where_archived(|row| row.exchange.as_str() == "P1")
+        .execute()
+        .unwrap();
+    assert_eq!(equal, vec![row3.clone()]);
+
+    let ordered = table
+        .select_all()
+        .where_archived(|row| row.another == 1)
+        .order_on(TestRowFields::Test, Order::Desc)
+        .execute()
+        .unwrap();
+    assert_eq!(ordered, vec![row1.clone(), row3.clone()]);
+
+    let chained = table
+        .select_by_another(1)
+        .where_archived(|row| row.test > 1)
+        .where_archived(|row| row.exchange.as_str() != "M1")
+        .where_by(|row| row.id > 0)
+        .execute()
+        .unwrap();
+    assert_eq!(chained, vec![row3]);
+}
+
 #[test]
 fn select_all_order_multiple_test() {
     let table = TestWorkTable::default();
@@ -1007,6 +1059,34 @@ fn select_all_order_two_test() {
     assert_eq!(&all[2].test, &3)
 }
 
+#[test]
+fn select_all_order_multiple_with_limit_test() {
+    let table = TestWorkTable::default();
+    for i in 0..1000 {
+        let row = TestRow {
+            id: table.get_next_pk().into(),
+            test: i,
+            another: (i * 7919 % 13) as u64,
+            exchange: format!("test_{}", i % 5),
+        };
+        let _ = table.insert(row).unwrap();
+    }
+
+    let mut expected = table.select_all().execute().unwrap();
+    expected.sort_by(|a, b| b.another.cmp(&a.another).then(a.exchange.cmp(&b.exchange)));
+
+    let all = table
+        .select_all()
+        .order_on(TestRowFields::Another, Order::Desc)
+        .order_on(TestRowFields::Exchange, Order::Asc)
+        .offset(15)
+        .limit(20)
+        .execute()
+        .unwrap();
+
+    assert_eq!(all, expected[15..35].to_vec());
+}
+
 #[test]
 fn select_by_order_on_test() {
     let table = TestWorkTable::default();
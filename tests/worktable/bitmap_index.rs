@@ -0,0 +1,216 @@
+use rkyv::{Archive, Deserialize, Serialize};
+use worktable::prelude::*;
+use worktable::worktable;
+
+#[derive(Archive, Clone, Copy, Debug, Deserialize, Serialize, PartialEq, PartialOrd, MemStat)]
+#[rkyv(compare(PartialEq), derive(Debug))]
+pub enum Status {
+    Open,
+    Filled,
+    Cancelled,
+}
+
+#[derive(Archive, Clone, Copy, Debug, Deserialize, Serialize, PartialEq, PartialOrd, MemStat)]
+#[rkyv(compare(PartialEq), derive(Debug))]
+pub enum Side {
+    Buy,
+    Sell,
+}
+
+worktable!(
+    name: Order,
+    columns: {
+        id: u64 primary_key autoincrement,
+        status: Status,
+        side: Side,
+        venue: u8,
+        comment: String,
+    },
+    indexes: {
+        status_idx: status bitmap,
+        side_idx: side bitmap,
+        venue_idx: venue bitmap,
+    },
+    queries: {
+        update: {
+            StatusById(status) by id,
+            CommentByStatus(comment) by status,
+        },
+        delete: {
+            BySide() by side,
+        }
+    }
+);
+
+fn ids(rows: Vec<OrderRow>) -> Vec<u64> {
+    let mut ids: Vec<_> = rows.into_iter().map(|r| r.id).collect();
+    ids.sort();
+    ids
+}
+
+fn fill(table: &OrderWorkTable) {
+    for i in 0..30u64 {
+        let status = match i % 3 {
+            0 => Status::Open,
+            1 => Status::Filled,
+            _ => Status::Cancelled,
+        };
+        let side = if i % 2 == 0 { Side::Buy } else { Side::Sell };
+        table
+            .insert(OrderRow {
+                id: table.get_next_pk().into(),
+                status,
+                side,
+                venue: (i % 5) as u8,
+                comment: String::new(),
+            })
+            .unwrap();
+    }
+}
+
+#[test]
+fn select_by_bitmap_index() {
+    let table = OrderWorkTable::default();
+    fill(&table);
+
+    let rows = table.select_by_status(Status::Open).execute().unwrap();
+    assert_eq!(rows.len(), 10);
+    assert!(rows.iter().all(|r| r.status == Status::Open));
+    assert_eq!(table.count_by_side(Side::Sell), 15);
+    assert_eq!(table.count_by_venue(4), 6);
+    assert!(table.exists_by_status(Status::Cancelled));
+    assert!(!table.exists_by_venue(7));
+}
+
+#[test]
+fn select_by_bitmaps_intersection() {
+    let table = OrderWorkTable::default();
+    fill(&table);
+
+    let filter = OrderBitmapFilter {
+        status: Some(Status::Open),
+        side: Some(Side::Buy),
+        venue: None,
+    };
+    assert_eq!(table.count_by_bitmaps(filter.clone()), 5);
+    let rows = table.select_by_bitmaps(filter.clone()).execute().unwrap();
+    assert_eq!(ids(rows), vec![0, 6, 12, 18, 24]);
+
+    let filter = OrderBitmapFilter {
+        status: Some(Status::Open),
+        side: Some(Side::Buy),
+        venue: Some(2),
+    };
+    let rows = table.select_by_bitmaps(filter).execute().unwrap();
+    assert_eq!(ids(rows), vec![12]);
+
+    let rows = table
+        .select_by_bitmaps(OrderBitmapFilter::default())
+        .limit(3)
+        .execute()
+        .unwrap();
+    assert_eq!(rows.len(), 3);
+    assert_eq!(table.count_by_bitmaps(OrderBitmapFilter::default()), 30);
+
+    let plan = table
+        .select_by_bitmaps(OrderBitmapFilter {
+            status: Some(Status::Filled),
+            side: Some(Side::Sell),
+            venue: None,
+        })
+        .explain()
+        .unwrap();
+    assert_eq!(
+        plan.access_path,
+        AccessPath::BitmapIntersection(vec!["side_idx", "status_idx"])
+    );
+    assert_eq!(plan.estimated_rows, Some(5));
+    assert_eq!(plan.actual_rows, 5);
+}
+
+#[tokio::test]
+async fn bitmaps_follow_updates() {
+    let table = OrderWorkTable::default();
+    fill(&table);
+
+    table
+        .update_status_by_id(
+            StatusByIdQuery {
+                status: Status::Filled,
+            },
+            OrderPrimaryKey::from(0),
+        )
+        .await
+        .unwrap();
+    assert_eq!(table.count_by_status(Status::Open), 9);
+    assert_eq!(table.count_by_status(Status::Filled), 11);
+    // Row is updated in place, so it keeps its link.
+    let rows = table.select_by_status(Status::Filled).execute().unwrap();
+    assert_eq!(rows.len(), 11);
+    assert!(rows.iter().any(|r| r.id == 0));
+    let filter = OrderBitmapFilter {
+        status: Some(Status::Filled),
+        side: Some(Side::Buy),
+        venue: None,
+    };
+    let rows = table.select_by_bitmaps(filter).execute().unwrap();
+    assert_eq!(ids(rows), vec![0, 4, 10, 16, 22, 28]);
+    let filter = OrderBitmapFilter {
+        status: None,
+        side: None,
+        venue: Some(0),
+    };
+    let rows = table.select_by_bitmaps(filter).execute().unwrap();
+    assert_eq!(ids(rows), vec![0, 5, 10, 15, 20, 25]);
+
+    table
+        .update(OrderRow {
+            id: 1,
+            status: Status::Open,
+            side: Side::Buy,
+            venue: 0,
+            comment: "moved".to_string(),
+        })
+        .await
+        .unwrap();
+    let filter = OrderBitmapFilter {
+        status: Some(Status::Open),
+        side: Some(Side::Buy),
+        venue: Some(0),
+    };
+    let rows = table.select_by_bitmaps(filter).execute().unwrap();
+    assert_eq!(ids(rows), vec![1]);
+
+    table
+        .update_comment_by_status(
+            CommentByStatusQuery {
+                comment: "done".to_string(),
+            },
+            Status::Filled,
+        )
+        .await
+        .unwrap();
+    let rows = table.select_by_status(Status::Filled).execute().unwrap();
+    assert!(rows.iter().all(|r| r.comment == "done"));
+
+    table.delete_by_side(Side::Sell).await.unwrap();
+    assert_eq!(table.count_by_side(Side::Sell), 0);
+    assert_eq!(table.count_by_bitmaps(OrderBitmapFilter::default()), 16);
+    assert_eq!(table.count_by_status(Status::Open), 5);
+}
+
+#[test]
+fn bitmap_index_info() {
+    let table = OrderWorkTable::default();
+    fill(&table);
+
+    let info = table.system_info();
+    let status = info
+        .indexes_info
+        .iter()
+        .find(|i| i.name == "status_idx")
+        .unwrap();
+    assert!(matches!(status.index_type, IndexKind::Bitmap));
+    assert_eq!(status.key_count, 30);
+    assert_eq!(status.node_count, 3);
+}
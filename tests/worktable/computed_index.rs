@@ -0,0 +1,157 @@
+use worktable::prelude::*;
+use worktable::worktable;
+
+worktable!(
+    name: Trade,
+    columns: {
+        id: u64 primary_key autoincrement,
+        email: String,
+        price: f64,
+        qty: f64,
+        comment: String,
+    },
+    indexes: {
+        notional_idx: expr(price * qty) as f64,
+        lower_email_idx: expr(email.to_lowercase()) unique,
+    },
+    queries: {
+        update: {
+            PriceById(price) by id,
+            EmailById(email) by id,
+            CommentById(comment) by id,
+        },
+    }
+);
+
+fn fill(table: &TradeWorkTable) {
+    for i in 0..10u64 {
+        table
+            .insert(TradeRow {
+                id: table.get_next_pk().into(),
+                email: format!("User{i}@Mail.com"),
+                price: (i % 5) as f64,
+                qty: 2.0,
+                comment: String::new(),
+            })
+            .unwrap();
+    }
+}
+
+#[test]
+fn select_by_computed_key() {
+    let table = TradeWorkTable::default();
+    fill(&table);
+
+    let rows = table.select_by_notional_idx(4.0).execute().unwrap();
+    assert_eq!(rows.len(), 2);
+    assert!(rows.iter().all(|r| r.price == 2.0));
+    assert_eq!(table.count_by_notional_idx(8.0), 2);
+    assert!(!table.exists_by_notional_idx(3.0));
+
+    let row = table
+        .select_by_lower_email_idx("user3@mail.com".to_string())
+        .unwrap();
+    assert_eq!(row.id, 3);
+    assert!(
+        table
+            .select_by_lower_email_idx("User3@Mail.com".to_string())
+            .is_none()
+    );
+
+    let res = table.insert(TradeRow {
+        id: table.get_next_pk().into(),
+        email: "USER3@MAIL.COM".to_string(),
+        price: 1.0,
+        qty: 1.0,
+        comment: String::new(),
+    });
+    assert!(res.is_err());
+}
+
+#[tokio::test]
+async fn computed_key_follows_updates() {
+    let table = TradeWorkTable::default();
+    fill(&table);
+
+    table
+        .update_price_by_id(PriceByIdQuery { price: 10.0 }, TradePrimaryKey::from(0))
+        .await
+        .unwrap();
+    assert_eq!(table.count_by_notional_idx(0.0), 1);
+    assert_eq!(
+        table.select_by_notional_idx(20.0).execute().unwrap()[0].id,
+        0
+    );
+
+    table
+        .update_email_by_id(
+            EmailByIdQuery {
+                email: "Renamed@Mail.com".to_string(),
+            },
+            TradePrimaryKey::from(1),
+        )
+        .await
+        .unwrap();
+    assert!(
+        table
+            .select_by_lower_email_idx("user1@mail.com".to_string())
+            .is_none()
+    );
+    assert_eq!(
+        table
+            .select_by_lower_email_idx("renamed@mail.com".to_string())
+            .unwrap()
+            .id,
+        1
+    );
+
+    // Comment is not used by any index, so keys stay the same.
+    table
+        .update_comment_by_id(
+            CommentByIdQuery {
+                comment: "long enough comment to change row size".to_string(),
+            },
+            TradePrimaryKey::from(2),
+        )
+        .await
+        .unwrap();
+    assert_eq!(table.count_by_notional_idx(4.0), 2);
+    assert_eq!(
+        table
+            .select_by_lower_email_idx("user2@mail.com".to_string())
+            .unwrap()
+            .id,
+        2
+    );
+
+    table
+        .update(TradeRow {
+            id: 3,
+            email: "Other@Mail.com".to_string(),
+            price: 3.0,
+            qty: 5.0,
+            comment: String::new(),
+        })
+        .await
+        .unwrap();
+    assert_eq!(table.count_by_notional_idx(6.0), 1);
+    assert_eq!(
+        table.select_by_notional_idx(15.0).execute().unwrap()[0].id,
+        3
+    );
+    assert_eq!(
+        table
+            .select_by_lower_email_idx("other@mail.com".to_string())
+            .unwrap()
+            .id,
+        3
+    );
+
+    table.delete(3.into()).await.unwrap();
+    assert_eq!(table.count_by_notional_idx(15.0), 0);
+    assert!(
+        table
+            .select_by_lower_email_idx("other@mail.com".to_string())
+            .is_none()
+    );
+}
@@ -69,3 +69,41 @@ async fn count() {
     // Count by WT
     assert_eq!(4, test_table.count());
 }
+
+#[tokio::test]
+async fn count_by_index() {
+    let test_table = TestWorkTable::default();
+
+    for i in 0..10 {
+        let row = TestRow {
+            val: 1,
+            attr1: format!("Attr{}", i % 3),
+            attr2: i,
+            id: i as u64,
+        };
+        test_table.insert(row).unwrap();
+    }
+
+    assert_eq!(test_table.count_by_attr1("Attr0".to_string()), 4);
+    assert_eq!(test_table.count_by_attr1("Attr2".to_string()), 3);
+    assert_eq!(test_table.count_by_attr1("Attr3".to_string()), 0);
+    assert!(test_table.exists_by_attr1("Attr1".to_string()));
+    assert!(!test_table.exists_by_attr1("Attr3".to_string()));
+
+    assert_eq!(test_table.count_by_attr2(5), 1);
+    assert_eq!(test_table.count_by_attr2(50), 0);
+    assert!(test_table.exists_by_attr2(9));
+    assert!(!test_table.exists_by_attr2(10));
+
+    assert_eq!(test_table.count_range_by_attr2(2..5), 3);
+    assert_eq!(test_table.count_range_by_attr2(..=5), 6);
+    assert_eq!(test_table.count_range_by_attr2(8..), 2);
+    assert_eq!(
+        test_table.count_range_by_attr1("Attr0".to_string().."Attr2".to_string()),
+        7
+    );
+
+    assert_eq!(test_table.count_range(0..3), 3);
+    assert_eq!(test_table.count_range(5..), 5);
+    assert_eq!(test_table.count_range::<u64, _>(..), 10);
+}
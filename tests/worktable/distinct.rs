@@ -0,0 +1,133 @@
+use worktable::prelude::*;
+use worktable::worktable;
+
+worktable!(
+    name: Test,
+    columns: {
+        id: u64 primary_key autoincrement,
+        exchange: String,
+        symbol: String,
+        price: f64,
+        seq: u64,
+    },
+    indexes: {
+        exchange_idx: exchange,
+        price_idx: price,
+        seq_idx: seq unique,
+    }
+);
+
+fn fill_table() -> TestWorkTable {
+    let table = TestWorkTable::default();
+    for i in 0..30u64 {
+        table
+            .insert(TestRow {
+                id: table.get_next_pk().into(),
+                exchange: format!("Ex{}", i % 4),
+                symbol: format!("S{}", i % 3),
+                price: (i % 5) as f64,
+                seq: i,
+            })
+            .unwrap();
+    }
+    table
+}
+
+#[test]
+fn distinct_values_by_index() {
+    let table = fill_table();
+
+    assert_eq!(
+        table.distinct_values(TestRowFields::Exchange).unwrap(),
+        (0..4)
+            .map(|i| TestColumnValue::Exchange(format!("Ex{i}")))
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(
+        table.distinct_values(TestRowFields::Price).unwrap(),
+        (0..5)
+            .map(|i| TestColumnValue::Price(i as f64))
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(table.distinct_values(TestRowFields::Seq).unwrap().len(), 30);
+}
+
+#[test]
+fn distinct_values_not_indexed() {
+    let table = fill_table();
+
+    assert_eq!(
+        table.distinct_values(TestRowFields::Symbol).unwrap(),
+        (0..3)
+            .map(|i| TestColumnValue::Symbol(format!("S{i}")))
+            .collect::<Vec<_>>()
+    );
+    assert!(
+        TestWorkTable::default()
+            .distinct_values(TestRowFields::Exchange)
+            .unwrap()
+            .is_empty()
+    );
+}
+
+#[test]
+fn distinct_on_keeps_first_in_order() {
+    let table = fill_table();
+
+    // Latest row per symbol.
+    let rows = table
+        .select_all()
+        .order_on(TestRowFields::Seq, Order::Desc)
+        .distinct_on(TestRowFields::Symbol)
+        .execute()
+        .unwrap();
+    assert_eq!(
+        rows.iter().map(|r| r.seq).collect::<Vec<_>>(),
+        vec![29, 28, 27]
+    );
+
+    let rows = table
+        .select_all()
+        .distinct_on(TestRowFields::Exchange)
+        .limit(2)
+        .execute()
+        .unwrap();
+    assert_eq!(rows.iter().map(|r| r.seq).collect::<Vec<_>>(), vec![0, 1]);
+
+    let rows = table
+        .select_all()
+        .order_on(TestRowFields::Seq, Order::Desc)
+        .distinct_on(TestRowFields::Price)
+        .offset(1)
+        .limit(2)
+        .execute()
+        .unwrap();
+    assert_eq!(rows.iter().map(|r| r.seq).collect::<Vec<_>>(), vec![28, 27]);
+}
+
+#[test]
+fn distinct_on_nan() {
+    let table = fill_table();
+    for seq in 30..32 {
+        table
+            .insert(TestRow {
+                id: table.get_next_pk().into(),
+                exchange: "Ex0".to_string(),
+                symbol: "S0".to_string(),
+                price: f64::NAN,
+                seq,
+            })
+            .unwrap();
+    }
+
+    let rows = table
+        .select_all()
+        .order_on(TestRowFields::Seq, Order::Desc)
+        .distinct_on(TestRowFields::Price)
+        .execute()
+        .unwrap();
+    assert_eq!(
+        rows.iter().map(|r| r.seq).collect::<Vec<_>>(),
+        vec![31, 29, 28, 27, 26, 25]
+    );
+}
@@ -0,0 +1,145 @@
+use worktable::prelude::*;
+use worktable::worktable;
+
+worktable!(
+    name: Test,
+    columns: {
+        id: u64 primary_key autoincrement,
+        exchange: String,
+        account: u64,
+        qty: u64,
+    },
+    indexes: {
+        exchange_idx: exchange,
+        account_idx: account unique,
+    }
+    queries: {
+        update: {
+            QtyById(qty) by id,
+            QtyByExchange(qty) by exchange,
+            QtyByAccount(qty) by account,
+            QtyByAccountAndExchange(qty) by account, exchange,
+            ExchangeByQty(exchange) by qty,
+        },
+        delete: {
+            ByQty() by qty,
+            ByExchange() by exchange,
+        }
+    }
+);
+
+fn fill_table() -> TestWorkTable {
+    let table = TestWorkTable::default();
+    for i in 0..20u64 {
+        table
+            .insert(TestRow {
+                id: table.get_next_pk().into(),
+                exchange: format!("Ex{}", i % 4),
+                account: i,
+                qty: i % 2,
+            })
+            .unwrap();
+    }
+    table
+}
+
+#[test]
+fn explain_select() {
+    let table = fill_table();
+
+    let plan = table.select_all().explain().unwrap();
+    assert_eq!(plan.access_path, AccessPath::FullScan);
+    assert_eq!(plan.estimated_rows, Some(20));
+    assert_eq!(plan.actual_rows, 20);
+    assert_eq!(plan.sort, None);
+
+    let plan = table
+        .select_by_exchange("Ex1".to_string())
+        .where_by(|r| r.qty == 1)
+        .order_on(TestRowFields::Qty, Order::Asc)
+        .limit(2)
+        .explain()
+        .unwrap();
+    assert_eq!(plan.access_path, AccessPath::IndexExact("exchange_idx"));
+    assert_eq!(plan.estimated_rows, Some(5));
+    assert_eq!(plan.actual_rows, 2);
+    assert_eq!(plan.sort, Some(SortStrategy::TopK(2)));
+    assert_eq!(
+        plan.to_string(),
+        "index `exchange_idx` exact, top-2 heap, estimated rows: 5, actual rows: 2"
+    );
+
+    let plan = table.explain_select_by_account(3).unwrap();
+    assert_eq!(plan.access_path, AccessPath::IndexExact("account_idx"));
+    assert_eq!(plan.actual_rows, 1);
+}
+
+#[test]
+fn explain_after() {
+    let table = fill_table();
+
+    let page = table.select_all().execute_page(5).unwrap();
+    let plan = table
+        .after(&page.cursor.unwrap())
+        .unwrap()
+        .explain()
+        .unwrap();
+    assert_eq!(plan.access_path, AccessPath::PrimaryKeyRange);
+    assert_eq!(plan.actual_rows, 15);
+
+    let page = table
+        .select_all()
+        .order_on(TestRowFields::Exchange, Order::Asc)
+        .execute_page(5)
+        .unwrap();
+    let plan = table
+        .after(&page.cursor.unwrap())
+        .unwrap()
+        .explain()
+        .unwrap();
+    assert_eq!(plan.access_path, AccessPath::IndexRange("exchange_idx"));
+    assert_eq!(plan.actual_rows, 15);
+}
+
+#[test]
+fn explain_update_and_delete() {
+    let table = fill_table();
+
+    let plan = table.explain_update_qty_by_id(3).unwrap();
+    assert_eq!(plan.access_path, AccessPath::PrimaryKeyLookup);
+    assert_eq!(plan.actual_rows, 1);
+
+    let plan = table
+        .explain_update_qty_by_exchange("Ex0".to_string())
+        .unwrap();
+    assert_eq!(plan.access_path, AccessPath::IndexExact("exchange_idx"));
+    assert_eq!(plan.actual_rows, 5);
+
+    let plan = table.explain_update_qty_by_account(100).unwrap();
+    assert_eq!(plan.access_path, AccessPath::IndexExact("account_idx"));
+    assert_eq!(plan.actual_rows, 0);
+
+    let plan = table
+        .explain_update_qty_by_account_and_exchange((1, "Ex1".to_string()))
+        .unwrap();
+    assert_eq!(plan.access_path, AccessPath::IndexExact("account_idx"));
+    assert_eq!(plan.estimated_rows, Some(1));
+    assert_eq!(plan.actual_rows, 1);
+    let plan = table
+        .explain_update_qty_by_account_and_exchange((1, "Ex0".to_string()))
+        .unwrap();
+    assert_eq!(plan.actual_rows, 0);
+
+    let plan = table.explain_update_exchange_by_qty(0).unwrap();
+    assert_eq!(plan.access_path, AccessPath::FullScan);
+    assert_eq!(plan.estimated_rows, Some(20));
+    assert_eq!(plan.actual_rows, 10);
+
+    let plan = table.explain_delete_by_qty(1).unwrap();
+    assert_eq!(plan.access_path, AccessPath::AsyncFullScan);
+    assert_eq!(plan.estimated_rows, Some(20));
+    assert_eq!(plan.actual_rows, 10);
+
+    // Explain doesn't modify table.
+    assert_eq!(table.select_all().execute().unwrap().len(), 20);
+}
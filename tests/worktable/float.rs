@@ -89,3 +89,23 @@ fn select_by_another_test() {
     assert_eq!(where_200.len(), 1);
     assert!(where_200.contains(&row3));
 }
+
+#[test]
+fn count_by_float_index_test() {
+    let table = TestFloatWorkTable::default();
+
+    for (test, another) in [(1, 100.0), (2, 200.0), (3, 200.0), (4, 300.0)] {
+        let row = TestFloatRow {
+            id: table.get_next_pk().into(),
+            test,
+            another,
+            exchange: "M".to_string(),
+        };
+        table.insert(row).unwrap();
+    }
+
+    assert_eq!(table.count_by_another(200.0), 2);
+    assert!(table.exists_by_another(300.0));
+    assert!(!table.exists_by_another(400.0));
+    assert_eq!(table.count_range_by_another(150.0..=300.0), 3);
+}
@@ -0,0 +1,235 @@
+use worktable::prelude::*;
+use worktable::worktable;
+
+worktable!(
+    name: Order,
+    columns: {
+        id: u64 primary_key autoincrement,
+        client_order_id: String,
+        account: u64,
+        price: f64,
+        tag: String optional,
+    },
+    indexes: {
+        coid_idx: client_order_id unique hash,
+        account_idx: account hash,
+        price_idx: price hash,
+        tag_idx: tag hash ci,
+    },
+    queries: {
+        update: {
+            PriceByCoid(price) by client_order_id,
+            TagByAccount(tag) by account,
+            AccountById(account) by id,
+            CoidById(client_order_id) by id,
+        },
+        delete: {
+            ByCoid() by client_order_id,
+            ByAccount() by account,
+        }
+    }
+);
+
+fn ids(rows: Vec<OrderRow>) -> Vec<u64> {
+    let mut ids: Vec<_> = rows.into_iter().map(|r| r.id).collect();
+    ids.sort();
+    ids
+}
+
+fn fill(table: &OrderWorkTable) {
+    for (coid, account, price, tag) in [
+        ("a1", 1, 10.0, Some("Fast")),
+        ("a2", 1, 20.0, None),
+        ("b1", 2, 10.0, Some("fast")),
+        ("b2", 2, 30.0, Some("slow")),
+        ("c1", 3, 40.0, None),
+    ] {
+        table
+            .insert(OrderRow {
+                id: table.get_next_pk().into(),
+                client_order_id: coid.to_string(),
+                account,
+                price,
+                tag: tag.map(|t| t.to_string()),
+            })
+            .unwrap();
+    }
+}
+
+#[test]
+fn select_by_hash_index() {
+    let table = OrderWorkTable::default();
+    fill(&table);
+
+    assert_eq!(
+        table
+            .select_by_client_order_id("b2".to_string())
+            .unwrap()
+            .id,
+        3
+    );
+    assert!(table.select_by_client_order_id("x".to_string()).is_none());
+    assert!(table.exists_by_client_order_id("c1".to_string()));
+    assert_eq!(table.count_by_client_order_id("a1".to_string()), 1);
+
+    let rows = table.select_by_account(1).execute().unwrap();
+    assert_eq!(ids(rows), vec![0, 1]);
+    assert_eq!(table.count_by_account(2), 2);
+    assert!(!table.exists_by_account(4));
+
+    let rows = table.select_by_price(10.0).execute().unwrap();
+    assert_eq!(ids(rows), vec![0, 2]);
+
+    let rows = table.select_by_tag("FAST".to_string()).execute().unwrap();
+    assert_eq!(ids(rows), vec![0, 2]);
+    let rows = table.select_by_tag_is_none().execute().unwrap();
+    assert_eq!(ids(rows), vec![1, 4]);
+
+    let res = table.insert(OrderRow {
+        id: table.get_next_pk().into(),
+        client_order_id: "a1".to_string(),
+        account: 5,
+        price: 1.0,
+        tag: None,
+    });
+    assert!(res.is_err());
+    assert!(!table.exists_by_account(5));
+}
+
+#[tokio::test]
+async fn update_and_delete_by_hash_index() {
+    let table = OrderWorkTable::default();
+    fill(&table);
+
+    table
+        .update_price_by_coid(PriceByCoidQuery { price: 50.0 }, "a1".to_string())
+        .await
+        .unwrap();
+    assert_eq!(table.count_by_price(10.0), 1);
+    assert_eq!(table.select_by_price(50.0).execute().unwrap()[0].id, 0);
+
+    table
+        .update_tag_by_account(
+            TagByAccountQuery {
+                tag: Some("Slow".to_string()),
+            },
+            2,
+        )
+        .await
+        .unwrap();
+    let rows = table.select_by_tag("slow".to_string()).execute().unwrap();
+    assert_eq!(ids(rows), vec![2, 3]);
+    assert_eq!(table.count_by_tag("fast".to_string()), 1);
+
+    table
+        .update_account_by_id(AccountByIdQuery { account: 3 }, OrderPrimaryKey::from(0))
+        .await
+        .unwrap();
+    assert_eq!(
+        ids(table.select_by_account(3).execute().unwrap()),
+        vec![0, 4]
+    );
+
+    table
+        .update_coid_by_id(
+            CoidByIdQuery {
+                client_order_id: "a1-new".to_string(),
+            },
+            OrderPrimaryKey::from(0),
+        )
+        .await
+        .unwrap();
+    assert!(!table.exists_by_client_order_id("a1".to_string()));
+    assert_eq!(
+        table
+            .select_by_client_order_id("a1-new".to_string())
+            .unwrap()
+            .id,
+        0
+    );
+    table.delete_by_coid("b2".to_string()).await.unwrap();
+    assert!(table.select(OrderPrimaryKey::from(3)).is_none());
+    table.delete_by_account(3).await.unwrap();
+    assert_eq!(ids(table.select_all().execute().unwrap()), vec![1, 2]);
+    assert!(!table.exists_by_account(3));
+    assert_eq!(table.count_by_tag("slow".to_string()), 1);
+
+    let res = table
+        .update_coid_by_id(
+            CoidByIdQuery {
+                client_order_id: "b1".to_string(),
+            },
+            OrderPrimaryKey::from(1),
+        )
+        .await;
+    assert!(res.is_err());
+    assert_eq!(
+        table
+            .select_by_client_order_id("a2".to_string())
+            .unwrap()
+            .id,
+        1
+    );
+}
+
+#[tokio::test]
+async fn delete_many_values_of_hash_key() {
+    let table = OrderWorkTable::default();
+    for i in 0..1000 {
+        table
+            .insert(OrderRow {
+                id: table.get_next_pk().into(),
+                client_order_id: format!("o{i}"),
+                account: 7,
+                price: i as f64,
+                tag: None,
+            })
+            .unwrap();
+    }
+    for i in (0..1000).step_by(2) {
+        table.delete_by_coid(format!("o{i}")).await.unwrap();
+    }
+
+    assert_eq!(table.count_by_account(7), 500);
+    let rows = table.select_by_account(7).execute().unwrap();
+    assert!(ids(rows).iter().all(|id| id % 2 == 1));
+    assert!(
+        table
+            .insert(OrderRow {
+                id: table.get_next_pk().into(),
+                client_order_id: "o0".to_string(),
+                account: 7,
+                price: 0.0,
+                tag: None,
+            })
+            .is_ok()
+    );
+    assert_eq!(table.count_by_account(7), 501);
+}
+
+#[test]
+fn hash_index_info() {
+    let table = OrderWorkTable::default();
+    fill(&table);
+
+    let info = table.system_info();
+    let coid = info
+        .indexes_info
+        .iter()
+        .find(|i| i.name == "coid_idx")
+        .unwrap();
+    assert!(matches!(coid.index_type, IndexKind::UniqueHash));
+    assert_eq!(coid.key_count, 5);
+    let account = info
+        .indexes_info
+        .iter()
+        .find(|i| i.name == "account_idx")
+        .unwrap();
+    assert!(matches!(account.index_type, IndexKind::NonUniqueHash));
+    assert_eq!(account.key_count, 5);
+
+    let plan = table
+        .explain_select_by_client_order_id("a2".to_string())
+        .unwrap();
+    assert_eq!(plan.actual_rows, 1);
+}
@@ -397,3 +397,225 @@ async fn test_update_in_place_and_update_unsized_multithread() -> eyre::Result<(
     assert_eq!(errors, 0);
     Ok(())
 }
+
+worktable!(
+    name: Fill,
+    columns: {
+        id: u64 primary_key autoincrement,
+        order_id: u64,
+        account: u32,
+        filled: u64,
+        notional: f64,
+    },
+    indexes: {
+        order_id_idx: order_id unique,
+        account_idx: account,
+    },
+    queries: {
+        in_place: {
+            IncrementFilledByOrderId(filled) by order_id,
+            AddFillByAccount(filled, notional) by account,
+        }
+    }
+);
+
+fn fill_table() -> FillWorkTable {
+    let table = FillWorkTable::default();
+    for i in 0..30u64 {
+        table
+            .insert(FillRow {
+                id: table.get_next_pk().into(),
+                order_id: i * 10,
+                account: (i % 3) as u32,
+                filled: 0,
+                notional: 0.0,
+            })
+            .unwrap();
+    }
+    table
+}
+
+#[tokio::test]
+async fn test_update_in_place_by_unique_index() -> eyre::Result<()> {
+    let table = fill_table();
+    for _ in 0..100 {
+        table
+            .update_increment_filled_by_order_id_in_place(|filled| *filled += 1, 50)
+            .await?;
+    }
+    assert_eq!(table.select(5).unwrap().filled, 100);
+    assert_eq!(table.select(6).unwrap().filled, 0);
+
+    // Nothing is updated if there is no row with the value.
+    let res = table
+        .update_increment_filled_by_order_id_in_place(|filled| *filled += 1, 55)
+        .await;
+    assert!(matches!(
+        res.unwrap_err().downcast_ref::<WorkTableError>(),
+        Some(WorkTableError::NotFound)
+    ));
+    assert_eq!(
+        table
+            .select_all()
+            .execute()?
+            .iter()
+            .map(|r| r.filled)
+            .sum::<u64>(),
+        100
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_update_in_place_by_non_unique_index() -> eyre::Result<()> {
+    let table = fill_table();
+    table
+        .update_add_fill_by_account_in_place(
+            |(filled, notional)| {
+                *filled += 2;
+                *notional = (notional.to_native() + 1.5).into();
+            },
+            1,
+        )
+        .await?;
+    let rows = table.select_by_account(1).execute()?;
+    assert_eq!(rows.len(), 10);
+    assert!(rows.iter().all(|r| r.filled == 2 && r.notional == 1.5));
+    assert!(
+        table
+            .select_by_account(2)
+            .execute()?
+            .iter()
+            .all(|r| r.filled == 0)
+    );
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 3)]
+async fn test_update_in_place_by_index_multithread() -> eyre::Result<()> {
+    let table = Arc::new(fill_table());
+    let shared = table.clone();
+    let h1 = tokio::spawn(async move {
+        for _ in 0..1_000 {
+            shared
+                .update_add_fill_by_account_in_place(|(filled, _)| *filled += 1, 0)
+                .await
+                .unwrap()
+        }
+    });
+    let shared = table.clone();
+    let h2 = tokio::spawn(async move {
+        for _ in 0..1_000 {
+            shared
+                .update_increment_filled_by_order_id_in_place(|filled| *filled += 1, 30)
+                .await
+                .unwrap()
+        }
+    });
+    for _ in 0..1_000 {
+        table
+            .update_add_fill_by_account_in_place(|(filled, _)| *filled += 1, 0)
+            .await?
+    }
+    h1.await?;
+    h2.await?;
+
+    // Row with `order_id` 30 has account 0, so it's updated by all tasks.
+    assert_eq!(table.select(3).unwrap().filled, 3_000);
+    assert_eq!(table.select(6).unwrap().filled, 2_000);
+    assert_eq!(table.select(1).unwrap().filled, 0);
+    Ok(())
+}
+
+worktable!(
+    name: Position,
+    columns: {
+        account: u32 primary_key,
+        symbol: u64 primary_key,
+        qty: i64,
+        avg_price: f64,
+    },
+    queries: {
+        in_place: {
+            QtyByKey(qty) by account, symbol,
+            FillByKey(qty, avg_price) by account, symbol,
+        }
+    }
+);
+
+fn position_table() -> PositionWorkTable {
+    let table = PositionWorkTable::default();
+    for account in 0..3u32 {
+        for symbol in 0..3u64 {
+            table
+                .insert(PositionRow {
+                    account,
+                    symbol,
+                    qty: 0,
+                    avg_price: 0.0,
+                })
+                .unwrap();
+        }
+    }
+    table
+}
+
+#[tokio::test]
+async fn test_update_in_place_by_composite_pk() -> eyre::Result<()> {
+    let table = position_table();
+    for _ in 0..100 {
+        table
+            .update_qty_by_key_in_place(|qty| *qty += 1, (1, 2))
+            .await?;
+    }
+    table
+        .update_fill_by_key_in_place(
+            |(qty, avg_price)| {
+                *qty -= 10;
+                *avg_price = 12.5.into();
+            },
+            PositionPrimaryKey(1, 2),
+        )
+        .await?;
+    let row = table.select((1, 2)).unwrap();
+    assert_eq!(row.qty, 90);
+    assert_eq!(row.avg_price, 12.5);
+    assert_eq!(table.select((2, 1)).unwrap().qty, 0);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_update_in_place_by_composite_pk_not_found() {
+    let table = position_table();
+    let res = table
+        .update_qty_by_key_in_place(|qty| *qty += 1, (5, 5))
+        .await;
+    assert!(res.is_err());
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 3)]
+async fn test_update_in_place_by_composite_pk_multithread() -> eyre::Result<()> {
+    let table = Arc::new(position_table());
+    let mut handles = vec![];
+    for _ in 0..2 {
+        let shared = table.clone();
+        handles.push(tokio::spawn(async move {
+            for _ in 0..5_000 {
+                shared
+                    .update_qty_by_key_in_place(|qty| *qty += 1, (0, 1))
+                    .await
+                    .unwrap()
+            }
+        }));
+    }
+    for _ in 0..5_000 {
+        table
+            .update_qty_by_key_in_place(|qty| *qty += 1, (0, 1))
+            .await?
+    }
+    for h in handles {
+        h.await?;
+    }
+    assert_eq!(table.select((0, 1)).unwrap().qty, 15_000);
+    Ok(())
+}
@@ -0,0 +1,196 @@
+use worktable::prelude::*;
+use worktable::worktable;
+
+worktable!(
+    name: Order,
+    columns: {
+        id: u64 primary_key autoincrement,
+        symbol: String,
+        qty: u64,
+    },
+    indexes: {
+        symbol_idx: symbol,
+    }
+);
+
+worktable!(
+    name: Fill,
+    columns: {
+        id: u64 primary_key autoincrement,
+        order_id: u64,
+        symbol: String,
+        price: f64,
+    },
+    indexes: {
+        order_id_idx: order_id,
+    }
+);
+
+fn fill_tables() -> (OrderWorkTable, FillWorkTable) {
+    let orders = OrderWorkTable::default();
+    let fills = FillWorkTable::default();
+    for i in 0..5u64 {
+        let order_id = orders
+            .insert(OrderRow {
+                id: orders.get_next_pk().into(),
+                symbol: format!("S{}", i % 2),
+                qty: i * 10,
+            })
+            .unwrap();
+        // Order with `i` id has `i` fills.
+        for j in 0..i {
+            fills
+                .insert(FillRow {
+                    id: fills.get_next_pk().into(),
+                    order_id: order_id.0,
+                    symbol: format!("S{}", i % 2),
+                    price: j as f64,
+                })
+                .unwrap();
+        }
+    }
+    (orders, fills)
+}
+
+#[test]
+fn inner_join_by_index() {
+    let (orders, fills) = fill_tables();
+
+    let joined: Vec<_> = orders
+        .select_all()
+        .join(fills.join_on_order_id(), |o| o.id)
+        .unwrap()
+        .collect();
+    assert_eq!(joined.len(), 10);
+    assert!(joined.iter().all(|(o, f)| o.id == f.order_id));
+}
+
+#[test]
+fn left_join_by_index() {
+    let (orders, fills) = fill_tables();
+
+    let joined: Vec<_> = orders
+        .select_all()
+        .left_join(fills.join_on_order_id(), |o| o.id)
+        .unwrap()
+        .collect();
+    assert_eq!(joined.len(), 11);
+    assert_eq!(joined[0].0.id, 0);
+    assert!(joined[0].1.is_none());
+    assert!(
+        joined[1..]
+            .iter()
+            .all(|(o, f)| o.id == f.as_ref().unwrap().order_id)
+    );
+}
+
+#[test]
+fn join_with_filters_and_limits() {
+    let (orders, fills) = fill_tables();
+
+    let joined: Vec<_> = orders
+        .select_all()
+        .where_by(|o| o.qty >= 30)
+        .limit(1)
+        .join(
+            fills
+                .join_on_order_id()
+                .where_by(|f| f.price > 0.0)
+                .limit(2),
+            |o| o.id,
+        )
+        .unwrap()
+        .collect();
+    assert_eq!(joined.len(), 2);
+    assert!(joined.iter().all(|(o, f)| o.id == 3 && f.price > 0.0));
+}
+
+#[test]
+fn join_by_primary_key() {
+    let (orders, fills) = fill_tables();
+
+    let joined: Vec<_> = fills
+        .select_all()
+        .join(orders.join_on_primary_key(), |f| f.order_id)
+        .unwrap()
+        .collect();
+    assert_eq!(joined.len(), 10);
+    assert!(joined.iter().all(|(f, o)| o.id == f.order_id));
+
+    let by_pk_column: Vec<_> = fills
+        .select_all()
+        .join(orders.join_on_id(), |f| f.order_id)
+        .unwrap()
+        .collect();
+    assert_eq!(by_pk_column, joined);
+}
+
+#[test]
+fn join_where_by_combines_filters() {
+    let (orders, fills) = fill_tables();
+
+    let joined: Vec<_> = orders
+        .select_all()
+        .join(
+            fills
+                .join_on_order_id()
+                .where_by(|f| f.price > 0.0)
+                .where_by(|f| f.price < 3.0),
+            |o| o.id,
+        )
+        .unwrap()
+        .collect();
+    // Fills with price 1 and 2 of orders 2, 3 and 4.
+    assert_eq!(joined.len(), 5);
+    assert!(joined.iter().all(|(_, f)| f.price > 0.0 && f.price < 3.0));
+}
+
+#[test]
+fn join_on_not_indexed_column() {
+    let (orders, fills) = fill_tables();
+
+    let joined: Vec<_> = orders
+        .select_by_symbol("S1".to_string())
+        .left_join(fills.join_on_symbol().where_by(|f| f.price < 2.0), |o| {
+            o.symbol.clone()
+        })
+        .unwrap()
+        .collect();
+    assert_eq!(joined.len(), 6);
+    assert!(
+        joined
+            .iter()
+            .all(|(o, f)| f.as_ref().unwrap().symbol == o.symbol)
+    );
+
+    let joined: Vec<_> = fills
+        .select_all()
+        .join(fills.join_on_price(), |f| f.price)
+        .unwrap()
+        .collect();
+    // Fills with same price are joined with each other: 4 with price 0,
+    // 3 with price 1, 2 with price 2 and 1 with price 3.
+    assert_eq!(joined.len(), 16 + 9 + 4 + 1);
+}
+
+#[test]
+fn hash_join() {
+    let (orders, fills) = fill_tables();
+
+    let right = HashJoin::from_query(fills.select_all().where_by(|f| f.price < 2.0), |f| {
+        f.symbol.clone()
+    })
+    .unwrap();
+    let joined: Vec<_> = orders
+        .select_by_symbol("S1".to_string())
+        .left_join(right, |o| o.symbol.clone())
+        .unwrap()
+        .collect();
+    // `S1` orders are 1 and 3, `S1` fills with price < 2 are 3 (1 + 2).
+    assert_eq!(joined.len(), 6);
+    assert!(
+        joined
+            .iter()
+            .all(|(o, f)| f.as_ref().unwrap().symbol == o.symbol)
+    );
+}
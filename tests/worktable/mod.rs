@@ -1,15 +1,34 @@
+mod aggregate;
 mod array;
+mod array_index;
 mod base;
 mod bench;
+mod bitmap_index;
+mod computed_index;
 mod config;
 mod count;
 mod custom_pk;
 mod delete;
+mod distinct;
+mod explain;
 mod float;
+mod hash_index;
 mod in_place;
 mod index;
+mod join;
 mod option;
+mod option_index;
+mod pagination;
+mod partial_index;
+mod spatial_index;
+mod stream;
+mod string_index;
+mod text_query;
 mod tuple_primary_key;
+mod type_path;
 mod unsized_;
+mod update_by_columns;
+mod update_primary_key;
 mod uuid;
+mod variable_size;
 mod with_enum;
@@ -0,0 +1,206 @@
+use worktable::prelude::*;
+use worktable::worktable;
+
+worktable!(
+    name: Account,
+    columns: {
+        id: u64 primary_key autoincrement,
+        email: String optional,
+        score: i64 optional,
+        name: String,
+    },
+    indexes: {
+        email_idx: email unique,
+        score_idx: score,
+    },
+    queries: {
+        update: {
+            EmailById(email) by id,
+            ScoreById(score) by id,
+        },
+    }
+);
+
+fn ids(rows: Vec<AccountRow>) -> Vec<u64> {
+    let mut ids: Vec<_> = rows.into_iter().map(|r| r.id).collect();
+    ids.sort();
+    ids
+}
+
+fn fill(table: &AccountWorkTable) {
+    for (email, score) in [
+        (Some("a@b.c"), Some(10)),
+        (None, Some(20)),
+        (None, None),
+        (Some("d@e.f"), Some(10)),
+        (None, Some(30)),
+    ] {
+        table
+            .insert(AccountRow {
+                id: table.get_next_pk().into(),
+                email: email.map(|e| e.to_string()),
+                score,
+                name: "name".to_string(),
+            })
+            .unwrap();
+    }
+}
+
+#[test]
+fn unique_allows_many_none() {
+    let table = AccountWorkTable::default();
+    fill(&table);
+
+    let row = table.select_by_email("a@b.c".to_string()).unwrap();
+    assert_eq!(row.id, 0);
+    assert!(table.select_by_email("x@y.z".to_string()).is_none());
+    assert_eq!(table.count_by_email("d@e.f".to_string()), 1);
+
+    let res = table.insert(AccountRow {
+        id: table.get_next_pk().into(),
+        email: Some("a@b.c".to_string()),
+        score: None,
+        name: "name".to_string(),
+    });
+    assert!(res.is_err());
+
+    let rows = table.select_by_email_is_none().execute().unwrap();
+    assert_eq!(ids(rows), vec![1, 2, 4]);
+}
+
+#[test]
+fn select_over_some_values() {
+    let table = AccountWorkTable::default();
+    fill(&table);
+
+    let rows = table.select_by_score(10).execute().unwrap();
+    assert_eq!(ids(rows), vec![0, 3]);
+    assert_eq!(table.count_by_score(20), 1);
+    assert!(!table.exists_by_score(40));
+
+    let rows = table.select_range_by_score(15..).execute().unwrap();
+    assert_eq!(ids(rows), vec![1, 4]);
+    let rows = table.select_range_by_score(..).execute().unwrap();
+    assert_eq!(rows.len(), 4);
+    assert_eq!(table.count_range_by_score(..=20), 3);
+
+    let rows = table.select_by_score_is_none().execute().unwrap();
+    assert_eq!(ids(rows), vec![2]);
+}
+
+#[tokio::test]
+async fn update_between_some_and_none() {
+    let table = AccountWorkTable::default();
+    fill(&table);
+
+    table
+        .update_email_by_id(EmailByIdQuery { email: None }, AccountPrimaryKey::from(0))
+        .await
+        .unwrap();
+    assert!(table.select_by_email("a@b.c".to_string()).is_none());
+
+    table
+        .update_email_by_id(
+            EmailByIdQuery {
+                email: Some("a@b.c".to_string()),
+            },
+            AccountPrimaryKey::from(2),
+        )
+        .await
+        .unwrap();
+    assert_eq!(table.select_by_email("a@b.c".to_string()).unwrap().id, 2);
+
+    let res = table
+        .update_email_by_id(
+            EmailByIdQuery {
+                email: Some("d@e.f".to_string()),
+            },
+            AccountPrimaryKey::from(1),
+        )
+        .await;
+    assert!(res.is_err());
+    assert_eq!(table.select_by_email("d@e.f".to_string()).unwrap().id, 3);
+    assert_eq!(
+        table.select(AccountPrimaryKey::from(1)).unwrap().email,
+        None
+    );
+
+    table
+        .update_score_by_id(ScoreByIdQuery { score: None }, AccountPrimaryKey::from(0))
+        .await
+        .unwrap();
+    let rows = table.select_by_score(10).execute().unwrap();
+    assert_eq!(ids(rows), vec![3]);
+
+    table.delete(3.into()).await.unwrap();
+    assert!(!table.exists_by_score(10));
+    assert!(table.select_by_email("d@e.f".to_string()).is_none());
+}
+
+worktable!(
+    name: Level,
+    columns: {
+        id: u64 primary_key autoincrement,
+        level: u32 optional,
+        ratio: f64 optional,
+    },
+    indexes: {
+        level_idx: level unique,
+        ratio_idx: ratio,
+    },
+    queries: {
+        update: {
+            LevelById(level) by id,
+            RatioById(ratio) by id,
+        },
+    }
+);
+
+#[tokio::test]
+async fn update_sized_optional_in_place() {
+    let table = LevelWorkTable::default();
+    let pk = table
+        .insert(LevelRow {
+            id: table.get_next_pk().into(),
+            level: None,
+            ratio: Some(0.5),
+        })
+        .unwrap();
+    table
+        .insert(LevelRow {
+            id: table.get_next_pk().into(),
+            level: Some(2),
+            ratio: None,
+        })
+        .unwrap();
+    let link = table.0.pk_map.get(&pk).unwrap().get().value;
+
+    table
+        .update_level_by_id(LevelByIdQuery { level: Some(1) }, pk.clone())
+        .await
+        .unwrap();
+    assert_eq!(table.0.pk_map.get(&pk).unwrap().get().value, link);
+    assert_eq!(table.select_by_level(1).unwrap().id, 0);
+    assert_eq!(table.select_by_level(2).unwrap().id, 1);
+
+    table
+        .update_ratio_by_id(RatioByIdQuery { ratio: None }, pk.clone())
+        .await
+        .unwrap();
+    assert_eq!(table.count_by_ratio(0.5), 0);
+    let rows = table.select_by_ratio_is_none().execute().unwrap();
+    assert_eq!(rows.len(), 2);
+
+    table
+        .update(LevelRow {
+            id: pk.into(),
+            level: None,
+            ratio: Some(1.5),
+        })
+        .await
+        .unwrap();
+    assert!(table.select_by_level(1).is_none());
+    let rows = table.select_range_by_ratio(1.0..2.0).execute().unwrap();
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0].id, 0);
+}
@@ -0,0 +1,172 @@
+use worktable::prelude::*;
+use worktable::worktable;
+
+worktable!(
+    name: Test,
+    columns: {
+        id: u64 primary_key autoincrement,
+        exchange: String,
+        price: f64,
+        qty: u64,
+    },
+    indexes: {
+        exchange_idx: exchange,
+        price_idx: price,
+    }
+);
+
+fn fill_table(count: u64) -> TestWorkTable {
+    let table = TestWorkTable::default();
+    for i in 0..count {
+        table
+            .insert(TestRow {
+                id: table.get_next_pk().into(),
+                exchange: format!("Ex{}", i % 4),
+                price: (i % 7) as f64,
+                qty: count - i,
+            })
+            .unwrap();
+    }
+    table
+}
+
+fn collect_pages<F>(table: &TestWorkTable, page_size: usize, first: F) -> Vec<TestRow>
+where
+    F: FnOnce(&TestWorkTable) -> SelectPage<TestRow, TestCursor>,
+{
+    let mut page = first(table);
+    let mut rows = page.rows;
+    while let Some(cursor) = page.cursor {
+        page = table
+            .after(&cursor)
+            .unwrap()
+            .execute_page(page_size)
+            .unwrap();
+        assert!(page.rows.len() <= page_size);
+        rows.extend(page.rows);
+    }
+    rows
+}
+
+#[test]
+fn pages_by_primary_key() {
+    let table = fill_table(25);
+
+    let rows = collect_pages(&table, 10, |t| t.select_all().execute_page(10).unwrap());
+    assert_eq!(rows, table.select_all().execute().unwrap());
+}
+
+#[tokio::test]
+async fn pages_are_stable_under_changes() {
+    let table = fill_table(10);
+
+    let first = table.select_all().execute_page(5).unwrap();
+    assert_eq!(first.rows.len(), 5);
+    let last_id = first.rows.last().unwrap().id;
+    // Removed row is placed before the cursor, so next page is not shifted.
+    table.delete(first.rows[0].id.into()).await.unwrap();
+
+    let second = table
+        .after(&first.cursor.unwrap())
+        .unwrap()
+        .execute_page(5)
+        .unwrap();
+    assert_eq!(second.rows.first().unwrap().id, last_id + 1);
+    assert_eq!(second.rows.len(), 5);
+}
+
+#[test]
+fn pages_by_non_unique_index() {
+    let table = fill_table(30);
+
+    let rows = collect_pages(&table, 7, |t| {
+        t.select_all()
+            .order_on(TestRowFields::Exchange, Order::Asc)
+            .execute_page(7)
+            .unwrap()
+    });
+    let expected = table
+        .select_all()
+        .order_on(TestRowFields::Exchange, Order::Asc)
+        .order_on(TestRowFields::Id, Order::Asc)
+        .execute()
+        .unwrap();
+    assert_eq!(rows, expected);
+}
+
+#[test]
+fn pages_by_float_index_desc() {
+    let table = fill_table(30);
+
+    let rows = collect_pages(&table, 4, |t| {
+        t.select_all()
+            .order_on(TestRowFields::Price, Order::Desc)
+            .execute_page(4)
+            .unwrap()
+    });
+    let expected = table
+        .select_all()
+        .order_on(TestRowFields::Price, Order::Desc)
+        .order_on(TestRowFields::Id, Order::Asc)
+        .execute()
+        .unwrap();
+    assert_eq!(rows, expected);
+}
+
+#[test]
+fn pages_by_not_indexed_column() {
+    let table = fill_table(20);
+
+    let query = || table.select_all().order_on(TestRowFields::Qty, Order::Asc);
+    let mut page = query().execute_page(6).unwrap();
+    assert!(matches!(
+        table.after(page.cursor.as_ref().unwrap()),
+        Err(WorkTableError::NotIndexedCursor(_))
+    ));
+    let mut rows = page.rows;
+    while let Some(cursor) = page.cursor {
+        page = query().after(&cursor).execute_page(6).unwrap();
+        rows.extend(page.rows);
+    }
+    let expected = query().execute().unwrap();
+    assert_eq!(rows, expected);
+}
+
+#[test]
+fn pages_of_filtered_query() {
+    let table = fill_table(40);
+
+    let query = || {
+        table
+            .select_by_exchange("Ex1".to_string())
+            .where_by(|r| r.price > 1.0)
+            .order_on(TestRowFields::Price, Order::Desc)
+    };
+    let mut page = query().execute_page(3).unwrap();
+    let mut rows = page.rows;
+    while let Some(cursor) = page.cursor {
+        page = query().after(&cursor).execute_page(3).unwrap();
+        assert!(page.rows.len() <= 3);
+        rows.extend(page.rows);
+    }
+    let expected = query()
+        .order_on(TestRowFields::Id, Order::Asc)
+        .execute()
+        .unwrap();
+    assert!(!expected.is_empty());
+    assert!(
+        expected
+            .iter()
+            .all(|r| r.exchange == "Ex1" && r.price > 1.0)
+    );
+    assert_eq!(rows, expected);
+}
+
+#[test]
+fn last_page_has_no_cursor() {
+    let table = fill_table(3);
+
+    let page = table.select_all().execute_page(5).unwrap();
+    assert_eq!(page.rows.len(), 3);
+    assert!(page.cursor.is_none());
+}
@@ -0,0 +1,284 @@
+use rkyv::{Archive, Deserialize, Serialize};
+use worktable::prelude::*;
+use worktable::worktable;
+
+#[derive(Archive, Clone, Copy, Debug, Deserialize, Serialize, PartialEq, PartialOrd, MemStat)]
+#[rkyv(compare(PartialEq), derive(Debug))]
+pub enum Status {
+    Open,
+    Filled,
+    Cancelled,
+}
+
+worktable!(
+    name: PartialOrder,
+    columns: {
+        id: u64 primary_key autoincrement,
+        client_order_id: String,
+        status: Status,
+        qty: u64,
+    },
+    indexes: {
+        open_coid_idx: client_order_id unique where(status == Open),
+        qty_idx: qty where(qty > 0 && status != Cancelled),
+    },
+    queries: {
+        update: {
+            StatusById(status) by id,
+            QtyById(qty) by id,
+        },
+    }
+);
+
+worktable!(
+    name: PartialAccount,
+    columns: {
+        id: u64 primary_key autoincrement,
+        account: u64,
+        status: Status,
+        price: f64,
+    },
+    indexes: {
+        open_account_idx: account unique where(status == Open),
+    },
+    queries: {
+        update: {
+            AccountStatusById(status) by id,
+            AccountPriceById(price) by id,
+        },
+    }
+);
+
+fn fill(table: &PartialOrderWorkTable) {
+    for i in 0..10u64 {
+        let status = if i % 2 == 0 {
+            Status::Open
+        } else {
+            Status::Filled
+        };
+        table
+            .insert(PartialOrderRow {
+                id: table.get_next_pk().into(),
+                client_order_id: format!("coid-{i}"),
+                status,
+                qty: i % 3,
+            })
+            .unwrap();
+    }
+}
+
+#[test]
+fn only_matching_rows_are_indexed() {
+    let table = PartialOrderWorkTable::default();
+    fill(&table);
+
+    assert_eq!(
+        table
+            .select_by_client_order_id("coid-4".to_string())
+            .unwrap()
+            .id,
+        4
+    );
+    assert!(
+        table
+            .select_by_client_order_id("coid-3".to_string())
+            .is_none()
+    );
+    assert_eq!(table.count_by_qty(0), 0);
+    assert_eq!(table.count_by_qty(1), 3);
+    let rows = table.select_by_qty(2).execute().unwrap();
+    assert_eq!(rows.len(), 3);
+    assert!(rows.iter().all(|r| r.qty == 2));
+
+    let info = table.system_info();
+    let coid = info
+        .indexes_info
+        .iter()
+        .find(|i| i.name == "open_coid_idx")
+        .unwrap();
+    assert_eq!(coid.key_count, 5);
+}
+
+#[test]
+fn uniqueness_among_indexed_rows() {
+    let table = PartialOrderWorkTable::default();
+    fill(&table);
+
+    // `coid-3` row is filled, so it's not in index.
+    table
+        .insert(PartialOrderRow {
+            id: table.get_next_pk().into(),
+            client_order_id: "coid-3".to_string(),
+            status: Status::Open,
+            qty: 0,
+        })
+        .unwrap();
+    assert_eq!(
+        table
+            .select_by_client_order_id("coid-3".to_string())
+            .unwrap()
+            .id,
+        10
+    );
+
+    let res = table.insert(PartialOrderRow {
+        id: table.get_next_pk().into(),
+        client_order_id: "coid-2".to_string(),
+        status: Status::Open,
+        qty: 0,
+    });
+    assert!(res.is_err());
+    table
+        .insert(PartialOrderRow {
+            id: table.get_next_pk().into(),
+            client_order_id: "coid-2".to_string(),
+            status: Status::Cancelled,
+            qty: 0,
+        })
+        .unwrap();
+    assert_eq!(
+        table
+            .select_by_client_order_id("coid-2".to_string())
+            .unwrap()
+            .id,
+        2
+    );
+}
+
+#[tokio::test]
+async fn rows_follow_predicate_on_update() {
+    let table = PartialOrderWorkTable::default();
+    fill(&table);
+
+    table
+        .update_status_by_id(
+            StatusByIdQuery {
+                status: Status::Filled,
+            },
+            PartialOrderPrimaryKey::from(0),
+        )
+        .await
+        .unwrap();
+    assert!(
+        table
+            .select_by_client_order_id("coid-0".to_string())
+            .is_none()
+    );
+    table
+        .update_status_by_id(
+            StatusByIdQuery {
+                status: Status::Open,
+            },
+            PartialOrderPrimaryKey::from(1),
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        table
+            .select_by_client_order_id("coid-1".to_string())
+            .unwrap()
+            .id,
+        1
+    );
+
+    table
+        .update_qty_by_id(QtyByIdQuery { qty: 0 }, PartialOrderPrimaryKey::from(4))
+        .await
+        .unwrap();
+    assert_eq!(table.count_by_qty(1), 2);
+    table
+        .update_status_by_id(
+            StatusByIdQuery {
+                status: Status::Cancelled,
+            },
+            PartialOrderPrimaryKey::from(7),
+        )
+        .await
+        .unwrap();
+    assert_eq!(table.count_by_qty(1), 1);
+
+    table
+        .update(PartialOrderRow {
+            id: 0,
+            client_order_id: "coid-0".to_string(),
+            status: Status::Open,
+            qty: 1,
+        })
+        .await
+        .unwrap();
+    assert_eq!(
+        table
+            .select_by_client_order_id("coid-0".to_string())
+            .unwrap()
+            .id,
+        0
+    );
+    assert_eq!(table.count_by_qty(1), 2);
+
+    table.delete(0.into()).await.unwrap();
+    assert!(
+        table
+            .select_by_client_order_id("coid-0".to_string())
+            .is_none()
+    );
+    assert_eq!(table.count_by_qty(1), 1);
+}
+
+#[tokio::test]
+async fn sized_table_update_changes_index() {
+    let table = PartialAccountWorkTable::default();
+    for i in 0..4u64 {
+        table
+            .insert(PartialAccountRow {
+                id: table.get_next_pk().into(),
+                account: i % 2,
+                status: if i < 2 { Status::Open } else { Status::Filled },
+                price: i as f64,
+            })
+            .unwrap();
+    }
+    assert_eq!(table.select_by_account(1).unwrap().id, 1);
+
+    table
+        .update_account_status_by_id(
+            AccountStatusByIdQuery {
+                status: Status::Cancelled,
+            },
+            PartialAccountPrimaryKey::from(1),
+        )
+        .await
+        .unwrap();
+    assert!(table.select_by_account(1).is_none());
+    table
+        .update_account_status_by_id(
+            AccountStatusByIdQuery {
+                status: Status::Open,
+            },
+            PartialAccountPrimaryKey::from(3),
+        )
+        .await
+        .unwrap();
+    assert_eq!(table.select_by_account(1).unwrap().id, 3);
+
+    table
+        .update_account_price_by_id(
+            AccountPriceByIdQuery { price: 10.0 },
+            PartialAccountPrimaryKey::from(3),
+        )
+        .await
+        .unwrap();
+    assert_eq!(table.select_by_account(1).unwrap().price, 10.0);
+
+    // Account `0` is already used by open row.
+    let res = table
+        .update_account_status_by_id(
+            AccountStatusByIdQuery {
+                status: Status::Open,
+            },
+            PartialAccountPrimaryKey::from(2),
+        )
+        .await;
+    assert!(res.is_err());
+    assert_eq!(table.select(2).unwrap().status, Status::Filled);
+    assert_eq!(table.select_by_account(0).unwrap().id, 0);
+}
@@ -0,0 +1,148 @@
+use worktable::prelude::*;
+use worktable::worktable;
+
+worktable!(
+    name: Asset,
+    columns: {
+        id: u64 primary_key autoincrement,
+        name: String,
+        lat: f64,
+        lon: f64,
+    },
+    indexes: {
+        location_idx: spatial(lat, lon),
+    },
+    queries: {
+        update: {
+            LatById(lat) by id,
+            NameById(name) by id,
+        },
+    }
+);
+
+fn ids(rows: Vec<AssetRow>) -> Vec<u64> {
+    let mut ids: Vec<_> = rows.into_iter().map(|r| r.id).collect();
+    ids.sort();
+    ids
+}
+
+fn fill(table: &AssetWorkTable) {
+    for x in 0..10u64 {
+        for y in 0..10u64 {
+            table
+                .insert(AssetRow {
+                    id: table.get_next_pk().into(),
+                    name: format!("asset-{x}-{y}"),
+                    lat: x as f64,
+                    lon: y as f64,
+                })
+                .unwrap();
+        }
+    }
+}
+
+#[test]
+fn select_within_box() {
+    let table = AssetWorkTable::default();
+    fill(&table);
+
+    let rows = table
+        .select_within_box((2.0, 3.0), (3.0, 5.0))
+        .execute()
+        .unwrap();
+    assert_eq!(ids(rows), vec![23, 24, 25, 33, 34, 35]);
+    assert!(
+        table
+            .select_within_box((10.5, 0.0), (20.0, 20.0))
+            .execute()
+            .unwrap()
+            .is_empty()
+    );
+
+    let plan = table
+        .select_within_box((0.0, 0.0), (1.0, 1.0))
+        .explain()
+        .unwrap();
+    assert_eq!(plan.access_path, AccessPath::Spatial("location_idx"));
+    assert_eq!(plan.actual_rows, 4);
+}
+
+#[test]
+fn select_nearest() {
+    let table = AssetWorkTable::default();
+    fill(&table);
+
+    let rows = table.select_nearest((4.2, 6.1), 3).execute().unwrap();
+    assert_eq!(rows.len(), 3);
+    assert_eq!(rows[0].id, 46);
+    assert_eq!(ids(rows[1..].to_vec()), vec![47, 56]);
+
+    let rows = table
+        .select_nearest((0.0, 0.0), 5)
+        .limit(2)
+        .execute()
+        .unwrap();
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows[0].id, 0);
+}
+
+#[tokio::test]
+async fn spatial_index_follows_updates() {
+    let table = AssetWorkTable::default();
+    fill(&table);
+
+    table
+        .update_lat_by_id(LatByIdQuery { lat: 50.0 }, AssetPrimaryKey::from(11))
+        .await
+        .unwrap();
+    let rows = table.select_nearest((49.0, 1.0), 1).execute().unwrap();
+    assert_eq!(rows[0].id, 11);
+    assert!(
+        table
+            .select_within_box((1.0, 1.0), (1.0, 1.0))
+            .execute()
+            .unwrap()
+            .is_empty()
+    );
+
+    // Name update changes row size, so row is reinserted.
+    table
+        .update_name_by_id(
+            NameByIdQuery {
+                name: "asset with much longer name".to_string(),
+            },
+            AssetPrimaryKey::from(12),
+        )
+        .await
+        .unwrap();
+    let rows = table
+        .select_within_box((1.0, 2.0), (1.0, 2.0))
+        .execute()
+        .unwrap();
+    assert_eq!(rows[0].name, "asset with much longer name");
+
+    table
+        .update(AssetRow {
+            id: 13,
+            name: "moved".to_string(),
+            lat: -5.0,
+            lon: -5.0,
+        })
+        .await
+        .unwrap();
+    let rows = table.select_nearest((-4.0, -4.0), 1).execute().unwrap();
+    assert_eq!(rows[0].id, 13);
+
+    table.delete(13.into()).await.unwrap();
+    let rows = table.select_nearest((-4.0, -4.0), 1).execute().unwrap();
+    assert_eq!(rows[0].id, 0);
+
+    let info = table.system_info();
+    let location = info
+        .indexes_info
+        .iter()
+        .find(|i| i.name == "location_idx")
+        .unwrap();
+    assert!(matches!(location.index_type, IndexKind::Spatial));
+    assert_eq!(location.key_count, 99);
+}
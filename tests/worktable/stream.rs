@@ -0,0 +1,163 @@
+use futures::StreamExt;
+use worktable::prelude::*;
+use worktable::worktable;
+
+worktable!(
+    name: Test,
+    columns: {
+        id: u64 primary_key autoincrement,
+        exchange: String,
+        qty: u64,
+    },
+    indexes: {
+        exchange_idx: exchange,
+    }
+);
+
+fn fill_table(count: u64) -> TestWorkTable {
+    let table = TestWorkTable::default();
+    for i in 0..count {
+        table
+            .insert(TestRow {
+                id: table.get_next_pk().into(),
+                exchange: format!("Ex{}", i % 3),
+                qty: i,
+            })
+            .unwrap();
+    }
+    table
+}
+
+#[tokio::test]
+async fn stream_all() {
+    let table = fill_table(500);
+
+    let rows: Vec<_> = table
+        .select_all()
+        .execute_stream()
+        .map(|r| r.unwrap())
+        .collect()
+        .await;
+    assert_eq!(rows, table.select_all().execute().unwrap());
+}
+
+#[tokio::test]
+async fn stream_with_offset_limit_and_order() {
+    let table = fill_table(50);
+
+    let rows: Vec<_> = table
+        .select_all()
+        .offset(10)
+        .limit(5)
+        .execute_stream()
+        .map(|r| r.unwrap())
+        .collect()
+        .await;
+    assert_eq!(
+        rows.iter().map(|r| r.id).collect::<Vec<_>>(),
+        vec![10, 11, 12, 13, 14]
+    );
+
+    let rows: Vec<_> = table
+        .select_by_exchange("Ex1".to_string())
+        .order_on(TestRowFields::Qty, Order::Desc)
+        .execute_stream()
+        .map(|r| r.unwrap())
+        .collect()
+        .await;
+    assert_eq!(
+        rows,
+        table
+            .select_by_exchange("Ex1".to_string())
+            .order_on(TestRowFields::Qty, Order::Desc)
+            .execute()
+            .unwrap()
+    );
+}
+
+#[tokio::test]
+async fn stream_does_not_block_table_changes() {
+    let table = fill_table(300);
+
+    let mut stream = Box::pin(table.select_all().execute_stream());
+    let first = stream.next().await.unwrap().unwrap();
+    assert_eq!(first.id, 0);
+
+    table.delete(299.into()).await.unwrap();
+    table
+        .insert(TestRow {
+            id: table.get_next_pk().into(),
+            exchange: "Ex0".to_string(),
+            qty: 0,
+        })
+        .unwrap();
+    table
+        .update(TestRow {
+            id: 1,
+            exchange: "Ex1".to_string(),
+            qty: 1000,
+        })
+        .await
+        .unwrap();
+
+    let rest: Vec<_> = stream.map(|r| r.unwrap()).collect().await;
+    assert_eq!(rest.first().unwrap().qty, 1000);
+    assert!(rest.iter().all(|r| r.id != 299));
+    assert_eq!(rest.last().unwrap().id, 300);
+}
+
+#[tokio::test]
+async fn stream_drop_cancels() {
+    let table = fill_table(300);
+
+    let rows: Vec<_> = table
+        .select_all()
+        .where_archived(|r| r.qty % 2 == 0)
+        .execute_stream()
+        .take(3)
+        .map(|r| r.unwrap().qty)
+        .collect()
+        .await;
+    assert_eq!(rows, vec![0, 2, 4]);
+
+    table.delete(0.into()).await.unwrap();
+    assert_eq!(table.select_all().execute().unwrap().len(), 299);
+}
+
+#[tokio::test]
+async fn stream_range_and_distinct_lazily() {
+    let table = fill_table(300);
+
+    let mut stream = Box::pin(
+        table
+            .select_all()
+            .range_on(TestRowFields::Qty, 100..200)
+            .execute_stream(),
+    );
+    let first = stream.next().await.unwrap().unwrap();
+    assert_eq!(first.qty, 100);
+
+    // Row is read after the update, as range is checked while streaming.
+    table
+        .update(TestRow {
+            id: 250,
+            exchange: "Ex1".to_string(),
+            qty: 150,
+        })
+        .await
+        .unwrap();
+
+    let rest: Vec<_> = stream.map(|r| r.unwrap().id).collect().await;
+    assert_eq!(rest.len(), 100);
+    assert_eq!(rest.last(), Some(&250));
+
+    let rows: Vec<_> = table
+        .select_all()
+        .range_on(TestRowFields::Qty, 10..)
+        .distinct_on(TestRowFields::Exchange)
+        .execute_stream()
+        .map(|r| r.unwrap().qty)
+        .collect()
+        .await;
+    assert_eq!(rows, vec![10, 11, 12]);
+}
@@ -0,0 +1,184 @@
+use worktable::prelude::*;
+use worktable::worktable;
+
+worktable!(
+    name: Ticker,
+    columns: {
+        id: u64 primary_key autoincrement,
+        symbol: String,
+        exchange: String,
+        price: u64,
+    },
+    indexes: {
+        symbol_idx: symbol unique prefix ci,
+        exchange_idx: exchange prefix,
+    },
+    queries: {
+        update: {
+            SymbolById(symbol) by id,
+            ExchangeById(exchange) by id,
+        },
+    }
+);
+
+fn symbols(rows: Vec<TickerRow>) -> Vec<String> {
+    let mut symbols: Vec<_> = rows.into_iter().map(|r| r.symbol).collect();
+    symbols.sort();
+    symbols
+}
+
+fn fill(table: &TickerWorkTable) {
+    for (symbol, exchange) in [
+        ("BTCUSDT", "binance"),
+        ("btcEUR", "bitstamp"),
+        ("BTT", "binance"),
+        ("ETHUSDT", "bybit"),
+        ("BT", "binance"),
+    ] {
+        table
+            .insert(TickerRow {
+                id: table.get_next_pk().into(),
+                symbol: symbol.to_string(),
+                exchange: exchange.to_string(),
+                price: 1,
+            })
+            .unwrap();
+    }
+}
+
+#[test]
+fn select_by_prefix() {
+    let table = TickerWorkTable::default();
+    fill(&table);
+
+    let rows = table.select_by_symbol_prefix("BTC").execute().unwrap();
+    assert_eq!(symbols(rows), vec!["BTCUSDT", "btcEUR"]);
+    let rows = table.select_by_symbol_prefix("bt").execute().unwrap();
+    assert_eq!(rows.len(), 4);
+    let rows = table.select_by_symbol_prefix("").execute().unwrap();
+    assert_eq!(rows.len(), 5);
+    assert!(
+        table
+            .select_by_symbol_prefix("X")
+            .execute()
+            .unwrap()
+            .is_empty()
+    );
+
+    let rows = table.select_by_exchange_prefix("bi").execute().unwrap();
+    assert_eq!(rows.len(), 4);
+    let rows = table.select_by_exchange_prefix("Bi").execute().unwrap();
+    assert!(rows.is_empty());
+    let rows = table
+        .select_by_exchange_prefix("binance")
+        .execute()
+        .unwrap();
+    assert_eq!(symbols(rows), vec!["BT", "BTCUSDT", "BTT"]);
+}
+
+#[test]
+fn case_insensitive_lookup() {
+    let table = TickerWorkTable::default();
+    fill(&table);
+
+    let row = table.select_by_symbol("btcusdt".to_string()).unwrap();
+    assert_eq!(row.symbol, "BTCUSDT");
+    let row = table.select_by_symbol("BTCEUR".to_string()).unwrap();
+    assert_eq!(row.symbol, "btcEUR");
+    assert!(table.exists_by_symbol("EthUsdt".to_string()));
+    assert_eq!(table.count_range_by_symbol("BTC".to_string()..), 4);
+    assert_eq!(
+        table.count_range_by_symbol("BTC".to_string().."BTD".to_string()),
+        2
+    );
+
+    let res = table.insert(TickerRow {
+        id: table.get_next_pk().into(),
+        symbol: "EthUSDT".to_string(),
+        exchange: "okx".to_string(),
+        price: 1,
+    });
+    assert!(res.is_err());
+}
+
+#[tokio::test]
+async fn update_normalized_keys() {
+    let table = TickerWorkTable::default();
+    fill(&table);
+
+    table
+        .update_symbol_by_id(
+            SymbolByIdQuery {
+                symbol: "btcusdt".to_string(),
+            },
+            TickerPrimaryKey::from(0),
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        table
+            .select_by_symbol("BTCUSDT".to_string())
+            .unwrap()
+            .symbol,
+        "btcusdt"
+    );
+
+    table
+        .update_symbol_by_id(
+            SymbolByIdQuery {
+                symbol: "SOLUSDT".to_string(),
+            },
+            TickerPrimaryKey::from(0),
+        )
+        .await
+        .unwrap();
+    assert!(!table.exists_by_symbol("btcusdt".to_string()));
+    assert_eq!(table.select_by_symbol("solusdt".to_string()).unwrap().id, 0);
+
+    let res = table
+        .update_symbol_by_id(
+            SymbolByIdQuery {
+                symbol: "Bt".to_string(),
+            },
+            TickerPrimaryKey::from(0),
+        )
+        .await;
+    assert!(res.is_err());
+    assert_eq!(table.select_by_symbol("SolUsdt".to_string()).unwrap().id, 0);
+    assert_eq!(table.select_by_symbol("bt".to_string()).unwrap().id, 4);
+
+    table.delete(1.into()).await.unwrap();
+    assert!(!table.exists_by_symbol("btceur".to_string()));
+    let rows = table.select_by_symbol_prefix("bt").execute().unwrap();
+    assert_eq!(symbols(rows), vec!["BT", "BTT"]);
+}
+
+worktable!(
+    name: Code,
+    columns: {
+        id: u64 primary_key autoincrement,
+        code: [u8; 4],
+        name: String,
+    },
+    indexes: {
+        name_idx: name ci,
+    },
+);
+
+#[test]
+fn non_unique_case_insensitive_lookup() {
+    let table = CodeWorkTable::default();
+    for name in ["Alpha", "ALPHA", "beta"] {
+        table
+            .insert(CodeRow {
+                id: table.get_next_pk().into(),
+                code: [0; 4],
+                name: name.to_string(),
+            })
+            .unwrap();
+    }
+
+    let rows = table.select_by_name("alpha".to_string()).execute().unwrap();
+    assert_eq!(rows.len(), 2);
+    assert_eq!(table.count_by_name("Beta".to_string()), 1);
+}
@@ -0,0 +1,108 @@
+use worktable::prelude::*;
+use worktable::worktable;
+
+worktable!(
+    name: Test,
+    columns: {
+        id: u64 primary_key autoincrement,
+        exchange: String,
+        price: f64,
+        qty: i32,
+        is_active: bool,
+        comment: String optional,
+    },
+    indexes: {
+        exchange_idx: exchange,
+    }
+);
+
+fn fill_table() -> TestWorkTable {
+    let table = TestWorkTable::default();
+    for i in 0..10 {
+        table
+            .insert(TestRow {
+                id: table.get_next_pk().into(),
+                exchange: format!("Ex {}", i % 3),
+                price: i as f64 / 2.0,
+                qty: 5 - i,
+                is_active: i % 2 == 0,
+                comment: (i == 3).then(|| "third".to_string()),
+            })
+            .unwrap();
+    }
+    table
+}
+
+#[test]
+fn query_text_filter_order_limit() {
+    let table = fill_table();
+
+    let result = table
+        .query_text(
+            "select id, qty where exchange = 'Ex 1' and price >= 1 order by qty desc limit 2",
+        )
+        .unwrap();
+    assert_eq!(result.columns, vec!["id", "qty"]);
+    assert_eq!(
+        result.rows,
+        vec![
+            vec!["4".to_string(), "1".to_string()],
+            vec!["7".to_string(), "-2".to_string()]
+        ]
+    );
+}
+
+#[test]
+fn query_text_all_columns() {
+    let table = fill_table();
+
+    let result = table
+        .query_text("where is_active = true and qty < 0 offset 1")
+        .unwrap();
+    assert_eq!(
+        result.columns,
+        vec!["id", "exchange", "price", "qty", "is_active", "comment"]
+    );
+    assert_eq!(
+        result
+            .rows
+            .iter()
+            .map(|r| r[0].as_str())
+            .collect::<Vec<_>>(),
+        vec!["8"]
+    );
+
+    let result = table.query_text("select id where comment != null").unwrap();
+    assert_eq!(result.rows, vec![vec!["3".to_string()]]);
+}
+
+#[test]
+fn query_text_errors() {
+    let table = fill_table();
+
+    assert!(matches!(
+        table.query_text("where unknown = 1"),
+        Err(WorkTableError::QueryParseError(_))
+    ));
+    assert!(matches!(
+        table.query_text("where qty = abc"),
+        Err(WorkTableError::QueryParseError(_))
+    ));
+    assert!(matches!(
+        table.query_text("order by"),
+        Err(WorkTableError::QueryParseError(_))
+    ));
+}
+
+#[test]
+fn query_text_display() {
+    let table = fill_table();
+
+    let result = table
+        .query_text("select id, exchange where id < 2")
+        .unwrap();
+    let rendered = result.to_string();
+    assert!(rendered.contains("exchange"));
+    assert!(rendered.contains("Ex 1"));
+    assert_eq!(rendered.lines().count(), 4);
+}
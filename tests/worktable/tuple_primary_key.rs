@@ -24,3 +24,83 @@ fn insert() {
     assert_eq!(selected_row, row);
     assert!(table.select((1, 0)).is_none())
 }
+
+worktable! (
+    name: Position,
+    columns: {
+        account: u32 primary_key,
+        symbol: u64 primary_key,
+        qty: i64,
+        note: String,
+    },
+    queries: {
+        update: {
+            QtyByKey(qty) by account, symbol,
+            NoteByKey(note) by account, symbol,
+        }
+    }
+);
+
+fn position_table() -> PositionWorkTable {
+    let table = PositionWorkTable::default();
+    for account in 0..3 {
+        for symbol in 0..3 {
+            table
+                .insert(PositionRow {
+                    account,
+                    symbol,
+                    qty: 0,
+                    note: "note".to_string(),
+                })
+                .unwrap();
+        }
+    }
+    table
+}
+
+#[tokio::test]
+async fn update_by_tuple_primary_key() {
+    let table = position_table();
+    table
+        .update_qty_by_key(QtyByKeyQuery { qty: 10 }, (1, 2))
+        .await
+        .unwrap();
+    table
+        .update_qty_by_key(QtyByKeyQuery { qty: 20 }, PositionPrimaryKey(2, 1))
+        .await
+        .unwrap();
+
+    assert_eq!(table.select((1, 2)).unwrap().qty, 10);
+    assert_eq!(table.select((2, 1)).unwrap().qty, 20);
+    assert_eq!(table.select((1, 1)).unwrap().qty, 0);
+    assert!(
+        table
+            .update_qty_by_key(QtyByKeyQuery { qty: 1 }, (5, 5))
+            .await
+            .is_err()
+    );
+}
+
+#[tokio::test]
+async fn update_unsized_by_tuple_primary_key() {
+    let table = position_table();
+    table
+        .update_note_by_key(
+            NoteByKeyQuery {
+                note: "much longer note than before".to_string(),
+            },
+            (0, 1),
+        )
+        .await
+        .unwrap();
+    table
+        .update_qty_by_key(QtyByKeyQuery { qty: 5 }, (0, 1))
+        .await
+        .unwrap();
+
+    let row = table.select((0, 1)).unwrap();
+    assert_eq!(row.note, "much longer note than before");
+    assert_eq!(row.qty, 5);
+    assert_eq!(table.select((0, 2)).unwrap().note, "note");
+    assert_eq!(table.select_all().execute().unwrap().len(), 9);
+}
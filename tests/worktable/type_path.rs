@@ -0,0 +1,123 @@
+use std::net::Ipv4Addr;
+
+use rkyv::{Archive, Deserialize, Serialize};
+use worktable::prelude::*;
+use worktable::worktable;
+
+#[derive(Archive, Clone, Copy, Debug, Deserialize, Serialize, PartialEq, PartialOrd, MemStat)]
+#[rkyv(
+    compare(PartialEq),
+    derive(Debug),
+    archive_bounds(T::Archived: core::fmt::Debug)
+)]
+pub struct Amount<T>(T);
+
+worktable! (
+    name: TypePath,
+    columns: {
+        id: u64 primary_key autoincrement,
+        hash: [u8; 32],
+        addr: std::net::Ipv4Addr,
+        payload: Vec<u8>,
+        amount: Amount<u64>,
+        comment: Option<String>,
+    },
+    indexes: {
+        hash_idx: hash unique,
+    },
+    queries: {
+        update: {
+            CommentById(comment) by id,
+            AddrByHash(addr) by hash,
+        },
+        delete: {
+            ByHash() by hash,
+        }
+    }
+);
+
+fn row(i: u8) -> TypePathRow {
+    TypePathRow {
+        id: i as u64,
+        hash: [i; 32],
+        addr: Ipv4Addr::new(127, 0, 0, i),
+        payload: vec![i; i as usize],
+        amount: Amount(i as u64 * 100),
+        comment: None,
+    }
+}
+
+#[test]
+fn insert_and_select() {
+    let table = TypePathWorkTable::default();
+    let row = row(1);
+    let pk = table.insert(row.clone()).unwrap();
+
+    assert_eq!(table.select(pk).unwrap(), row);
+    assert_eq!(table.select_by_hash([1; 32]).unwrap(), row);
+    assert!(table.select_by_hash([2; 32]).is_none());
+}
+
+#[tokio::test]
+async fn update_by_unique_index() {
+    let table = TypePathWorkTable::default();
+    for i in 0..10 {
+        table.insert(row(i)).unwrap();
+    }
+
+    table
+        .update_addr_by_hash(
+            AddrByHashQuery {
+                addr: Ipv4Addr::new(10, 0, 0, 1),
+            },
+            [5; 32],
+        )
+        .await
+        .unwrap();
+
+    let updated = table.select_by_hash([5; 32]).unwrap();
+    assert_eq!(updated.addr, Ipv4Addr::new(10, 0, 0, 1));
+    assert_eq!(updated.payload, vec![5; 5]);
+    assert_eq!(table.select(6).unwrap(), row(6));
+}
+
+#[tokio::test]
+async fn update_optional_string() {
+    let table = TypePathWorkTable::default();
+    for i in 0..10 {
+        table.insert(row(i)).unwrap();
+    }
+
+    table
+        .update_comment_by_id(
+            CommentByIdQuery {
+                comment: Some("some long comment".to_string()),
+            },
+            TypePathPrimaryKey::from(3),
+        )
+        .await
+        .unwrap();
+    let updated = table.select(3).unwrap();
+    assert_eq!(updated.comment, Some("some long comment".to_string()));
+    assert_eq!(updated.amount, Amount(300));
+
+    let mut new_row = row(4);
+    new_row.comment = Some("full row update".to_string());
+    table.update(new_row.clone()).await.unwrap();
+    assert_eq!(table.select(4).unwrap(), new_row);
+    assert_eq!(table.select_by_hash([4; 32]).unwrap(), new_row);
+}
+
+#[tokio::test]
+async fn delete_by_unique_index() {
+    let table = TypePathWorkTable::default();
+    for i in 0..10 {
+        table.insert(row(i)).unwrap();
+    }
+
+    table.delete_by_hash([7; 32]).await.unwrap();
+
+    assert!(table.select(7).is_none());
+    assert!(table.select_by_hash([7; 32]).is_none());
+    assert_eq!(table.select_all().execute().unwrap().len(), 9);
+}
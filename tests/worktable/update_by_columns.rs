@@ -0,0 +1,166 @@
+use worktable::prelude::*;
+use worktable::worktable;
+
+worktable!(
+    name: Order,
+    columns: {
+        id: u64 primary_key autoincrement,
+        account: u32,
+        symbol: String,
+        status: u8,
+        qty: u64,
+        price: f64,
+    },
+    indexes: {
+        account_idx: account,
+    },
+    queries: {
+        update: {
+            StatusByAccountAndSymbol(status) by account, symbol,
+            QtyBySymbol(qty) by symbol,
+            StatusByQtyAndPrice(status) by qty, price,
+            SymbolByStatus(symbol) by status,
+        }
+    }
+);
+
+fn order_table() -> OrderWorkTable {
+    let table = OrderWorkTable::default();
+    for i in 0..30u64 {
+        table
+            .insert(OrderRow {
+                id: table.get_next_pk().into(),
+                account: (i % 3) as u32,
+                symbol: format!("SYM{}", i % 5),
+                status: 0,
+                qty: i % 2,
+                price: (i % 4) as f64,
+            })
+            .unwrap();
+    }
+    table
+}
+
+#[tokio::test]
+async fn update_by_indexed_and_non_indexed_columns() {
+    let table = order_table();
+    table
+        .update_status_by_account_and_symbol(
+            StatusByAccountAndSymbolQuery { status: 1 },
+            (1, "SYM2".to_string()),
+        )
+        .await
+        .unwrap();
+
+    let updated = table
+        .select_all()
+        .execute()
+        .unwrap()
+        .into_iter()
+        .filter(|r| r.status == 1)
+        .collect::<Vec<_>>();
+    assert_eq!(updated.len(), 2);
+    assert!(updated.iter().all(|r| r.account == 1 && r.symbol == "SYM2"));
+}
+
+#[tokio::test]
+async fn update_by_non_indexed_column() {
+    let table = order_table();
+    table
+        .update_qty_by_symbol(QtyBySymbolQuery { qty: 100 }, "SYM3".to_string())
+        .await
+        .unwrap();
+
+    let rows = table.select_all().execute().unwrap();
+    assert_eq!(rows.iter().filter(|r| r.qty == 100).count(), 6);
+    assert!(
+        rows.iter()
+            .filter(|r| r.symbol == "SYM3")
+            .all(|r| r.qty == 100)
+    );
+
+    // Nothing is updated if there is no row with the value.
+    table
+        .update_qty_by_symbol(QtyBySymbolQuery { qty: 200 }, "SYM9".to_string())
+        .await
+        .unwrap();
+    assert!(
+        table
+            .select_all()
+            .execute()
+            .unwrap()
+            .iter()
+            .all(|r| r.qty != 200)
+    );
+}
+
+#[tokio::test]
+async fn update_by_non_indexed_columns_with_float() {
+    let table = order_table();
+    table
+        .update_status_by_qty_and_price(StatusByQtyAndPriceQuery { status: 7 }, (1, 3.0))
+        .await
+        .unwrap();
+
+    let rows = table.select_all().execute().unwrap();
+    assert_eq!(rows.iter().filter(|r| r.status == 7).count(), 7);
+    assert!(
+        rows.iter()
+            .filter(|r| r.status == 7)
+            .all(|r| r.qty == 1 && r.price == 3.0)
+    );
+}
+
+#[tokio::test]
+async fn update_unsized_by_non_indexed_column() {
+    let table = order_table();
+    table
+        .update_status_by_account_and_symbol(
+            StatusByAccountAndSymbolQuery { status: 3 },
+            (0, "SYM0".to_string()),
+        )
+        .await
+        .unwrap();
+    table
+        .update_symbol_by_status(
+            SymbolByStatusQuery {
+                symbol: "RENAMED_SYMBOL".to_string(),
+            },
+            3,
+        )
+        .await
+        .unwrap();
+
+    let rows = table.select_all().execute().unwrap();
+    assert_eq!(rows.len(), 30);
+    let renamed = rows
+        .iter()
+        .filter(|r| r.symbol == "RENAMED_SYMBOL")
+        .collect::<Vec<_>>();
+    assert_eq!(renamed.len(), 2);
+    assert!(renamed.iter().all(|r| r.status == 3 && r.account == 0));
+    assert_eq!(table.select_by_account(0).execute().unwrap().len(), 10);
+}
+
+#[tokio::test]
+async fn update_by_columns_releases_locks() {
+    let table = order_table();
+    for status in 0..3 {
+        table
+            .update_qty_by_symbol(QtyBySymbolQuery { qty: status }, "SYM1".to_string())
+            .await
+            .unwrap();
+        table
+            .update_status_by_account_and_symbol(
+                StatusByAccountAndSymbolQuery {
+                    status: status as u8,
+                },
+                (1, "SYM1".to_string()),
+            )
+            .await
+            .unwrap();
+    }
+    // Rows must be unlocked, so delete doesn't wait forever.
+    table.delete(2.into()).await.unwrap();
+    assert!(table.select(2).is_none());
+}
@@ -0,0 +1,168 @@
+use std::sync::Arc;
+
+use worktable::prelude::*;
+use worktable::worktable;
+
+worktable!(
+    name: Account,
+    columns: {
+        id: u64 primary_key,
+        email: String,
+        group: u32,
+        balance: i64,
+    },
+    indexes: {
+        email_idx: email unique,
+        group_idx: group,
+    },
+    queries: {
+        update: {
+            BalanceById(balance) by id,
+        }
+    }
+);
+
+fn account_table() -> AccountWorkTable {
+    let table = AccountWorkTable::default();
+    for id in 0..10 {
+        table
+            .insert(AccountRow {
+                id,
+                email: format!("user{id}@mail.com"),
+                group: (id % 2) as u32,
+                balance: id as i64 * 10,
+            })
+            .unwrap();
+    }
+    table
+}
+
+#[tokio::test]
+async fn update_primary_key() {
+    let table = account_table();
+    table.update_primary_key(3, 100).await.unwrap();
+
+    assert!(table.select(3).is_none());
+    let row = table.select(100).unwrap();
+    assert_eq!(row.email, "user3@mail.com");
+    assert_eq!(row.balance, 30);
+    assert_eq!(
+        table
+            .select_by_email("user3@mail.com".to_string())
+            .unwrap()
+            .id,
+        100
+    );
+    let group = table.select_by_group(1).execute().unwrap();
+    assert_eq!(group.len(), 5);
+    assert!(group.iter().any(|r| r.id == 100));
+    assert!(group.iter().all(|r| r.id != 3));
+    assert_eq!(table.count(), 10);
+
+    // Row is updated by the new key.
+    table
+        .update_balance_by_id(BalanceByIdQuery { balance: 1 }, 100)
+        .await
+        .unwrap();
+    assert_eq!(table.select(100).unwrap().balance, 1);
+    assert!(
+        table
+            .update_balance_by_id(BalanceByIdQuery { balance: 1 }, 3)
+            .await
+            .is_err()
+    );
+}
+
+#[tokio::test]
+async fn update_primary_key_to_existing() {
+    let table = account_table();
+    let res = table.update_primary_key(3, 4).await;
+    assert!(matches!(res, Err(WorkTableError::AlreadyExists(_))));
+
+    assert_eq!(table.select(3).unwrap().email, "user3@mail.com");
+    assert_eq!(table.select(4).unwrap().email, "user4@mail.com");
+    assert_eq!(table.count(), 10);
+    assert_eq!(table.select_by_group(1).execute().unwrap().len(), 5);
+
+    // Locks are released, so both keys can be used again.
+    table.update_primary_key(3, 30).await.unwrap();
+    table.update_primary_key(4, 3).await.unwrap();
+    assert_eq!(table.select(30).unwrap().email, "user3@mail.com");
+    assert_eq!(table.select(3).unwrap().email, "user4@mail.com");
+    assert!(table.select(4).is_none());
+}
+
+#[tokio::test]
+async fn update_primary_key_not_found() {
+    let table = account_table();
+    let res = table.update_primary_key(42, 43).await;
+    assert!(matches!(res, Err(WorkTableError::NotFound)));
+    assert!(table.select(43).is_none());
+
+    let res = table.update_primary_key(42, 42).await;
+    assert!(matches!(res, Err(WorkTableError::NotFound)));
+    table.update_primary_key(5, 5).await.unwrap();
+    assert!(table.select(5).is_some());
+}
+
+#[test]
+fn reinsert_with_other_primary_key() {
+    let table = account_table();
+    let row_old = table.select(4).unwrap();
+    let mut row_new = row_old.clone();
+    row_new.id = 200;
+    let res = table.reinsert(row_old, row_new);
+    assert!(matches!(res, Err(WorkTableError::PrimaryUpdateTry)));
+    assert!(table.select(4).is_some());
+    assert!(table.select(200).is_none());
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 3)]
+async fn update_primary_key_with_parallel_updates() {
+    let table = Arc::new(account_table());
+    let shared = table.clone();
+    let h = tokio::spawn(async move {
+        for i in 0..100u64 {
+            let (old, new) = if i % 2 == 0 { (1, 1001) } else { (1001, 1) };
+            shared.update_primary_key(old, new).await.unwrap();
+        }
+    });
+    for i in 0..1_000 {
+        table
+            .update_balance_by_id(BalanceByIdQuery { balance: i }, 2)
+            .await
+            .unwrap();
+    }
+    h.await.unwrap();
+
+    assert_eq!(table.select(1).unwrap().email, "user1@mail.com");
+    assert!(table.select(1001).is_none());
+    assert_eq!(table.select(2).unwrap().balance, 999);
+    assert_eq!(table.count(), 10);
+}
+
+worktable!(
+    name: PositionMove,
+    columns: {
+        account: u32 primary_key,
+        symbol: u64 primary_key,
+        qty: i64,
+    },
+);
+
+#[tokio::test]
+async fn update_tuple_primary_key() {
+    let table = PositionMoveWorkTable::default();
+    table
+        .insert(PositionMoveRow {
+            account: 1,
+            symbol: 2,
+            qty: 10,
+        })
+        .unwrap();
+    table.update_primary_key((1, 2), (3, 4)).await.unwrap();
+
+    assert!(table.select((1, 2)).is_none());
+    let row = table.select((3, 4)).unwrap();
+    assert_eq!((row.account, row.symbol, row.qty), (3, 4, 10));
+}
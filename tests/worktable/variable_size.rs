@@ -0,0 +1,171 @@
+use rkyv::{Archive, Deserialize, Serialize};
+use worktable::prelude::*;
+use worktable::worktable;
+
+#[derive(
+    Archive,
+    Clone,
+    Debug,
+    Default,
+    Deserialize,
+    Eq,
+    Hash,
+    MemStat,
+    Ord,
+    PartialEq,
+    PartialOrd,
+    Serialize,
+    SizeMeasure,
+    VariableSizeMeasure,
+)]
+#[rkyv(compare(PartialEq), derive(Debug, PartialEq, Eq, PartialOrd, Ord))]
+pub struct Name {
+    first: String,
+    last: String,
+}
+
+impl Name {
+    fn new(first: &str, last: &str) -> Self {
+        Self {
+            first: first.to_string(),
+            last: last.to_string(),
+        }
+    }
+}
+
+impl ArchivedSize for ArchivedName {
+    fn archived_size(&self) -> usize {
+        self.first.archived_size() + self.last.archived_size()
+    }
+}
+
+worktable! (
+    name: VariableSize,
+    columns: {
+        id: u64 primary_key autoincrement,
+        value: u64,
+        payload: Vec<u8>,
+        blob: Box<[u8]>,
+        name: Name unsized,
+    },
+    indexes: {
+        value_idx: value unique,
+        payload_idx: payload,
+        name_idx: name unique,
+    },
+    queries: {
+        update: {
+            PayloadById(payload) by id,
+            BlobByValue(blob) by value,
+            NameById(name) by id,
+        }
+    }
+);
+
+fn row(table: &VariableSizeWorkTable, i: u8) -> VariableSizeRow {
+    VariableSizeRow {
+        id: table.get_next_pk().into(),
+        value: i as u64,
+        payload: vec![i; 4],
+        blob: vec![i; 4].into_boxed_slice(),
+        name: Name::new("name", &i.to_string()),
+    }
+}
+
+#[tokio::test]
+async fn update_full_row_reinserts() {
+    let table = VariableSizeWorkTable::default();
+    let row = row(&table, 1);
+    let pk = table.insert(row.clone()).unwrap();
+    let first_link = table.0.pk_map.get(&pk).unwrap().get().value;
+
+    let mut new_row = row.clone();
+    new_row.payload = vec![2; 256];
+    new_row.name = Name::new("much longer first name", "much longer last name");
+    table.update(new_row.clone()).await.unwrap();
+
+    let link = table.0.pk_map.get(&pk).unwrap().get().value;
+    assert_ne!(link, first_link);
+    assert_eq!(table.select(pk).unwrap(), new_row);
+    assert_eq!(table.select_by_name(new_row.name.clone()).unwrap(), new_row);
+    assert!(table.select_by_name(row.name).is_none());
+}
+
+#[tokio::test]
+async fn update_vec_by_pk() {
+    let table = VariableSizeWorkTable::default();
+    for i in 0..10 {
+        table.insert(row(&table, i)).unwrap();
+    }
+
+    table
+        .update_payload_by_id(
+            PayloadByIdQuery {
+                payload: vec![42; 100],
+            },
+            VariableSizePrimaryKey::from(3),
+        )
+        .await
+        .unwrap();
+
+    let updated = table.select(3).unwrap();
+    assert_eq!(updated.payload, vec![42; 100]);
+    assert_eq!(updated.name, Name::new("name", "3"));
+    let by_payload = table.select_by_payload(vec![42; 100]).execute().unwrap();
+    assert_eq!(by_payload, vec![updated]);
+    assert!(
+        table
+            .select_by_payload(vec![3; 4])
+            .execute()
+            .unwrap()
+            .is_empty()
+    );
+    for i in (0..10).filter(|i| *i != 3) {
+        assert_eq!(table.select(i).unwrap().payload, vec![i as u8; 4]);
+    }
+}
+
+#[tokio::test]
+async fn update_boxed_slice_by_unique() {
+    let table = VariableSizeWorkTable::default();
+    for i in 0..10 {
+        table.insert(row(&table, i)).unwrap();
+    }
+
+    table
+        .update_blob_by_value(
+            BlobByValueQuery {
+                blob: vec![1; 33].into_boxed_slice(),
+            },
+            5,
+        )
+        .await
+        .unwrap();
+
+    let updated = table.select_by_value(5).unwrap();
+    assert_eq!(updated.blob, vec![1; 33].into_boxed_slice());
+    assert_eq!(updated.payload, vec![5; 4]);
+    assert_eq!(table.select_all().execute().unwrap().len(), 10);
+}
+
+#[tokio::test]
+async fn update_unsized_struct_by_pk() {
+    let table = VariableSizeWorkTable::default();
+    for i in 0..10 {
+        table.insert(row(&table, i)).unwrap();
+    }
+
+    let name = Name::new("some very long first name", "and some long last name");
+    table
+        .update_name_by_id(
+            NameByIdQuery { name: name.clone() },
+            VariableSizePrimaryKey::from(7),
+        )
+        .await
+        .unwrap();
+
+    let updated = table.select_by_name(name).unwrap();
+    assert_eq!(updated.id, 7);
+    assert_eq!(updated.payload, vec![7; 4]);
+    assert!(table.select_by_name(Name::new("name", "7")).is_none());
+}